@@ -1,7 +1,107 @@
-use std::io::{BufRead, Write};
-use crate::adapter::errors::AdapterError;
+use std::io::{self, BufRead, Write};
+use crate::adapter::currency::normalize_statement;
+use crate::adapter::errors::{map_parse_err, AdapterError, Location};
 pub(crate) use crate::adapter::statement::Statement;
 
+/// How readers deal with anomalies in the input.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Mode {
+    /// Fail on records that cannot be read; report other anomalies as warnings.
+    #[default]
+    Normal,
+    /// Fail on the first anomaly, including those reported as warnings otherwise.
+    Strict,
+    /// Skip records that cannot be read and keep them in the quarantine.
+    Lenient,
+}
+
+/// Options for [`Adapter::read_with`].
+#[derive(Debug, Clone, Default)]
+pub struct ReadOptions {
+    /// How anomalies are handled; strict mode also rejects unknown currency codes.
+    pub mode: Mode,
+    /// MT940 text uses the SWIFT Cyrillic transliteration and is read back into Cyrillic.
+    pub transliterated: bool,
+}
+
+/// A record skipped in [`Mode::Lenient`], as found in the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rejected {
+    /// Where the record starts, when known.
+    pub location: Option<Location>,
+    /// Source text of the record.
+    pub raw: String,
+}
+
+/// Statements read by [`Adapter::read_with`], with the anomalies found on the way.
+#[derive(Debug, Default)]
+pub struct ReadReport {
+    /// The statements read.
+    pub statements: Vec<Statement>,
+    /// Anomalies the reader worked around, with their location when known.
+    pub warnings: Vec<AdapterError>,
+    /// Records skipped in lenient mode.
+    pub rejected: Vec<Rejected>,
+    mode: Mode,
+}
+
+impl ReadReport {
+    /// An empty report for reading in `mode`.
+    pub fn new(mode: Mode) -> Self {
+        ReadReport { mode, ..Default::default() }
+    }
+
+    /// Whether records that cannot be read are skipped rather than failing the read.
+    pub fn is_lenient(&self) -> bool {
+        self.mode == Mode::Lenient
+    }
+
+    /// Reports an anomaly the reader can work around: an error in strict mode, a warning otherwise.
+    pub fn warn(&mut self, e: AdapterError) -> Result<(), AdapterError> {
+        if self.mode == Mode::Strict {
+            return Err(e);
+        }
+        self.warnings.push(e);
+        Ok(())
+    }
+
+    /// Reports a record that cannot be read. In lenient mode this is a warning
+    /// and the caller skips the record, handing it to [`ReadReport::quarantine`];
+    /// otherwise it is an error.
+    pub fn reject(&mut self, e: AdapterError) -> Result<(), AdapterError> {
+        if self.mode != Mode::Lenient {
+            return Err(e);
+        }
+        self.warnings.push(e);
+        Ok(())
+    }
+
+    /// Keeps the source text of a skipped record.
+    pub fn quarantine(&mut self, location: Option<Location>, raw: impl Into<String>) {
+        self.rejected.push(Rejected { location, raw: raw.into() });
+    }
+
+    /// Normalizes the currency codes of `st`; unknown codes are reported with [`ReadReport::warn`].
+    pub fn normalize(&mut self, st: &mut Statement) -> Result<(), AdapterError> {
+        if let Err(e) = normalize_statement(st, true) {
+            self.warn(map_parse_err(e))?;
+            normalize_statement(st, false).map_err(map_parse_err)?;
+        }
+        Ok(())
+    }
+
+    /// Writes the quarantined records to `sink`, each on its own lines.
+    pub fn write_quarantine<W: Write>(&self, mut sink: W) -> io::Result<()> {
+        for r in &self.rejected {
+            sink.write_all(r.raw.as_bytes())?;
+            if !r.raw.ends_with('\n') {
+                sink.write_all(b"\n")?;
+            }
+        }
+        Ok(())
+    }
+}
+
 /// Defines a common interface for reading and writing financial statements
 /// in different data formats.
 pub trait Adapter {
@@ -10,4 +110,32 @@ pub trait Adapter {
 
     /// Writes the provided [`Statement`] to the given output stream.
     fn write_to<W: Write>(writer: W, st: &Statement) -> Result<(), AdapterError>;
+
+    /// Reads every [`Statement`] contained in the input.
+    ///
+    /// Formats without a notion of several statements per file yield exactly one.
+    fn read_all<R: BufRead>(reader: R) -> Result<Vec<Statement>, AdapterError> {
+        Ok(vec![Self::read_from(reader)?])
+    }
+
+    /// Reads every [`Statement`] like [`Adapter::read_all`], handling anomalies
+    /// as set by `options` and reporting them.
+    fn read_with<R: BufRead>(reader: R, options: &ReadOptions) -> Result<ReadReport, AdapterError> {
+        let mut report = ReadReport::new(options.mode);
+        for mut st in Self::read_all(reader)? {
+            report.normalize(&mut st)?;
+            report.statements.push(st);
+        }
+        Ok(report)
+    }
+
+    /// Writes a batch of statements to the given output stream.
+    ///
+    /// By default each statement is written one after another with [`Adapter::write_to`].
+    fn write_all<W: Write>(mut writer: W, statements: &[Statement]) -> Result<(), AdapterError> {
+        for st in statements {
+            Self::write_to(&mut writer, st)?;
+        }
+        Ok(())
+    }
 }
@@ -0,0 +1,163 @@
+use crate::adapter::money::Money;
+use crate::adapter::statement::Statement;
+
+/// An ISO 4217 currency.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Currency {
+    /// Alphabetic code, e.g. `EUR`.
+    pub code: &'static str,
+    /// Numeric code, e.g. `978`.
+    pub numeric: u16,
+    /// Number of decimals; `None` for units without minor units (e.g. gold).
+    pub minor_units: Option<u8>,
+}
+
+const fn c(code: &'static str, numeric: u16, minor_units: u8) -> Currency {
+    Currency { code, numeric, minor_units: Some(minor_units) }
+}
+
+const fn n(code: &'static str, numeric: u16) -> Currency {
+    Currency { code, numeric, minor_units: None }
+}
+
+/// Active ISO 4217 currencies and funds.
+const CURRENCIES: &[Currency] = &[
+    c("AED", 784, 2), c("AFN", 971, 2), c("ALL", 8, 2), c("AMD", 51, 2), c("ANG", 532, 2),
+    c("AOA", 973, 2), c("ARS", 32, 2), c("AUD", 36, 2), c("AWG", 533, 2), c("AZN", 944, 2),
+    c("BAM", 977, 2), c("BBD", 52, 2), c("BDT", 50, 2), c("BGN", 975, 2), c("BHD", 48, 3),
+    c("BIF", 108, 0), c("BMD", 60, 2), c("BND", 96, 2), c("BOB", 68, 2), c("BOV", 984, 2),
+    c("BRL", 986, 2), c("BSD", 44, 2), c("BTN", 64, 2), c("BWP", 72, 2), c("BYN", 933, 2),
+    c("BZD", 84, 2), c("CAD", 124, 2), c("CDF", 976, 2), c("CHE", 947, 2), c("CHF", 756, 2),
+    c("CHW", 948, 2), c("CLF", 990, 4), c("CLP", 152, 0), c("CNY", 156, 2), c("COP", 170, 2),
+    c("COU", 970, 2), c("CRC", 188, 2), c("CUP", 192, 2), c("CVE", 132, 2), c("CZK", 203, 2),
+    c("DJF", 262, 0), c("DKK", 208, 2), c("DOP", 214, 2), c("DZD", 12, 2), c("EGP", 818, 2),
+    c("ERN", 232, 2), c("ETB", 230, 2), c("EUR", 978, 2), c("FJD", 242, 2), c("FKP", 238, 2),
+    c("GBP", 826, 2), c("GEL", 981, 2), c("GHS", 936, 2), c("GIP", 292, 2), c("GMD", 270, 2),
+    c("GNF", 324, 0), c("GTQ", 320, 2), c("GYD", 328, 2), c("HKD", 344, 2), c("HNL", 340, 2),
+    c("HTG", 332, 2), c("HUF", 348, 2), c("IDR", 360, 2), c("ILS", 376, 2), c("INR", 356, 2),
+    c("IQD", 368, 3), c("IRR", 364, 2), c("ISK", 352, 0), c("JMD", 388, 2), c("JOD", 400, 3),
+    c("JPY", 392, 0), c("KES", 404, 2), c("KGS", 417, 2), c("KHR", 116, 2), c("KMF", 174, 0),
+    c("KPW", 408, 2), c("KRW", 410, 0), c("KWD", 414, 3), c("KYD", 136, 2), c("KZT", 398, 2),
+    c("LAK", 418, 2), c("LBP", 422, 2), c("LKR", 144, 2), c("LRD", 430, 2), c("LSL", 426, 2),
+    c("LYD", 434, 3), c("MAD", 504, 2), c("MDL", 498, 2), c("MGA", 969, 2), c("MKD", 807, 2),
+    c("MMK", 104, 2), c("MNT", 496, 2), c("MOP", 446, 2), c("MRU", 929, 2), c("MUR", 480, 2),
+    c("MVR", 462, 2), c("MWK", 454, 2), c("MXN", 484, 2), c("MXV", 979, 2), c("MYR", 458, 2),
+    c("MZN", 943, 2), c("NAD", 516, 2), c("NGN", 566, 2), c("NIO", 558, 2), c("NOK", 578, 2),
+    c("NPR", 524, 2), c("NZD", 554, 2), c("OMR", 512, 3), c("PAB", 590, 2), c("PEN", 604, 2),
+    c("PGK", 598, 2), c("PHP", 608, 2), c("PKR", 586, 2), c("PLN", 985, 2), c("PYG", 600, 0),
+    c("QAR", 634, 2), c("RON", 946, 2), c("RSD", 941, 2), c("RUB", 643, 2), c("RWF", 646, 0),
+    c("SAR", 682, 2), c("SBD", 90, 2), c("SCR", 690, 2), c("SDG", 938, 2), c("SEK", 752, 2),
+    c("SGD", 702, 2), c("SHP", 654, 2), c("SLE", 925, 2), c("SOS", 706, 2), c("SRD", 968, 2),
+    c("SSP", 728, 2), c("STN", 930, 2), c("SVC", 222, 2), c("SYP", 760, 2), c("SZL", 748, 2),
+    c("THB", 764, 2), c("TJS", 972, 2), c("TMT", 934, 2), c("TND", 788, 3), c("TOP", 776, 2),
+    c("TRY", 949, 2), c("TTD", 780, 2), c("TWD", 901, 2), c("TZS", 834, 2), c("UAH", 980, 2),
+    c("UGX", 800, 0), c("USD", 840, 2), c("USN", 997, 2), c("UYI", 940, 0), c("UYU", 858, 2),
+    c("UYW", 927, 4), c("UZS", 860, 2), c("VED", 926, 2), c("VES", 928, 2), c("VND", 704, 0),
+    c("VUV", 548, 0), c("WST", 882, 2), c("XAF", 950, 0), c("XCD", 951, 2), c("XOF", 952, 0),
+    c("XPF", 953, 0), c("YER", 886, 2), c("ZAR", 710, 2), c("ZMW", 967, 2), c("ZWG", 924, 2),
+    n("XAG", 961), n("XAU", 959), n("XBA", 955), n("XBB", 956), n("XBC", 957), n("XBD", 958),
+    n("XDR", 960), n("XPD", 964), n("XPT", 962), n("XSU", 994), n("XTS", 963), n("XUA", 965),
+    n("XXX", 999),
+];
+
+/// Withdrawn codes still found in exports, with their replacement.
+/// The Russian rouble before 1998 (`RUR`, `810`) is used in Russian account numbers to this day.
+const LEGACY: &[(&str, &str)] = &[("RUR", "RUB"), ("810", "RUB")];
+
+/// Looks up an alphabetic or numeric code, legacy codes included.
+pub fn find(code: &str) -> Option<&'static Currency> {
+    let code = code.trim().to_ascii_uppercase();
+    let code = LEGACY.iter().find(|(old, _)| *old == code).map_or(code.as_str(), |(_, new)| new);
+    if code.len() == 3 && code.bytes().all(|b| b.is_ascii_digit()) {
+        let numeric: u16 = code.parse().ok()?;
+        return CURRENCIES.iter().find(|c| c.numeric == numeric);
+    }
+    CURRENCIES.iter().find(|c| c.code == code)
+}
+
+/// Canonical alphabetic code of `code`. Unknown codes are an error in strict
+/// mode and are kept as given otherwise.
+pub fn normalize(code: &str, strict: bool) -> Result<String, String> {
+    match find(code) {
+        Some(c) => Ok(c.code.to_string()),
+        None if strict => Err(format!("unknown currency `{code}`")),
+        None => Ok(code.to_string()),
+    }
+}
+
+/// Number of decimals of a currency; 2 for unknown codes.
+pub fn minor_units(code: &str) -> Option<u8> {
+    match find(code) {
+        Some(c) => c.minor_units,
+        None => Some(Money::DEFAULT_EXPONENT),
+    }
+}
+
+/// `amount` with the number of decimals of `code`, unless that would drop digits.
+pub fn scale(amount: Money, code: &str) -> Money {
+    minor_units(code).and_then(|e| amount.rescale(e).ok()).unwrap_or(amount)
+}
+
+/// Normalizes every currency code of a statement; see [`normalize`].
+pub fn normalize_statement(st: &mut Statement, strict: bool) -> Result<(), String> {
+    let fix = |code: &mut String| -> Result<(), String> {
+        if !code.is_empty() {
+            *code = normalize(code, strict)?;
+        }
+        Ok(())
+    };
+    if let Some(ccy) = &mut st.account_currency {
+        fix(ccy)?;
+    }
+    for b in &mut st.balances {
+        fix(&mut b.currency)?;
+    }
+    for e in &mut st.entries {
+        fix(&mut e.currency)?;
+        let amounts = e.instructed_amount.iter_mut()
+            .chain(e.transaction_amount.iter_mut())
+            .chain(e.charges.iter_mut().map(|c| &mut c.amount))
+            .chain(e.batch.iter_mut().flat_map(|b| b.total_amount.iter_mut()))
+            .chain(e.transactions.iter_mut().flat_map(|tx| tx.amount.iter_mut()));
+        for a in amounts {
+            fix(&mut a.currency)?;
+        }
+        if let Some(x) = &mut e.exchange {
+            fix(&mut x.source_currency)?;
+            for ccy in x.target_currency.iter_mut().chain(x.unit_currency.iter_mut()) {
+                fix(ccy)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codes_and_legacy_codes() {
+        assert_eq!(find("eur").map(|c| c.numeric), Some(978));
+        assert_eq!(find("643").map(|c| c.code), Some("RUB"));
+        assert_eq!(find("810").map(|c| c.code), Some("RUB"));
+        assert_eq!(normalize("RUR", true).unwrap(), "RUB");
+        assert_eq!(normalize("008", true).unwrap(), "ALL");
+        assert!(normalize("ABC", true).is_err());
+        assert_eq!(normalize("ABC", false).unwrap(), "ABC");
+        for fund in ["BOV", "CHE", "CHW", "COU", "MXV", "USN", "UYW", "VED", "XSU", "XUA"] {
+            assert_eq!(normalize(fund, true).unwrap(), fund);
+        }
+        assert_eq!(minor_units("UYW"), Some(4));
+    }
+
+    #[test]
+    fn amounts_follow_minor_units() {
+        let amount = Money::parse("1000.00").unwrap();
+        assert_eq!(scale(amount, "JPY").to_string(), "1000");
+        assert_eq!(scale(amount, "KWD").to_string(), "1000.000");
+        assert_eq!(scale(Money::parse("1.5").unwrap(), "EUR").to_string(), "1.50");
+        assert_eq!(scale(Money::parse("1.25").unwrap(), "JPY").to_string(), "1.25");
+        assert_eq!(scale(Money::parse("1.125").unwrap(), "XAU").to_string(), "1.125");
+    }
+}
@@ -1,18 +1,168 @@
+use std::fmt;
+use std::io;
+
+/// Where in the input an error was found.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Location {
+    /// Position in a line-oriented input (MT940, CSV).
+    Line {
+        /// Line number, starting at 1.
+        line: usize,
+        /// Column in characters, starting at 1.
+        column: usize,
+    },
+    /// Path of an XML element, e.g. `/Document/BkToCstmrStmt/Stmt/Bal/Amt`.
+    Path(String),
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Location::Line { line, column } => write!(f, "line {line}, column {column}"),
+            Location::Path(path) => f.write_str(path),
+        }
+    }
+}
+
 /// Common error type for adapter operations such as parsing and writing.
 #[derive(Debug)]
 pub enum AdapterError {
-    /// Error occurred while parsing input data.
-    ParseError(String),
-    /// Error occurred while writing or serializing output data.
-    WriteError(String),
+    /// Reading the input or writing the output failed.
+    Io(io::Error),
+    /// A mandatory field is absent.
+    MissingField {
+        /// MT940 tag (e.g. `:62a:`), XML element or CSV column.
+        field: String,
+        /// Where the field was expected, when known.
+        location: Option<Location>,
+    },
+    /// A field has a value that cannot be read.
+    InvalidField {
+        /// MT940 tag, XML element or CSV column.
+        field: String,
+        /// The offending value.
+        value: String,
+        /// Why the value was rejected.
+        reason: String,
+        /// Where the value was found, when known.
+        location: Option<Location>,
+    },
+    /// The input is malformed in a way not tied to one field, e.g. broken XML.
+    Parse {
+        /// Description of the problem.
+        message: String,
+        /// Where the problem was found, when known.
+        location: Option<Location>,
+    },
+    /// The statement cannot be serialized into the output format.
+    Write(String),
+}
+
+impl AdapterError {
+    /// Error for a mandatory `field` that is absent.
+    pub fn missing(field: impl Into<String>) -> Self {
+        AdapterError::MissingField { field: field.into(), location: None }
+    }
+
+    /// Error for a `value` of `field` rejected for `reason`.
+    pub fn invalid(field: impl Into<String>, value: impl Into<String>, reason: impl fmt::Display) -> Self {
+        AdapterError::InvalidField { field: field.into(), value: value.into(), reason: reason.to_string(), location: None }
+    }
+
+    /// Sets the location of an error that has none yet.
+    pub fn at(mut self, at: Location) -> Self {
+        if let AdapterError::MissingField { location, .. }
+        | AdapterError::InvalidField { location, .. }
+        | AdapterError::Parse { location, .. } = &mut self
+        {
+            location.get_or_insert(at);
+        }
+        self
+    }
+
+    /// Attributes an error raised while reading `value` of `field` to that
+    /// field: a plain [`AdapterError::Parse`] becomes an
+    /// [`AdapterError::InvalidField`]; other errors only get the location.
+    pub fn in_field(self, field: &str, value: &str, location: Location) -> Self {
+        match self {
+            AdapterError::Parse { message, location: None } => AdapterError::invalid(field, value, message).at(location),
+            other => other.at(location),
+        }
+    }
+
+    /// Where the error was found, when known.
+    pub fn location(&self) -> Option<&Location> {
+        match self {
+            AdapterError::MissingField { location, .. }
+            | AdapterError::InvalidField { location, .. }
+            | AdapterError::Parse { location, .. } => location.as_ref(),
+            AdapterError::Io(_) | AdapterError::Write(_) => None,
+        }
+    }
 }
 
-/// Maps any displayable error into an [`AdapterError::ParseError`].
-pub fn map_parse_err<E: std::fmt::Display>(e: E) -> AdapterError {
-    AdapterError::ParseError(e.to_string())
+impl fmt::Display for AdapterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AdapterError::Io(e) => write!(f, "I/O error: {e}")?,
+            AdapterError::MissingField { field, .. } => write!(f, "missing {field}")?,
+            AdapterError::InvalidField { field, value, reason, .. } => write!(f, "invalid {field} `{value}`: {reason}")?,
+            AdapterError::Parse { message, .. } => f.write_str(message)?,
+            AdapterError::Write(message) => write!(f, "cannot write output: {message}")?,
+        }
+        match self.location() {
+            Some(location) => write!(f, " at {location}"),
+            None => Ok(()),
+        }
+    }
 }
 
-/// Maps any displayable error into an [`AdapterError::WriteError`].
-pub fn map_write_err<E: std::fmt::Display>(e: E) -> AdapterError {
-    AdapterError::WriteError(e.to_string())
-}
\ No newline at end of file
+impl std::error::Error for AdapterError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            AdapterError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for AdapterError {
+    fn from(e: io::Error) -> Self {
+        AdapterError::Io(e)
+    }
+}
+
+/// Maps any displayable error into an [`AdapterError::Parse`] without location.
+pub fn map_parse_err<E: fmt::Display>(e: E) -> AdapterError {
+    AdapterError::Parse { message: e.to_string(), location: None }
+}
+
+/// Maps any displayable error into an [`AdapterError::Write`].
+pub fn map_write_err<E: fmt::Display>(e: E) -> AdapterError {
+    AdapterError::Write(e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_names_field_value_and_location() {
+        let e = map_parse_err("invalid date").in_field(":60F:", "C25x", Location::Line { line: 3, column: 6 });
+        assert_eq!(e.to_string(), "invalid :60F: `C25x`: invalid date at line 3, column 6");
+
+        let e = AdapterError::missing("Amt").at(Location::Path("/Document/Stmt/Bal".into()));
+        assert_eq!(e.to_string(), "missing Amt at /Document/Stmt/Bal");
+        // The innermost location wins.
+        let e = e.at(Location::Line { line: 1, column: 1 });
+        assert_eq!(e.location(), Some(&Location::Path("/Document/Stmt/Bal".into())));
+    }
+
+    #[test]
+    fn io_errors_keep_their_source() {
+        use std::error::Error;
+        let e = AdapterError::from(io::Error::new(io::ErrorKind::NotFound, "no such file"));
+        assert_eq!(e.to_string(), "I/O error: no such file");
+        assert!(e.source().is_some());
+    }
+}
@@ -0,0 +1,154 @@
+use crate::adapter::reference::mod97;
+
+/// IBAN length per country (SWIFT IBAN registry).
+const IBAN_LENGTHS: &[(&str, usize)] = &[
+    ("AD", 24), ("AE", 23), ("AL", 28), ("AT", 20), ("AZ", 28), ("BA", 20), ("BE", 16), ("BG", 22),
+    ("BH", 22), ("BI", 27), ("BR", 29), ("BY", 28), ("CH", 21), ("CR", 22), ("CY", 28), ("CZ", 24),
+    ("DE", 22), ("DJ", 27), ("DK", 18), ("DO", 28), ("EE", 20), ("EG", 29), ("ES", 24), ("FI", 18),
+    ("FK", 18), ("FO", 18), ("FR", 27), ("GB", 22), ("GE", 22), ("GI", 23), ("GL", 18), ("GR", 27),
+    ("GT", 28), ("HN", 28), ("HR", 21), ("HU", 28), ("IE", 22), ("IL", 23), ("IQ", 23), ("IS", 26),
+    ("IT", 27), ("JO", 30), ("KW", 30), ("KZ", 20), ("LB", 28), ("LC", 32), ("LI", 21), ("LT", 20),
+    ("LU", 20), ("LV", 21), ("LY", 25), ("MC", 27), ("MD", 24), ("ME", 22), ("MK", 19), ("MN", 20),
+    ("MR", 27), ("MT", 31), ("MU", 30), ("NI", 28), ("NL", 18), ("NO", 15), ("OM", 23), ("PK", 24),
+    ("PL", 28), ("PS", 29), ("PT", 25), ("QA", 29), ("RO", 24), ("RS", 22), ("RU", 33), ("SA", 24),
+    ("SC", 31), ("SD", 18), ("SE", 24), ("SI", 19), ("SK", 24), ("SM", 27), ("SO", 23), ("ST", 25),
+    ("SV", 28), ("TL", 23), ("TN", 24), ("TR", 26), ("UA", 29), ("VA", 22), ("VG", 24), ("XK", 20),
+    ("YE", 30),
+];
+
+/// ISO 3166-1 alpha-2 country codes, for the country part of a BIC.
+const COUNTRY_CODES: &str = "\
+AD AE AF AG AI AL AM AO AQ AR AS AT AU AW AX AZ BA BB BD BE BF BG BH BI BJ BL BM BN BO BQ BR BS \
+BT BV BW BY BZ CA CC CD CF CG CH CI CK CL CM CN CO CR CU CV CW CX CY CZ DE DJ DK DM DO DZ EC EE \
+EG EH ER ES ET FI FJ FK FM FO FR GA GB GD GE GF GG GH GI GL GM GN GP GQ GR GS GT GU GW GY HK HM \
+HN HR HT HU ID IE IL IM IN IO IQ IR IS IT JE JM JO JP KE KG KH KI KM KN KP KR KW KY KZ LA LB LC \
+LI LK LR LS LT LU LV LY MA MC MD ME MF MG MH MK ML MM MN MO MP MQ MR MS MT MU MV MW MX MY MZ NA \
+NC NE NF NG NI NL NO NP NR NU NZ OM PA PE PF PG PH PK PL PM PN PR PS PT PW PY QA RE RO RS RU RW \
+SA SB SC SD SE SG SH SI SJ SK SL SM SN SO SR SS ST SV SX SY SZ TC TD TF TG TH TJ TK TL TM TN TO \
+TR TT TV TW TZ UA UG UM US UY UZ VA VC VE VG VI VN VU WF WS XK YE YT ZA ZM ZW";
+
+/// Electronic format of an IBAN: without spaces and in upper case.
+pub fn normalize_iban(s: &str) -> String {
+    s.chars().filter(|c| !c.is_whitespace()).map(|c| c.to_ascii_uppercase()).collect()
+}
+
+/// Print format of an IBAN: groups of four characters separated by spaces.
+pub fn format_iban_print(s: &str) -> String {
+    let chars: Vec<char> = normalize_iban(s).chars().collect();
+    chars.chunks(4).map(|c| c.iter().collect::<String>()).collect::<Vec<_>>().join(" ")
+}
+
+/// Validates an IBAN in electronic or print format and returns its electronic format.
+pub fn validate_iban(s: &str) -> Result<String, String> {
+    let iban = normalize_iban(s);
+    let b = iban.as_bytes();
+    if b.len() < 5
+        || !b[..2].iter().all(u8::is_ascii_uppercase)
+        || !b[2..4].iter().all(u8::is_ascii_digit)
+        || !b.iter().all(u8::is_ascii_alphanumeric)
+    {
+        return Err(format!("`{s}` is not an IBAN"));
+    }
+    let country = &iban[..2];
+    match IBAN_LENGTHS.iter().find(|(c, _)| *c == country) {
+        None => return Err(format!("IBAN `{s}`: unknown country `{country}`")),
+        Some((_, len)) if *len != iban.len() => {
+            return Err(format!("IBAN `{s}`: {country} IBANs have {len} characters, not {}", iban.len()));
+        }
+        Some(_) => {}
+    }
+    let rotated = format!("{}{}", &iban[4..], &iban[..4]);
+    if mod97(&rotated) != Some(1) {
+        return Err(format!("IBAN `{s}` has wrong check digits"));
+    }
+    Ok(iban)
+}
+
+/// Returns `true` if `s` is a valid IBAN in electronic or print format.
+pub fn is_iban(s: &str) -> bool {
+    validate_iban(s).is_ok()
+}
+
+/// Whether `s` is shaped like an IBAN (country letters and check digits), valid or not.
+pub fn looks_like_iban(s: &str) -> bool {
+    let b = s.as_bytes();
+    b.len() > 4 && b[..2].iter().all(u8::is_ascii_alphabetic) && b[2..4].iter().all(u8::is_ascii_digit)
+}
+
+/// Checks an account identifier: one shaped like an IBAN must be a valid IBAN;
+/// other account numbers are not checked.
+pub fn check_account_id(s: &str) -> Result<(), String> {
+    if looks_like_iban(&normalize_iban(s)) {
+        validate_iban(s)?;
+    }
+    Ok(())
+}
+
+/// Account identifier as readers store it: a valid IBAN in electronic format,
+/// anything else unchanged.
+pub fn normalize_account_id(s: &str) -> String {
+    validate_iban(s).unwrap_or_else(|_| s.to_string())
+}
+
+/// Validates a BIC (ISO 9362): 4 letters bank code, ISO 3166 country code,
+/// 2 alphanumerics location code and an optional 3 alphanumerics branch code.
+pub fn validate_bic(s: &str) -> Result<(), String> {
+    let b = s.as_bytes();
+    if b.len() != 8 && b.len() != 11 {
+        return Err(format!("BIC `{s}` must have 8 or 11 characters"));
+    }
+    let alnum = |c: &u8| c.is_ascii_uppercase() || c.is_ascii_digit();
+    if !b[..4].iter().all(u8::is_ascii_uppercase) || !b[4..].iter().all(alnum) {
+        return Err(format!("`{s}` is not a BIC"));
+    }
+    let country = &s[4..6];
+    if !COUNTRY_CODES.split(' ').any(|c| c == country) {
+        return Err(format!("BIC `{s}`: unknown country `{country}`"));
+    }
+    Ok(())
+}
+
+/// Returns `true` if `s` is a valid BIC.
+pub fn is_bic(s: &str) -> bool {
+    validate_bic(s).is_ok()
+}
+
+/// Whether `s` is shaped like a BIC (8 or 11 characters starting with 4 letters), valid or not.
+pub fn looks_like_bic(s: &str) -> bool {
+    (s.len() == 8 || s.len() == 11) && s.bytes().take(4).all(|b| b.is_ascii_alphabetic()) && s.bytes().all(|b| b.is_ascii_alphanumeric())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn iban_check_digits_and_length() {
+        assert_eq!(validate_iban("DE89 3704 0044 0532 0130 00").unwrap(), "DE89370400440532013000");
+        assert!(is_iban("gb29nwbk60161331926819"));
+        assert!(validate_iban("DE88370400440532013000").unwrap_err().contains("check digits"));
+        assert!(validate_iban("DE8937040044053201300").unwrap_err().contains("22 characters"));
+        assert!(validate_iban("ZZ89370400440532013000").unwrap_err().contains("unknown country"));
+        assert!(!is_iban("40702810440000030888"));
+    }
+
+    #[test]
+    fn iban_formats() {
+        assert_eq!(format_iban_print("DE89370400440532013000"), "DE89 3704 0044 0532 0130 00");
+        assert_eq!(normalize_account_id("de89 3704 0044 0532 0130 00"), "DE89370400440532013000");
+        assert_eq!(normalize_account_id("ACC-1"), "ACC-1");
+        assert!(check_account_id("DE88 3704 0044 0532 0130 00").unwrap_err().contains("check digits"));
+        assert!(check_account_id("40702810440000030888").is_ok());
+    }
+
+    #[test]
+    fn bic_shape_and_country() {
+        assert!(is_bic("COBADEFF"));
+        assert!(is_bic("COBADEFFXXX"));
+        assert!(is_bic("SABRRUMM"));
+        assert!(validate_bic("COBADEF").is_err());
+        assert!(validate_bic("C0BADEFF").is_err());
+        assert!(validate_bic("COBAQQFF").unwrap_err().contains("unknown country"));
+        assert!(looks_like_bic("COBAQQFF") && !looks_like_bic("37040044"));
+    }
+}
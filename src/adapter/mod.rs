@@ -1,6 +1,21 @@
 /// Adapters for converting between different formats.
+#[allow(clippy::module_inception)]
 pub mod adapter;
+/// ISO 4217 currency codes and minor units.
+pub mod currency;
 /// Errors that can occur during parsing and conversion.
 pub mod errors;
+/// IBAN and BIC validation and normalization.
+pub mod identifier;
+/// Exact fixed-point money amounts.
+pub mod money;
+/// Payment reference validation (ISO 11649 creditor references).
+pub mod reference;
+/// Russian bank requisites: account control key, INN, KPP and BIK checks.
+pub mod requisites;
 /// Statement represent common transaction models.
-pub mod statement;
\ No newline at end of file
+pub mod statement;
+/// Bank transaction codes and the SWIFT/ISO 20022 mapping table.
+pub mod transaction_code;
+/// Statement consistency checks: balance reconciliation, currencies and dates.
+pub mod validation;
\ No newline at end of file
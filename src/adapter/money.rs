@@ -0,0 +1,320 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, Neg, Sub};
+use std::str::FromStr;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Exact fixed-point monetary amount.
+///
+/// The value is stored as an integer number of minor units together with the
+/// decimal exponent of the currency, so `100.50 EUR` is `10050` with exponent `2`.
+/// Two amounts are equal when they denote the same value, regardless of exponent.
+#[derive(Debug, Clone, Copy)]
+pub struct Money {
+    minor_units: i64,
+    exponent: u8,
+}
+
+impl Money {
+    /// Exponent used when the currency does not say otherwise.
+    pub const DEFAULT_EXPONENT: u8 = 2;
+
+    /// Largest number of decimals [`Money::parse`] accepts.
+    pub const MAX_EXPONENT: u8 = 18;
+
+    /// Creates an amount from minor units and the currency exponent.
+    pub fn new(minor_units: i64, exponent: u8) -> Self {
+        Money { minor_units, exponent }
+    }
+
+    /// Zero with the default exponent.
+    pub fn zero() -> Self {
+        Money::new(0, Self::DEFAULT_EXPONENT)
+    }
+
+    /// Amount in minor units (e.g. cents).
+    pub fn minor_units(&self) -> i64 {
+        self.minor_units
+    }
+
+    /// Number of decimal places of the minor unit.
+    pub fn exponent(&self) -> u8 {
+        self.exponent
+    }
+
+    /// Returns `true` if the amount is below zero.
+    pub fn is_negative(&self) -> bool {
+        self.minor_units < 0
+    }
+
+    /// Returns `true` if the amount is exactly zero.
+    pub fn is_zero(&self) -> bool {
+        self.minor_units == 0
+    }
+
+    /// Absolute value of the amount.
+    pub fn abs(&self) -> Self {
+        Money::new(self.minor_units.abs(), self.exponent)
+    }
+
+    /// Parses a decimal amount written with either `.` or `,` as the decimal separator.
+    ///
+    /// Whitespace (including non-breaking spaces used as thousands separators) is ignored,
+    /// and a leading `+` or `-` sign is accepted. The resulting exponent is the number of
+    /// fractional digits, but never less than [`Money::DEFAULT_EXPONENT`] and at most
+    /// [`Money::MAX_EXPONENT`].
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let cleaned: String = s.chars().filter(|c| !c.is_whitespace()).collect();
+        let (negative, digits) = match cleaned.as_bytes().first() {
+            Some(b'-') => (true, &cleaned[1..]),
+            Some(b'+') => (false, &cleaned[1..]),
+            _ => (false, cleaned.as_str()),
+        };
+
+        let (int_part, frac_part) = match digits.find(['.', ',']) {
+            Some(pos) => (&digits[..pos], &digits[pos + 1..]),
+            None => (digits, ""),
+        };
+
+        let all_digits = |p: &str| p.bytes().all(|b| b.is_ascii_digit());
+        if (int_part.is_empty() && frac_part.is_empty()) || !all_digits(int_part) || !all_digits(frac_part) {
+            return Err(format!("invalid amount `{s}`"));
+        }
+
+        let exponent = u8::try_from(frac_part.len())
+            .ok()
+            .filter(|&e| e <= Self::MAX_EXPONENT)
+            .ok_or_else(|| format!("too many decimals in `{s}`"))?
+            .max(Self::DEFAULT_EXPONENT);
+
+        let mut minor: i64 = 0;
+        let padded = frac_part.chars().chain(std::iter::repeat('0')).take(exponent as usize);
+        for c in int_part.chars().chain(padded) {
+            minor = minor
+                .checked_mul(10)
+                .and_then(|m| m.checked_add(i64::from(c as u8 - b'0')))
+                .ok_or_else(|| format!("amount out of range `{s}`"))?;
+        }
+
+        Ok(Money::new(if negative { -minor } else { minor }, exponent))
+    }
+
+    /// Converts the amount to another exponent.
+    ///
+    /// Fails if the conversion would drop non-zero digits or overflow.
+    pub fn rescale(&self, exponent: u8) -> Result<Self, String> {
+        match exponent.cmp(&self.exponent) {
+            Ordering::Equal => Ok(*self),
+            Ordering::Greater => 10i64
+                .checked_pow(u32::from(exponent - self.exponent))
+                .and_then(|f| self.minor_units.checked_mul(f))
+                .map(|m| Money::new(m, exponent))
+                .ok_or_else(|| format!("amount {self} out of range")),
+            Ordering::Less => match 10i64.checked_pow(u32::from(self.exponent - exponent)) {
+                Some(factor) if self.minor_units % factor == 0 => Ok(Money::new(self.minor_units / factor, exponent)),
+                // A factor beyond `i64` leaves only zero without dropping digits.
+                None if self.minor_units == 0 => Ok(Money::new(0, exponent)),
+                _ => Err(format!("amount {self} has more than {exponent} decimals")),
+            },
+        }
+    }
+
+    /// Formats the amount with exactly `exponent` decimals and the given separator.
+    pub fn format_with(&self, separator: char) -> String {
+        let sign = if self.is_negative() { "-" } else { "" };
+        let abs = self.minor_units.unsigned_abs();
+        if self.exponent == 0 {
+            return format!("{sign}{abs}");
+        }
+        let abs = u128::from(abs);
+        let (int, frac) = match 10u128.checked_pow(u32::from(self.exponent)) {
+            Some(factor) => (abs / factor, abs % factor),
+            None => (0, abs),
+        };
+        let width = self.exponent as usize;
+        format!("{sign}{int}{separator}{frac:0width$}")
+    }
+
+    /// Sum of two amounts at the larger exponent; fails instead of overflowing.
+    pub fn checked_add(self, other: Money) -> Result<Money, String> {
+        self.combine(other, i64::checked_add)
+    }
+
+    /// Difference of two amounts at the larger exponent; fails instead of overflowing.
+    pub fn checked_sub(self, other: Money) -> Result<Money, String> {
+        self.combine(other, i64::checked_sub)
+    }
+
+    /// Sum of all amounts, like [`Sum`] but failing instead of overflowing.
+    pub fn checked_sum(amounts: impl IntoIterator<Item = Money>) -> Result<Money, String> {
+        amounts.into_iter().try_fold(Money::zero(), Money::checked_add)
+    }
+
+    // Saturates where the exponents are too far apart for `i128`, which keeps the order.
+    fn normalized(&self, exponent: u8) -> i128 {
+        i128::from(self.minor_units).saturating_mul(10i128.saturating_pow(u32::from(exponent - self.exponent)))
+    }
+
+    fn combine(self, other: Money, op: fn(i64, i64) -> Option<i64>) -> Result<Money, String> {
+        let exponent = self.exponent.max(other.exponent);
+        let (a, b) = (self.rescale(exponent)?, other.rescale(exponent)?);
+        op(a.minor_units, b.minor_units)
+            .map(|m| Money::new(m, exponent))
+            .ok_or_else(|| format!("amount out of range combining {self} and {other}"))
+    }
+}
+
+impl Default for Money {
+    fn default() -> Self {
+        Money::zero()
+    }
+}
+
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.format_with('.'))
+    }
+}
+
+impl FromStr for Money {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Money::parse(s)
+    }
+}
+
+impl PartialEq for Money {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Money {}
+
+impl PartialOrd for Money {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Money {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let exponent = self.exponent.max(other.exponent);
+        self.normalized(exponent).cmp(&other.normalized(exponent))
+    }
+}
+
+/// Panics on overflow; use [`Money::checked_add`] for amounts read from input.
+impl Add for Money {
+    type Output = Money;
+
+    fn add(self, rhs: Money) -> Money {
+        self.checked_add(rhs).expect("money overflow")
+    }
+}
+
+/// Panics on overflow; use [`Money::checked_sub`] for amounts read from input.
+impl Sub for Money {
+    type Output = Money;
+
+    fn sub(self, rhs: Money) -> Money {
+        self.checked_sub(rhs).expect("money overflow")
+    }
+}
+
+impl Neg for Money {
+    type Output = Money;
+
+    fn neg(self) -> Money {
+        Money::new(-self.minor_units, self.exponent)
+    }
+}
+
+/// Panics on overflow; use [`Money::checked_sum`] for amounts read from input.
+impl Sum for Money {
+    fn sum<I: Iterator<Item = Money>>(iter: I) -> Money {
+        iter.fold(Money::zero(), Add::add)
+    }
+}
+
+impl Serialize for Money {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for Money {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Money::parse(&s).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_accepts_both_separators() {
+        assert_eq!(Money::parse("100,00").unwrap(), Money::new(10000, 2));
+        assert_eq!(Money::parse("100.5").unwrap(), Money::new(10050, 2));
+        assert_eq!(Money::parse("100,").unwrap(), Money::new(10000, 2));
+        assert_eq!(Money::parse("1 540.00").unwrap(), Money::new(154000, 2));
+        assert_eq!(Money::parse("-0.125").unwrap().exponent(), 3);
+    }
+
+    #[test]
+    fn parse_rejects_garbage() {
+        assert!(Money::parse("").is_err());
+        assert!(Money::parse("12a").is_err());
+        assert!(Money::parse("1.2.3").is_err());
+        assert!(Money::parse(",").is_err());
+    }
+
+    #[test]
+    fn format_uses_requested_separator() {
+        let m = Money::new(-123405, 2);
+        assert_eq!(m.to_string(), "-1234.05");
+        assert_eq!(m.format_with(','), "-1234,05");
+        assert_eq!(Money::new(500, 0).to_string(), "500");
+        assert_eq!(Money::new(1500, 3).to_string(), "1.500");
+    }
+
+    #[test]
+    fn rescale_keeps_value() {
+        let m = Money::new(10050, 2);
+        assert_eq!(m.rescale(3).unwrap().minor_units(), 100500);
+        assert!(m.rescale(0).is_err());
+        assert_eq!(Money::new(10000, 2).rescale(0).unwrap().minor_units(), 100);
+    }
+
+    #[test]
+    fn arithmetic_across_exponents() {
+        let a = Money::new(10000, 2);
+        let b = Money::new(1500, 3);
+        assert_eq!(a + b, Money::new(101500, 3));
+        assert_eq!(a - b, Money::parse("98.5").unwrap());
+        assert_eq!(-a, Money::parse("-100").unwrap());
+        let total: Money = [a, a, b].into_iter().sum();
+        assert_eq!(total.to_string(), "201.500");
+    }
+
+    #[test]
+    fn overflow_is_an_error() {
+        let tiny = Money::parse("0.000000000000000001").unwrap();
+        let balance = Money::parse("100.00").unwrap();
+        assert!(balance.checked_add(tiny).is_err());
+        assert!(balance.checked_sub(tiny).is_err());
+        assert!(Money::checked_sum([balance, tiny]).is_err());
+        assert_eq!(Money::checked_sum([balance, balance]).unwrap(), Money::parse("200").unwrap());
+        assert!(Money::parse("0.0000000000000000001").is_err());
+
+        let far = Money::new(1, 200);
+        assert!(far < balance);
+        assert_eq!(far.rescale(0).unwrap_err(), format!("amount {far} has more than 0 decimals"));
+        assert_eq!(Money::new(0, 200).rescale(2).unwrap(), Money::zero());
+        assert_eq!(Money::new(5, 25).format_with('.'), format!("0.{}5", "0".repeat(24)));
+    }
+}
@@ -0,0 +1,48 @@
+/// Prefix of an ISO 11649 structured creditor reference.
+const RF_PREFIX: &str = "RF";
+
+/// Maximum length of an ISO 11649 reference, including `RF` and the check digits.
+const RF_MAX_LEN: usize = 25;
+
+/// Remainder modulo 97 of an alphanumeric string with letters expanded to
+/// `A = 10` … `Z = 35`, as used by ISO 7064 MOD 97-10 checks.
+pub(crate) fn mod97(s: &str) -> Option<u32> {
+    let mut rem = 0u32;
+    for c in s.chars() {
+        let v = c.to_digit(36)?;
+        rem = if v < 10 { (rem * 10 + v) % 97 } else { (rem * 100 + v) % 97 };
+    }
+    Some(rem)
+}
+
+/// Whether `s` is shaped like an ISO 11649 reference (`RF` and two digits).
+pub fn is_creditor_reference(s: &str) -> bool {
+    s.starts_with(RF_PREFIX) && s.len() > 4 && s.as_bytes()[2..4].iter().all(u8::is_ascii_digit)
+}
+
+/// Validates an ISO 11649 creditor reference (`RF` + check digits + up to 21 alphanumerics).
+pub fn validate_creditor_reference(s: &str) -> Result<(), String> {
+    if !is_creditor_reference(s) || s.len() > RF_MAX_LEN {
+        return Err(format!("`{s}` is not an RF creditor reference"));
+    }
+    let rotated = format!("{}{}", &s[4..], &s[..4]);
+    match mod97(&rotated) {
+        Some(1) => Ok(()),
+        Some(_) => Err(format!("RF creditor reference `{s}` has wrong check digits")),
+        None => Err(format!("RF creditor reference `{s}` contains invalid characters")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn creditor_reference_check_digits() {
+        assert!(validate_creditor_reference("RF18539007547034").is_ok());
+        assert!(validate_creditor_reference("RF712348231").is_ok());
+        assert!(validate_creditor_reference("RF19539007547034").is_err());
+        assert!(validate_creditor_reference("RF18-539007547034").is_err());
+        assert!(validate_creditor_reference("INV-2025-1").is_err());
+    }
+}
@@ -0,0 +1,112 @@
+/// Weights of the Bank of Russia account control key, applied to the 3-digit
+/// bank prefix followed by the 20-digit account number.
+const ACCOUNT_KEY_WEIGHTS: [u32; 23] = [7, 1, 3, 7, 1, 3, 7, 1, 3, 7, 1, 3, 7, 1, 3, 7, 1, 3, 7, 1, 3, 7, 1];
+
+/// INN check digit weights: 10-digit INN, then the two check digits of a 12-digit INN.
+const INN10_WEIGHTS: [u32; 9] = [2, 4, 10, 3, 5, 9, 4, 6, 8];
+const INN12_WEIGHTS_11: [u32; 10] = [7, 2, 4, 10, 3, 5, 9, 4, 6, 8];
+const INN12_WEIGHTS_12: [u32; 11] = [3, 7, 2, 4, 10, 3, 5, 9, 4, 6, 8];
+
+fn digits(s: &str, len: usize) -> Option<Vec<u32>> {
+    (s.len() == len).then(|| s.chars().map(|c| c.to_digit(10)).collect::<Option<Vec<_>>>())?
+}
+
+/// Validates a BIK: 9 digits, starting with the `04` country code of banks or
+/// `01` of the Federal Treasury offices.
+pub fn validate_bik(bik: &str) -> Result<(), String> {
+    if digits(bik, 9).is_none() || !(bik.starts_with("04") || bik.starts_with("01")) {
+        return Err(format!("`{bik}` is not a BIK"));
+    }
+    Ok(())
+}
+
+/// Checks the control key (9th digit) of a 20-digit account against the bank's BIK.
+///
+/// Accounts held at a cash settlement centre of the Bank of Russia (BIK
+/// ending in `000`–`002`) or at a Treasury office (BIK starting with `01`) use
+/// `0` and the 5th–6th digits of the BIK as prefix; accounts at credit
+/// institutions use the last three BIK digits. Treasury accounts (`03…`) carry
+/// no control key.
+pub fn validate_account(account: &str, bik: &str) -> Result<(), String> {
+    validate_bik(bik)?;
+    if digits(account, 20).is_none() {
+        return Err(format!("account `{account}` must have 20 digits"));
+    }
+    let treasury = bik.starts_with("01");
+    if treasury && account.starts_with("03") {
+        return Ok(());
+    }
+    let prefix = if treasury || matches!(&bik[6..], "000" | "001" | "002") {
+        format!("0{}", &bik[4..6])
+    } else {
+        bik[6..].to_string()
+    };
+    let all = digits(&format!("{prefix}{account}"), 23).unwrap_or_default();
+    let sum: u32 = all.iter().zip(ACCOUNT_KEY_WEIGHTS).map(|(d, w)| d * w % 10).sum();
+    if !sum.is_multiple_of(10) {
+        return Err(format!("account `{account}` has a wrong control key for BIK {bik}"));
+    }
+    Ok(())
+}
+
+/// Validates the check digits of a 10-digit (organisation) or 12-digit (individual) INN.
+pub fn validate_inn(inn: &str) -> Result<(), String> {
+    let check = |d: &[u32], weights: &[u32]| d.iter().zip(weights).map(|(d, w)| d * w).sum::<u32>() % 11 % 10;
+    let valid = match (digits(inn, 10), digits(inn, 12)) {
+        (Some(d), _) => check(&d, &INN10_WEIGHTS) == d[9],
+        (_, Some(d)) => check(&d, &INN12_WEIGHTS_11) == d[10] && check(&d, &INN12_WEIGHTS_12) == d[11],
+        _ => return Err(format!("INN `{inn}` must have 10 or 12 digits")),
+    };
+    if !valid {
+        return Err(format!("INN `{inn}` has wrong check digits"));
+    }
+    Ok(())
+}
+
+/// Validates the KPP format: 4 digits tax office, 2 digits or capital Latin
+/// letters reason code, 3 digits serial number.
+pub fn validate_kpp(kpp: &str) -> Result<(), String> {
+    let b = kpp.as_bytes();
+    let valid = b.len() == 9
+        && b[..4].iter().all(u8::is_ascii_digit)
+        && b[4..6].iter().all(|c| c.is_ascii_digit() || c.is_ascii_uppercase())
+        && b[6..].iter().all(u8::is_ascii_digit);
+    if !valid {
+        return Err(format!("`{kpp}` is not a KPP"));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn account_control_key() {
+        assert!(validate_account("40702810600014448120", "044525545").is_ok());
+        assert!(validate_account("30233810642000600001", "042202603").is_ok());
+        assert!(validate_account("40702810700014448120", "044525545").unwrap_err().contains("control key"));
+        assert!(validate_account("4070281060001444812", "044525545").is_err());
+        assert!(validate_account("40702810600014448120", "123456789").is_err());
+        assert!(validate_bik("017003983").is_ok());
+        assert!(validate_account("40102810445370000059", "017003983").is_ok());
+        assert!(validate_account("40102810545370000059", "017003983").unwrap_err().contains("control key"));
+        assert!(validate_account("03100643000000018500", "017003983").is_ok());
+    }
+
+    #[test]
+    fn inn_check_digits() {
+        assert!(validate_inn("7707083893").is_ok());
+        assert!(validate_inn("500100732259").is_ok());
+        assert!(validate_inn("7707083894").unwrap_err().contains("check digits"));
+        assert!(validate_inn("77070838").is_err());
+    }
+
+    #[test]
+    fn kpp_format() {
+        assert!(validate_kpp("773601001").is_ok());
+        assert!(validate_kpp("7736AB001").is_ok());
+        assert!(validate_kpp("77360100").is_err());
+        assert!(validate_kpp("7736ab001").is_err());
+    }
+}
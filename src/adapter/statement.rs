@@ -1,66 +1,941 @@
-use serde::{Deserialize, Serialize};
+use std::fmt;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use crate::adapter::money::Money;
+use crate::adapter::transaction_code::TransactionCode;
+
+/// Calendar date without time zone.
+///
+/// All adapters normalize their native notation (`YYMMDD` in MT940, `YYYY-MM-DD`
+/// in CAMT, `dd.mm.yyyy` in bank CSV exports) into this type and render it back
+/// when writing. The default value is `1970-01-01`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    year: i32,
+    month: u8,
+    day: u8,
+}
+
+impl Date {
+    /// Two-digit years below this pivot belong to the 21st century, the rest to the 20th.
+    pub const CENTURY_PIVOT: u8 = 80;
+
+    /// Creates a date, checking that the day exists in the given month.
+    pub fn new(year: i32, month: u8, day: u8) -> Result<Self, String> {
+        if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+            return Err(format!("invalid date {year:04}-{month:02}-{day:02}"));
+        }
+        Ok(Date { year, month, day })
+    }
+
+    /// Year, e.g. `2025`.
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+
+    /// Month, `1..=12`.
+    pub fn month(&self) -> u8 {
+        self.month
+    }
+
+    /// Day of the month, `1..=31`.
+    pub fn day(&self) -> u8 {
+        self.day
+    }
+
+    /// Parses an ISO 8601 date (`YYYY-MM-DD`), ignoring any trailing time part.
+    pub fn parse_iso(s: &str) -> Result<Self, String> {
+        let b = s.as_bytes();
+        if !s.get(..10).is_some_and(|d| d.is_ascii()) || b[4] != b'-' || b[7] != b'-' || (b.len() > 10 && b[10] != b'T') {
+            return Err(format!("invalid ISO date `{s}`"));
+        }
+        Date::new(num(&s[0..4], s)?, num(&s[5..7], s)?, num(&s[8..10], s)?)
+    }
+
+    /// Parses a compact `YYYYMMDD` date.
+    pub fn parse_yyyymmdd(s: &str) -> Result<Self, String> {
+        if s.len() != 8 || !s.is_ascii() {
+            return Err(format!("invalid YYYYMMDD date `{s}`"));
+        }
+        Date::new(num(&s[0..4], s)?, num(&s[4..6], s)?, num(&s[6..8], s)?)
+    }
+
+    /// Parses an MT940 `YYMMDD` date, inferring the century with [`Date::CENTURY_PIVOT`].
+    pub fn parse_yymmdd(s: &str) -> Result<Self, String> {
+        if s.len() != 6 || !s.is_ascii() {
+            return Err(format!("invalid YYMMDD date `{s}`"));
+        }
+        let yy: u8 = num(&s[0..2], s)?;
+        let century = if yy < Self::CENTURY_PIVOT { 2000 } else { 1900 };
+        Date::new(century + i32::from(yy), num(&s[2..4], s)?, num(&s[4..6], s)?)
+    }
+
+    /// Parses a `dd.mm.yyyy` date as used in Russian bank exports.
+    pub fn parse_dmy(s: &str) -> Result<Self, String> {
+        let b = s.as_bytes();
+        if b.len() != 10 || !s.is_ascii() || b[2] != b'.' || b[5] != b'.' {
+            return Err(format!("invalid dd.mm.yyyy date `{s}`"));
+        }
+        Date::new(num(&s[6..10], s)?, num(&s[3..5], s)?, num(&s[0..2], s)?)
+    }
+
+    /// Parses any of the supported notations, detected by shape.
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let s = s.trim();
+        match s.len() {
+            6 => Date::parse_yymmdd(s),
+            8 => Date::parse_yyyymmdd(s),
+            10 if s.as_bytes()[2] == b'.' => Date::parse_dmy(s),
+            _ => Date::parse_iso(s),
+        }
+    }
+
+    /// Resolves a `MMDD` date (e.g. the MT940 `:61:` entry date) to the year that
+    /// puts it closest to `self`, so a December value date with a January entry
+    /// date rolls over into the next year and vice versa.
+    pub fn resolve_mmdd(&self, mmdd: &str) -> Result<Self, String> {
+        if mmdd.len() != 4 || !mmdd.is_ascii() {
+            return Err(format!("invalid MMDD date `{mmdd}`"));
+        }
+        let (month, day) = (num(&mmdd[0..2], mmdd)?, num(&mmdd[2..4], mmdd)?);
+        [self.year, self.year - 1, self.year + 1]
+            .into_iter()
+            .filter_map(|y| Date::new(y, month, day).ok())
+            .min_by_key(|d| (d.days_since_epoch() - self.days_since_epoch()).abs())
+            .ok_or_else(|| format!("invalid MMDD date `{mmdd}`"))
+    }
+
+    /// Renders as `YYMMDD`.
+    pub fn format_yymmdd(&self) -> String {
+        format!("{:02}{:02}{:02}", self.year.rem_euclid(100), self.month, self.day)
+    }
+
+    /// Renders as `MMDD`.
+    pub fn format_mmdd(&self) -> String {
+        format!("{:02}{:02}", self.month, self.day)
+    }
+
+    /// Renders as `dd.mm.yyyy`.
+    pub fn format_dmy(&self) -> String {
+        format!("{:02}.{:02}.{:04}", self.day, self.month, self.year)
+    }
+
+    /// Number of days since 1970-01-01 (negative before it).
+    pub fn days_since_epoch(&self) -> i64 {
+        // Howard Hinnant's days_from_civil.
+        let y = i64::from(self.year) - i64::from(self.month <= 2);
+        let era = y.div_euclid(400);
+        let yoe = y - era * 400;
+        let m = i64::from(self.month);
+        let doy = (153 * (if m > 2 { m - 3 } else { m + 9 }) + 2) / 5 + i64::from(self.day) - 1;
+        let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+        era * 146097 + doe - 719468
+    }
+
+    /// Inverse of [`Date::days_since_epoch`].
+    pub fn from_days_since_epoch(days: i64) -> Self {
+        // Howard Hinnant's civil_from_days.
+        let z = days + 719468;
+        let era = z.div_euclid(146097);
+        let doe = z - era * 146097;
+        let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = (doy - (153 * mp + 2) / 5 + 1) as u8;
+        let month = (if mp < 10 { mp + 3 } else { mp - 9 }) as u8;
+        let year = (yoe + era * 400 + i64::from(month <= 2)) as i32;
+        Date { year, month, day }
+    }
+}
+
+impl Default for Date {
+    fn default() -> Self {
+        Date { year: 1970, month: 1, day: 1 }
+    }
+}
+
+/// Renders as ISO 8601 `YYYY-MM-DD`.
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+impl Serialize for Date {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for Date {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Date::parse(&s).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Date with time of day and an optional UTC offset.
+///
+/// Used for creation timestamps (MT940 `:13D:`, CAMT `CreDtTm`) and statement periods.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct DateTime {
+    date: Date,
+    hour: u8,
+    minute: u8,
+    second: u8,
+    offset_minutes: Option<i16>,
+}
+
+impl DateTime {
+    /// Creates a date-time without a UTC offset.
+    pub fn new(date: Date, hour: u8, minute: u8, second: u8) -> Result<Self, String> {
+        if hour > 23 || minute > 59 || second > 59 {
+            return Err(format!("invalid time {hour:02}:{minute:02}:{second:02}"));
+        }
+        Ok(DateTime { date, hour, minute, second, offset_minutes: None })
+    }
+
+    /// Midnight at the start of `date`.
+    pub fn from_date(date: Date) -> Self {
+        DateTime { date, ..Default::default() }
+    }
+
+    /// Returns the same local time tagged with a UTC offset in minutes.
+    pub fn with_offset(self, offset_minutes: i16) -> Self {
+        DateTime { offset_minutes: Some(offset_minutes), ..self }
+    }
+
+    /// Current time in UTC.
+    pub fn now_utc() -> Self {
+        let secs = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs() as i64)
+            .unwrap_or_default();
+        let tod = secs.rem_euclid(86400);
+        DateTime {
+            date: Date::from_days_since_epoch(secs.div_euclid(86400)),
+            hour: (tod / 3600) as u8,
+            minute: (tod % 3600 / 60) as u8,
+            second: (tod % 60) as u8,
+            offset_minutes: Some(0),
+        }
+    }
+
+    /// Calendar date part.
+    pub fn date(&self) -> Date {
+        self.date
+    }
+
+    /// Hour, `0..=23`.
+    pub fn hour(&self) -> u8 {
+        self.hour
+    }
+
+    /// Minute, `0..=59`.
+    pub fn minute(&self) -> u8 {
+        self.minute
+    }
+
+    /// Second, `0..=59`.
+    pub fn second(&self) -> u8 {
+        self.second
+    }
+
+    /// UTC offset in minutes, if known.
+    pub fn offset_minutes(&self) -> Option<i16> {
+        self.offset_minutes
+    }
+
+    /// Parses ISO 8601 (`YYYY-MM-DDTHH:MM:SS[.fff][Z|±hh:mm]`); a bare date means midnight.
+    /// Fractional seconds are dropped.
+    pub fn parse_iso(s: &str) -> Result<Self, String> {
+        let s = s.trim();
+        let date = Date::parse_iso(s)?;
+        let Some(time) = s.get(11..) else {
+            return Ok(DateTime::from_date(date));
+        };
+        let Some(hms) = time.get(..8).filter(|t| t.is_ascii()) else {
+            return Err(format!("invalid ISO date-time `{s}`"));
+        };
+        let b = hms.as_bytes();
+        if b[2] != b':' || b[5] != b':' {
+            return Err(format!("invalid ISO date-time `{s}`"));
+        }
+        let dt = DateTime::new(date, num(&hms[0..2], s)?, num(&hms[3..5], s)?, num(&hms[6..8], s)?)?;
+
+        let zone = time[8..].trim_start_matches(|c: char| c == '.' || c.is_ascii_digit());
+        match zone {
+            "" => Ok(dt),
+            "Z" => Ok(dt.with_offset(0)),
+            _ => Ok(dt.with_offset(parse_offset(&zone.replace(':', ""), s)?)),
+        }
+    }
+
+    /// Parses an MT940 `:13D:` value (`YYMMDDHHMM±hhmm`).
+    pub fn parse_13d(s: &str) -> Result<Self, String> {
+        let s = s.trim();
+        if s.len() != 15 || !s.is_ascii() {
+            return Err(format!("invalid :13D: date-time `{s}`"));
+        }
+        let date = Date::parse_yymmdd(&s[0..6])?;
+        let dt = DateTime::new(date, num(&s[6..8], s)?, num(&s[8..10], s)?, 0)?;
+        Ok(dt.with_offset(parse_offset(&s[10..], s)?))
+    }
+
+    /// Renders as `YYMMDDHHMM±hhmm`; a missing offset is written as `+0000`.
+    pub fn format_13d(&self) -> String {
+        let off = self.offset_minutes.unwrap_or(0);
+        let sign = if off < 0 { '-' } else { '+' };
+        let off = off.unsigned_abs();
+        format!(
+            "{}{:02}{:02}{sign}{:02}{:02}",
+            self.date.format_yymmdd(), self.hour, self.minute, off / 60, off % 60
+        )
+    }
+}
+
+/// Renders as ISO 8601 `YYYY-MM-DDTHH:MM:SS`, followed by `±hh:mm` when the offset is known.
+impl fmt::Display for DateTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}T{:02}:{:02}:{:02}", self.date, self.hour, self.minute, self.second)?;
+        if let Some(off) = self.offset_minutes {
+            let sign = if off < 0 { '-' } else { '+' };
+            let off = off.unsigned_abs();
+            write!(f, "{sign}{:02}:{:02}", off / 60, off % 60)?;
+        }
+        Ok(())
+    }
+}
+
+/// Time span covered by a statement (CAMT `FrToDt`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Period {
+    /// Start of the period.
+    pub from: DateTime,
+    /// End of the period.
+    pub to: DateTime,
+}
+
+/// SWIFT FIN envelope of a message: the header and trailer blocks around its text.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FinEnvelope {
+    /// BIC of the sending institution.
+    pub sender: Option<String>,
+    /// BIC of the receiving institution.
+    pub receiver: Option<String>,
+    /// Message type, e.g. `940`.
+    pub message_type: Option<String>,
+    /// Message user reference (user header field `108`).
+    pub mur: Option<String>,
+    /// Checksum from the trailer (`{5:{CHK:...}}`).
+    pub checksum: Option<String>,
+}
+
+fn parse_offset(s: &str, whole: &str) -> Result<i16, String> {
+    let (sign, digits) = match s.as_bytes().first() {
+        Some(b'+') => (1, &s[1..]),
+        Some(b'-') => (-1, &s[1..]),
+        _ => return Err(format!("invalid UTC offset in `{whole}`")),
+    };
+    if digits.len() != 4 || !digits.is_ascii() {
+        return Err(format!("invalid UTC offset in `{whole}`"));
+    }
+    let (h, m): (i16, i16) = (num(&digits[0..2], whole)?, num(&digits[2..4], whole)?);
+    Ok(sign * (h * 60 + m))
+}
+
+fn is_leap_year(year: i32) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+fn days_in_month(year: i32, month: u8) -> u8 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+fn num<T: std::str::FromStr>(part: &str, whole: &str) -> Result<T, String> {
+    if !part.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(format!("invalid date `{whole}`"));
+    }
+    part.parse().map_err(|_| format!("invalid date `{whole}`"))
+}
 
 /// Indicates the type of transaction: debit (outflow) or credit (inflow).
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
 pub enum DebitCredit {
     /// A debit transaction (money out).
     Debit,
     /// A credit transaction (money in).
+    #[default]
     Credit,
 }
 
+impl DebitCredit {
+    /// The other direction; a reversal of a credit is booked as a debit and vice versa.
+    pub fn opposite(self) -> Self {
+        match self {
+            DebitCredit::Debit => DebitCredit::Credit,
+            DebitCredit::Credit => DebitCredit::Debit,
+        }
+    }
+}
+
+/// Booking status of an entry (ISO 20022 `Sts`).
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum EntryStatus {
+    /// Booked on the account (`BOOK`).
+    #[default]
+    Booked,
+    /// Not yet booked; does not affect booked balances (`PDNG`).
+    Pending,
+    /// For information only, never booked (`INFO`).
+    Information,
+}
+
+/// A party to a transaction: the account holder and the bank servicing the account.
+///
+/// Used both for the counterparty and for our own side of an entry.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct Counterparty {
+    /// Name of the account holder.
+    pub name: Option<String>,
+    /// Account number or IBAN.
+    pub account: Option<String>,
+    /// Tax identifier of the account holder (e.g. Russian INN).
+    pub tax_id: Option<String>,
+    /// Russian tax registration reason code (KPP) of an organisation.
+    pub kpp: Option<String>,
+    /// Identifier of the servicing bank: BIC, or Russian BIK.
+    pub bank_id: Option<String>,
+    /// Name of the servicing bank.
+    pub bank_name: Option<String>,
+}
+
+impl Counterparty {
+    /// Returns `true` if no field is set.
+    pub fn is_empty(&self) -> bool {
+        self == &Counterparty::default()
+    }
+}
+
+/// Source data the model has no field for, kept so the same adapter can write it back.
+///
+/// Keys are namespaced by format (`mt940:`, `camt:`, `csv:`). A key may occur
+/// more than once and insertion order is preserved.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct Extensions(Vec<(String, String)>);
+
+impl Extensions {
+    /// Appends a value under `key`.
+    pub fn push(&mut self, key: impl Into<String>, value: impl Into<String>) {
+        self.0.push((key.into(), value.into()));
+    }
+
+    /// First value stored under `key`.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.0.iter().find(|(k, _)| k == key).map(|(_, v)| v.as_str())
+    }
+
+    /// All entries in insertion order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.0.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+
+    /// Entries whose key starts with `prefix`, with the prefix stripped.
+    pub fn with_prefix<'a>(&'a self, prefix: &'a str) -> impl Iterator<Item = (&'a str, &'a str)> {
+        self.iter().filter_map(move |(k, v)| Some((k.strip_prefix(prefix)?, v)))
+    }
+
+    /// Moves all entries of `other` to the end.
+    pub fn append(&mut self, other: Extensions) {
+        self.0.extend(other.0);
+    }
+
+    /// Value of the most recently added entry, e.g. to append a continuation line.
+    pub fn last_value_mut(&mut self) -> Option<&mut String> {
+        self.0.last_mut().map(|(_, v)| v)
+    }
+
+    /// Returns `true` if nothing is stored.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+/// An amount together with its currency.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct CurrencyAmount {
+    /// The amount.
+    pub amount: Money,
+    /// Currency code of the amount.
+    pub currency: String,
+}
+
+/// Currency exchange applied to an entry (ISO 20022 `CcyXchg`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct CurrencyExchange {
+    /// Currency the amount was converted from.
+    pub source_currency: String,
+    /// Currency the amount was converted into.
+    pub target_currency: Option<String>,
+    /// Currency the rate is quoted per unit of.
+    pub unit_currency: Option<String>,
+    /// Exchange rate as an exact decimal.
+    pub rate: Money,
+}
+
+/// A charge levied on an entry (ISO 20022 `Chrgs`, MT940 `/CHGS/`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct Charge {
+    /// Amount and currency of the charge.
+    pub amount: CurrencyAmount,
+    /// Charge type code, e.g. `COMM`.
+    pub code: Option<String>,
+}
+
+/// Batch summary of an entry that aggregates several payments (ISO 20022 `NtryDtls/Btch`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct Batch {
+    /// Identification of the message the batch was sent in.
+    pub message_id: Option<String>,
+    /// Identification of the payment information block.
+    pub payment_info_id: Option<String>,
+    /// Number of transactions in the batch.
+    pub number_of_transactions: Option<u32>,
+    /// Total amount of the batch.
+    pub total_amount: Option<CurrencyAmount>,
+}
+
+/// One underlying transaction of an entry (ISO 20022 `TxDtls`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct TransactionDetails {
+    /// Amount of this transaction.
+    pub amount: Option<CurrencyAmount>,
+    /// End-to-end identification assigned by the initiating party.
+    pub end_to_end_id: Option<String>,
+    /// Direct debit mandate identification.
+    pub mandate_id: Option<String>,
+    /// Reference assigned to the transaction by the account servicing bank.
+    pub servicer_reference: Option<String>,
+    /// Unstructured remittance lines.
+    pub remittance_info: Vec<String>,
+    /// Structured creditor reference.
+    pub creditor_reference: Option<String>,
+    /// The other side of the transaction.
+    pub counterparty: Option<Counterparty>,
+    /// Our own side of the transaction.
+    pub own_party: Option<Counterparty>,
+    /// Unmapped source data of this transaction.
+    pub extensions: Extensions,
+}
+
 /// Represents a single transaction entry within a financial statement.
 ///
 /// Each entry includes booking and value dates, amount, currency, and
 /// whether it is a debit or credit. It may also include an optional
-/// reference and a human-readable description.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+/// reference, a human-readable description and the parties involved.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
 pub struct Entry {
-    /// Date when the transaction was booked (format: YYYY-MM-DD).
-    pub booking_date: String,
-    /// Date when the transaction value takes effect (format: YYYY-MM-DD).
-    pub value_date: String,
-    /// Transaction amount as a string (to preserve exact formatting).
-    pub amount: String,
+    /// Date when the transaction was booked.
+    pub booking_date: Date,
+    /// Date when the transaction value takes effect.
+    pub value_date: Date,
+    /// Transaction amount (always positive, direction is given by `kind`).
+    pub amount: Money,
     /// Currency code (e.g. "EUR", "USD").
     pub currency: String,
+    /// Third letter of the currency code of the funds, when the bank gives it
+    /// (MT940 `:61:` funds code).
+    pub funds_code: Option<char>,
     /// Whether this is a debit or credit transaction.
     pub kind: DebitCredit,
+    /// Booking status; only booked entries move booked balances.
+    pub status: EntryStatus,
+    /// The entry reverses an earlier one of the opposite direction
+    /// (MT940 `RC`/`RD`, ISO 20022 `RvslInd`).
+    pub reversal: bool,
     /// Description or purpose of the transaction.
     pub description: String,
     /// Optional reference or identifier provided by the bank.
     pub reference: Option<String>,
+    /// Unstructured remittance lines (`RmtInf/Ustrd`).
+    pub remittance_info: Vec<String>,
+    /// Structured creditor reference, e.g. an ISO 11649 `RF` reference.
+    pub creditor_reference: Option<String>,
+    /// End-to-end identification assigned by the initiating party.
+    pub end_to_end_id: Option<String>,
+    /// Direct debit mandate identification.
+    pub mandate_id: Option<String>,
+    /// SEPA creditor identifier of a direct debit (German MT940 `CRED+`).
+    pub creditor_id: Option<String>,
+    /// Reference assigned to the entry by the account servicing bank.
+    pub servicer_reference: Option<String>,
+    /// Free text of the MT940 `:61:` supplementary details, besides `/OCMT/` and `/CHGS/`.
+    pub supplementary_details: Option<String>,
+    /// Amount as instructed by the originator, before conversion and charges
+    /// (`AmtDtls/InstdAmt`, MT940 `/OCMT/`).
+    pub instructed_amount: Option<CurrencyAmount>,
+    /// Amount of the underlying transaction (`AmtDtls/TxAmt`).
+    pub transaction_amount: Option<CurrencyAmount>,
+    /// Currency exchange between the original and the booked amount.
+    pub exchange: Option<CurrencyExchange>,
+    /// Charges deducted for the entry.
+    pub charges: Vec<Charge>,
+    /// Batch summary when the entry books several payments at once.
+    pub batch: Option<Batch>,
+    /// Underlying transactions of a batch entry; empty for a single payment,
+    /// whose details live on the entry itself.
+    pub transactions: Vec<TransactionDetails>,
+    /// The other side of the transaction: payer for credits, payee for debits.
+    pub counterparty: Option<Counterparty>,
+    /// Our own side of the transaction, when the format carries it.
+    pub own_party: Option<Counterparty>,
+    /// Bank transaction code (SWIFT `:61:` type and/or ISO 20022 `BkTxCd`).
+    pub transaction_code: Option<TransactionCode>,
+    /// Unmapped source data of this entry.
+    pub extensions: Extensions,
 }
 
-/// Represents an account balance at a specific date.
+impl Entry {
+    /// Amount with sign: negative for debits.
+    pub fn signed_amount(&self) -> Money {
+        match self.kind {
+            DebitCredit::Credit => self.amount,
+            DebitCredit::Debit => -self.amount,
+        }
+    }
+
+    /// Returns `true` if the entry is booked and counts towards booked balances.
+    pub fn is_booked(&self) -> bool {
+        self.status == EntryStatus::Booked
+    }
+
+    /// Splits a batch entry into one entry per underlying transaction.
+    ///
+    /// Each child keeps the dates, status and code of the batch. Entries without
+    /// transactions, or with a transaction lacking an amount in the entry
+    /// currency, are returned unchanged.
+    pub fn expand_batch(&self) -> Vec<Entry> {
+        let amounts: Option<Vec<Money>> = self.transactions.iter()
+            .map(|tx| tx.amount.as_ref().filter(|a| a.currency == self.currency).map(|a| a.amount))
+            .collect();
+        let Some(amounts) = amounts.filter(|a| !a.is_empty()) else {
+            return vec![self.clone()];
+        };
+
+        self.transactions.iter().zip(amounts).map(|(tx, amount)| {
+            let remittance = (!tx.remittance_info.is_empty()).then(|| tx.remittance_info.join(" "));
+            Entry {
+                amount,
+                description: remittance.unwrap_or_else(|| self.description.clone()),
+                remittance_info: tx.remittance_info.clone(),
+                creditor_reference: tx.creditor_reference.clone(),
+                end_to_end_id: tx.end_to_end_id.clone(),
+                mandate_id: tx.mandate_id.clone(),
+                servicer_reference: tx.servicer_reference.clone().or_else(|| self.servicer_reference.clone()),
+                counterparty: tx.counterparty.clone(),
+                own_party: tx.own_party.clone().or_else(|| self.own_party.clone()),
+                instructed_amount: None,
+                transaction_amount: None,
+                exchange: None,
+                charges: Vec::new(),
+                batch: None,
+                transactions: Vec::new(),
+                extensions: tx.extensions.clone(),
+                ..self.clone()
+            }
+        }).collect()
+    }
+
+    /// Unstructured remittance lines joined into one string, if there are any.
+    pub fn remittance_text(&self) -> Option<String> {
+        (!self.remittance_info.is_empty()).then(|| self.remittance_info.join(" "))
+    }
+}
+
+/// Kind of balance reported in a statement.
 ///
-/// Used for both opening and closing balances in a statement.
+/// Each variant notes its MT940 tag and ISO 20022 balance type code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BalanceType {
+    /// Booked opening balance (`:60F:`, `OPBD`).
+    OpeningBooked,
+    /// Closing booked balance of the previous statement (`PRCD`); opens this one.
+    PreviouslyClosedBooked,
+    /// Intermediate opening balance of a continuation page (`:60M:`, first `ITBD`).
+    IntermediateOpening,
+    /// Intermediate closing balance of a page that continues (`:62M:`, later `ITBD`).
+    IntermediateClosing,
+    /// Booked closing balance (`:62F:`, `CLBD`).
+    ClosingBooked,
+    /// Opening available balance (`OPAV`).
+    OpeningAvailable,
+    /// Interim available balance (`ITAV`).
+    InterimAvailable,
+    /// Closing available balance (`:64:`, `CLAV`).
+    ClosingAvailable,
+    /// Forward available balance (`:65:`, `FWAV`); a statement may carry several.
+    ForwardAvailable,
+}
+
+impl BalanceType {
+    /// Returns `true` for balances a statement opens with.
+    pub fn is_opening(&self) -> bool {
+        matches!(
+            self,
+            BalanceType::OpeningBooked | BalanceType::PreviouslyClosedBooked | BalanceType::IntermediateOpening
+        )
+    }
+
+    /// Returns `true` for booked balances a statement closes with.
+    pub fn is_closing(&self) -> bool {
+        matches!(self, BalanceType::ClosingBooked | BalanceType::IntermediateClosing)
+    }
+}
+
+/// Represents an account balance at a specific date.
 #[derive(Debug, Clone, PartialEq)]
 pub struct Balance {
+    /// Which balance this is (opening, closing, available, ...).
+    pub balance_type: BalanceType,
     /// Indicates whether the balance is debit or credit.
     pub kind: DebitCredit,
-    /// Balance date in YYYY-MM-DD format.
-    pub date_yyymmdd: String,
+    /// Balance date.
+    pub date: Date,
     /// Currency code (e.g. "EUR", "USD").
     pub currency: String,
-    /// Account balance amount as a string.
-    pub amount: String,
+    /// Account balance amount.
+    pub amount: Money,
+}
+
+impl Balance {
+    /// Amount with sign: negative for debit balances.
+    pub fn signed_amount(&self) -> Money {
+        match self.kind {
+            DebitCredit::Credit => self.amount,
+            DebitCredit::Debit => -self.amount,
+        }
+    }
 }
 
 /// Represents a full financial statement (e.g. one MT940 message).
 ///
-/// Contains metadata such as statement ID and account ID, as well as
-/// the list of entries and optional opening and closing balances.
-#[derive(Debug, Clone, PartialEq)]
+/// Contains metadata such as statement ID, account ID and sequence numbers,
+/// as well as the list of entries and the balances reported for the account.
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct Statement {
     /// Unique identifier of the statement (e.g. `:20:` field in MT940).
     pub id: String,
+    /// Related reference (MT940 `:21:`).
+    pub related_reference: Option<String>,
+    /// Electronic sequence number (MT940 `:28C:` statement number, CAMT `ElctrncSeqNb`).
+    pub electronic_sequence: Option<u64>,
+    /// Legal sequence number (CAMT `LglSeqNb`).
+    pub legal_sequence: Option<u64>,
+    /// Page of a multi-message statement (MT940 `:28C:` sequence number).
+    pub page_number: Option<u32>,
+    /// When the statement was created (MT940 `:13D:`, CAMT `CreDtTm`).
+    pub created_at: Option<DateTime>,
+    /// Period covered by the statement (CAMT `FrToDt`).
+    pub period: Option<Period>,
     /// Account identifier (e.g. IBAN or account number).
     pub account_id: String,
-    /// Opening balance (e.g. MT940 `:60F:` field).
-    pub opening_balance: Option<Balance>,
+    /// Currency of the account.
+    pub account_currency: Option<String>,
+    /// Name of the account owner (CAMT `Acct/Ownr/Nm`).
+    pub account_owner: Option<String>,
+    /// BIC of the bank servicing the account (CAMT `Acct/Svcr`).
+    pub servicer_bic: Option<String>,
+    /// SWIFT FIN envelope the message came in (MT940).
+    pub envelope: Option<FinEnvelope>,
+    /// Balances in the order they appear in the source (e.g. MT940 `:60a:`, `:62a:`, `:64:`, `:65:`).
+    pub balances: Vec<Balance>,
     /// List of transaction entries in this statement.
     pub entries: Vec<Entry>,
-    /// Closing balance (e.g. MT940 `:62F:` field).
-    pub closing_balance: Option<Balance>,
+    /// Unmapped source data of the statement.
+    pub extensions: Extensions,
+}
+
+impl Statement {
+    /// First balance of the given type.
+    pub fn balance(&self, balance_type: BalanceType) -> Option<&Balance> {
+        self.balances.iter().find(|b| b.balance_type == balance_type)
+    }
+
+    /// Balance the statement opens with: booked, previously closed, or intermediate.
+    pub fn opening_balance(&self) -> Option<&Balance> {
+        self.balances.iter().find(|b| b.balance_type.is_opening())
+    }
+
+    /// Booked balance the statement closes with, final or intermediate.
+    pub fn closing_balance(&self) -> Option<&Balance> {
+        self.balances.iter().find(|b| b.balance_type.is_closing())
+    }
+
+    /// Replaces batch entries with one entry per underlying transaction, for
+    /// formats without a batch concept; see [`Entry::expand_batch`].
+    pub fn expand_batches(&mut self) {
+        self.entries = self.entries.iter().flat_map(Entry::expand_batch).collect();
+    }
+
+    /// Net movement of booked entries; pending and information-only entries are left out.
+    /// Fails when the amounts cannot be added up in an `i64`.
+    pub fn booked_movement(&self) -> Result<Money, String> {
+        Money::checked_sum(self.entries.iter().filter(|e| e.is_booked()).map(Entry::signed_amount))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn date_parses_all_notations() {
+        let d = Date::new(2025, 10, 1).unwrap();
+        assert_eq!(Date::parse("251001").unwrap(), d);
+        assert_eq!(Date::parse("20251001").unwrap(), d);
+        assert_eq!(Date::parse("2025-10-01").unwrap(), d);
+        assert_eq!(Date::parse("2025-10-01T12:30:00").unwrap(), d);
+        assert_eq!(Date::parse("01.10.2025").unwrap(), d);
+        assert_eq!(Date::parse_yymmdd("991231").unwrap().year(), 1999);
+    }
+
+    #[test]
+    fn date_rejects_impossible_days() {
+        assert!(Date::new(2023, 2, 29).is_err());
+        assert!(Date::new(2024, 2, 29).is_ok());
+        assert!(Date::parse("251301").is_err());
+        assert!(Date::parse("2025-1O-01").is_err());
+    }
+
+    #[test]
+    fn date_rejects_non_ascii() {
+        assert!(Date::parse_yymmdd("1é101").is_err());
+        assert!(Date::parse_yyyymmdd("2025é101").is_err());
+        assert!(Date::parse_dmy("01.1é.2025").is_err());
+        assert!(Date::parse_iso("2025-1é-01").is_err());
+        assert!(Date::parse_iso("2025-10-0é").is_err());
+        assert!(Date::parse_iso("2025-10-é1T10:00:00").is_err());
+        assert!(Date::new(2025, 10, 1).unwrap().resolve_mmdd("1é1").is_err());
+    }
+
+    #[test]
+    fn date_renders_each_notation() {
+        let d = Date::new(2024, 2, 5).unwrap();
+        assert_eq!(d.to_string(), "2024-02-05");
+        assert_eq!(d.format_yymmdd(), "240205");
+        assert_eq!(d.format_mmdd(), "0205");
+        assert_eq!(d.format_dmy(), "05.02.2024");
+    }
+
+    #[test]
+    fn resolve_mmdd_handles_year_rollover() {
+        let dec = Date::new(2025, 12, 31).unwrap();
+        assert_eq!(dec.resolve_mmdd("0102").unwrap(), Date::new(2026, 1, 2).unwrap());
+        let jan = Date::new(2026, 1, 1).unwrap();
+        assert_eq!(jan.resolve_mmdd("1231").unwrap(), Date::new(2025, 12, 31).unwrap());
+        assert_eq!(jan.resolve_mmdd("0103").unwrap(), Date::new(2026, 1, 3).unwrap());
+    }
+
+    #[test]
+    fn days_since_epoch_matches_known_values() {
+        assert_eq!(Date::default().days_since_epoch(), 0);
+        assert_eq!(Date::new(2000, 3, 1).unwrap().days_since_epoch(), 11017);
+    }
+
+    #[test]
+    fn from_days_since_epoch_is_inverse() {
+        for days in [-1, 0, 59, 11017, 20000, 20453] {
+            assert_eq!(Date::from_days_since_epoch(days).days_since_epoch(), days);
+        }
+    }
+
+    #[test]
+    fn date_time_parses_iso_variants() {
+        let d = Date::new(2025, 10, 1).unwrap();
+        let dt = DateTime::parse_iso("2025-10-01T10:15:30").unwrap();
+        assert_eq!((dt.date(), dt.hour(), dt.minute(), dt.second()), (d, 10, 15, 30));
+        assert_eq!(dt.offset_minutes(), None);
+        assert_eq!(DateTime::parse_iso("2025-10-01T10:15:30.123Z").unwrap().offset_minutes(), Some(0));
+        assert_eq!(DateTime::parse_iso("2025-10-01T10:15:30-05:30").unwrap().offset_minutes(), Some(-330));
+        assert_eq!(DateTime::parse_iso("2025-10-01").unwrap(), DateTime::from_date(d));
+        assert!(DateTime::parse_iso("2025-10-01T25:00:00").is_err());
+        assert!(DateTime::parse_iso("2025-10-01T10:15:3é").is_err());
+        assert!(DateTime::parse_iso("2025-10-01T10:15:30+0é:0").is_err());
+    }
+
+    #[test]
+    fn date_time_13d_round_trip() {
+        let dt = DateTime::parse_13d("2510011015+0200").unwrap();
+        assert_eq!(dt.to_string(), "2025-10-01T10:15:00+02:00");
+        assert_eq!(dt.format_13d(), "2510011015+0200");
+        assert!(DateTime::parse_13d("2510011015").is_err());
+        assert!(DateTime::parse_13d("25100110é+0200").is_err());
+    }
+
+    #[test]
+    fn expand_batch_splits_transactions() {
+        let tx = |amount: &str, name: &str| TransactionDetails {
+            amount: Some(CurrencyAmount { amount: Money::parse(amount).unwrap(), currency: "EUR".to_string() }),
+            remittance_info: vec![format!("Invoice {name}")],
+            counterparty: Some(Counterparty { name: Some(name.to_string()), ..Default::default() }),
+            ..Default::default()
+        };
+        let batch = Entry {
+            amount: Money::parse("30.00").unwrap(),
+            currency: "EUR".to_string(),
+            description: "SEPA batch".to_string(),
+            batch: Some(Batch { number_of_transactions: Some(2), ..Default::default() }),
+            transactions: vec![tx("10.00", "A"), tx("20.00", "B")],
+            ..Default::default()
+        };
+
+        let mut st = Statement { entries: vec![batch.clone(), Entry::default()], ..Default::default() };
+        st.expand_batches();
+        assert_eq!(st.entries.len(), 3);
+        assert_eq!(st.entries[1].amount, Money::parse("20.00").unwrap());
+        assert_eq!(st.entries[1].description, "Invoice B");
+        assert_eq!(st.entries[1].counterparty.as_ref().unwrap().name.as_deref(), Some("B"));
+        assert!(st.entries[1].transactions.is_empty() && st.entries[1].batch.is_none());
+
+        let mut foreign = batch;
+        foreign.transactions[0].amount.as_mut().unwrap().currency = "USD".to_string();
+        assert_eq!(foreign.expand_batch(), [foreign.clone()]);
+    }
+
+    #[test]
+    fn extensions_keep_order_and_duplicates() {
+        let mut ext = Extensions::default();
+        ext.push("mt940:NS", "22first");
+        ext.push("camt:Ntry/Avlbty", "<Avlbty/>");
+        ext.push("mt940:NS", "23second");
+        ext.last_value_mut().unwrap().push_str("\nmore");
+
+        assert_eq!(ext.get("mt940:NS"), Some("22first"));
+        let ns: Vec<_> = ext.with_prefix("mt940:").collect();
+        assert_eq!(ns, [("NS", "22first"), ("NS", "23second\nmore")]);
+    }
+
+    #[test]
+    fn booked_movement_skips_pending_entries() {
+        let entry = |kind, amount: &str, status| Entry {
+            kind,
+            amount: Money::parse(amount).unwrap(),
+            status,
+            ..Default::default()
+        };
+        let st = Statement {
+            entries: vec![
+                entry(DebitCredit::Credit, "100.00", EntryStatus::Booked),
+                entry(DebitCredit::Debit, "30.00", EntryStatus::Booked),
+                entry(DebitCredit::Debit, "50.00", EntryStatus::Pending),
+                entry(DebitCredit::Credit, "5.00", EntryStatus::Information),
+            ],
+            ..Default::default()
+        };
+        assert_eq!(st.booked_movement().unwrap(), Money::parse("70.00").unwrap());
+    }
 }
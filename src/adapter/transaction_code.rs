@@ -0,0 +1,165 @@
+use serde::{Deserialize, Serialize};
+use crate::adapter::statement::DebitCredit;
+
+/// Issuer used for SWIFT transaction type codes carried as a proprietary ISO 20022 code.
+pub const SWIFT_ISSUER: &str = "SWIFT";
+
+/// Bank transaction code of an entry.
+///
+/// Holds the SWIFT transaction type identification code of MT940 `:61:`
+/// (e.g. `NTRF`), the ISO 20022 domain/family/sub-family triple of CAMT `BkTxCd`,
+/// and an optional proprietary code. Either side can be derived from the other
+/// through the built-in mapping table.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct TransactionCode {
+    /// SWIFT code including its `N`/`F`/`S` prefix, e.g. `NTRF`.
+    pub swift: Option<String>,
+    /// ISO 20022 domain code, e.g. `PMNT`.
+    pub domain: Option<String>,
+    /// ISO 20022 family code, e.g. `RCDT`.
+    pub family: Option<String>,
+    /// ISO 20022 sub-family code, e.g. `DMCT`.
+    pub sub_family: Option<String>,
+    /// Proprietary code (`BkTxCd/Prtry/Cd`).
+    pub proprietary: Option<String>,
+    /// Issuer of the proprietary code (`BkTxCd/Prtry/Issr`).
+    pub proprietary_issuer: Option<String>,
+}
+
+/// ISO 20022 domain, family and sub-family codes.
+pub type IsoCode = (&'static str, &'static str, &'static str);
+
+/// SWIFT type code (without prefix) with its ISO 20022 equivalents for debits and credits.
+const MAPPING: &[(&str, IsoCode, IsoCode)] = &[
+    ("TRF", ("PMNT", "ICDT", "DMCT"), ("PMNT", "RCDT", "DMCT")),
+    ("STO", ("PMNT", "ICDT", "STDO"), ("PMNT", "RCDT", "STDO")),
+    ("SAL", ("PMNT", "ICDT", "SALA"), ("PMNT", "RCDT", "SALA")),
+    ("TAX", ("PMNT", "ICDT", "TAXS"), ("PMNT", "RCDT", "TAXS")),
+    ("RTI", ("PMNT", "ICDT", "RRTN"), ("PMNT", "RCDT", "RRTN")),
+    ("DDT", ("PMNT", "RDDT", "ESDD"), ("PMNT", "IDDT", "ESDD")),
+    ("CHK", ("PMNT", "ICHQ", "CCHQ"), ("PMNT", "RCHQ", "CCHQ")),
+    ("CAS", ("PMNT", "CNTR", "CWDL"), ("PMNT", "CNTR", "CDPT")),
+    ("CHG", ("ACMT", "MDOP", "CHRG"), ("ACMT", "MCOP", "CHRG")),
+    ("COM", ("ACMT", "MDOP", "COMM"), ("ACMT", "MCOP", "COMM")),
+    ("INT", ("ACMT", "MDOP", "INTR"), ("ACMT", "MCOP", "INTR")),
+    ("MSC", ("ACMT", "MDOP", "OTHR"), ("ACMT", "MCOP", "OTHR")),
+    ("DIV", ("SECU", "CORP", "DVCA"), ("SECU", "CORP", "DVCA")),
+    ("FEX", ("FORX", "SPOT", "OTHR"), ("FORX", "SPOT", "OTHR")),
+];
+
+/// SWIFT code used when nothing more specific is known.
+const FALLBACK_SWIFT: &str = "MSC";
+
+/// Maps a SWIFT type code (with or without the `N`/`F`/`S` prefix) to ISO 20022.
+pub fn swift_to_iso(code: &str, kind: DebitCredit) -> Option<IsoCode> {
+    let code = strip_swift_prefix(code);
+    MAPPING.iter().find(|(swift, _, _)| *swift == code).map(|(_, debit, credit)| match kind {
+        DebitCredit::Debit => *debit,
+        DebitCredit::Credit => *credit,
+    })
+}
+
+/// Maps ISO 20022 codes to a three-letter SWIFT type code.
+///
+/// An exact match wins; otherwise the family decides, and `MSC` is the last resort.
+pub fn iso_to_swift(domain: &str, family: &str, sub_family: &str) -> &'static str {
+    let exact = MAPPING.iter().find(|(_, d, c)| {
+        [d, c].iter().any(|iso| **iso == (domain, family, sub_family))
+    });
+    if let Some((swift, _, _)) = exact {
+        return swift;
+    }
+    match family {
+        "ICDT" | "RCDT" => "TRF",
+        "IDDT" | "RDDT" => "DDT",
+        "ICHQ" | "RCHQ" => "CHK",
+        "CNTR" => "CAS",
+        "FEES" => "CHG",
+        _ => FALLBACK_SWIFT,
+    }
+}
+
+fn strip_swift_prefix(code: &str) -> &str {
+    match code.as_bytes() {
+        [b'N' | b'F' | b'S', _, _, _] => &code[1..],
+        _ => code,
+    }
+}
+
+impl TransactionCode {
+    /// Builds a code from the MT940 `:61:` type code and fills in its ISO 20022 mapping.
+    pub fn from_swift(code: &str, kind: DebitCredit) -> Self {
+        let iso = swift_to_iso(code, kind);
+        TransactionCode {
+            swift: Some(code.to_string()),
+            domain: iso.map(|(d, _, _)| d.to_string()),
+            family: iso.map(|(_, f, _)| f.to_string()),
+            sub_family: iso.map(|(_, _, s)| s.to_string()),
+            ..Default::default()
+        }
+    }
+
+    /// ISO 20022 codes: as given, or derived from the SWIFT code.
+    pub fn iso(&self, kind: DebitCredit) -> Option<(String, String, String)> {
+        if let (Some(d), Some(f), Some(s)) = (&self.domain, &self.family, &self.sub_family) {
+            return Some((d.clone(), f.clone(), s.clone()));
+        }
+        let (d, f, s) = swift_to_iso(self.swift.as_deref()?, kind)?;
+        Some((d.to_string(), f.to_string(), s.to_string()))
+    }
+
+    /// Four-character SWIFT code for `:61:`: as given, a proprietary SWIFT code,
+    /// or derived from the ISO 20022 codes (`NMSC` when nothing is known).
+    pub fn swift_code(&self) -> String {
+        if let Some(swift) = &self.swift {
+            return swift.clone();
+        }
+        if self.proprietary_issuer.as_deref() == Some(SWIFT_ISSUER)
+            && let Some(code) = &self.proprietary
+        {
+            return code.clone();
+        }
+        let code = match (&self.domain, &self.family, &self.sub_family) {
+            (Some(d), Some(f), Some(s)) => iso_to_swift(d, f, s),
+            _ => FALLBACK_SWIFT,
+        };
+        format!("N{code}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn swift_maps_by_direction() {
+        assert_eq!(swift_to_iso("NTRF", DebitCredit::Credit), Some(("PMNT", "RCDT", "DMCT")));
+        assert_eq!(swift_to_iso("TRF", DebitCredit::Debit), Some(("PMNT", "ICDT", "DMCT")));
+        assert_eq!(swift_to_iso("NDDT", DebitCredit::Debit), Some(("PMNT", "RDDT", "ESDD")));
+        assert_eq!(swift_to_iso("NXYZ", DebitCredit::Debit), None);
+    }
+
+    #[test]
+    fn iso_maps_back_with_family_fallback() {
+        assert_eq!(iso_to_swift("ACMT", "MDOP", "CHRG"), "CHG");
+        assert_eq!(iso_to_swift("PMNT", "RCDT", "XBCT"), "TRF");
+        assert_eq!(iso_to_swift("LDAS", "CSLN", "OTHR"), "MSC");
+    }
+
+    #[test]
+    fn transaction_code_round_trip() {
+        let code = TransactionCode::from_swift("NCHG", DebitCredit::Debit);
+        assert_eq!(code.iso(DebitCredit::Debit), Some(("ACMT".into(), "MDOP".into(), "CHRG".into())));
+
+        let iso_only = TransactionCode { swift: None, ..code };
+        assert_eq!(iso_only.swift_code(), "NCHG");
+        assert_eq!(TransactionCode::default().swift_code(), "NMSC");
+
+        let prtry = TransactionCode {
+            proprietary: Some("NSTO".into()),
+            proprietary_issuer: Some(SWIFT_ISSUER.into()),
+            ..Default::default()
+        };
+        assert_eq!(prtry.swift_code(), "NSTO");
+    }
+}
@@ -0,0 +1,402 @@
+use std::fmt;
+use crate::adapter::money::Money;
+use crate::adapter::currency::find;
+use crate::adapter::identifier::{check_account_id, validate_bic};
+use crate::adapter::requisites::{validate_account, validate_bik, validate_inn, validate_kpp};
+use crate::adapter::statement::{Counterparty, Date, Statement};
+
+/// Largest distance in days between booking and value date that is not reported.
+pub const MAX_VALUE_DATE_GAP_DAYS: i64 = 31;
+
+/// Which consistency rule a finding comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Check {
+    /// Opening balance plus booked movement does not give the closing balance.
+    Balance,
+    /// Entry currency differs from the balance currency.
+    Currency,
+    /// Entry is booked outside the statement period.
+    Period,
+    /// Value date is implausibly far from the booking date.
+    ValueDate,
+    /// Account number shaped like an IBAN fails the IBAN checks.
+    Iban,
+    /// Bank identifier is not a valid BIC.
+    Bic,
+    /// Numeric bank identifier is not a valid Russian BIK.
+    Bik,
+    /// Russian account number fails the control key check against its BIK.
+    Account,
+    /// Tax identifier fails the INN check digits.
+    Inn,
+    /// KPP has a wrong format.
+    Kpp,
+}
+
+/// How serious a finding is; errors make a statement unfit for conversion.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    /// The statement is inconsistent.
+    Error,
+    /// Suspicious but possibly legitimate.
+    Warning,
+}
+
+/// A problem found in a statement.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Finding {
+    /// Rule that produced the finding.
+    pub check: Check,
+    /// Error or warning.
+    pub severity: Severity,
+    /// Index of the entry concerned, if any.
+    pub entry: Option<usize>,
+    /// Human-readable description.
+    pub message: String,
+}
+
+impl Finding {
+    fn error(check: Check, entry: Option<usize>, message: String) -> Self {
+        Finding { check, severity: Severity::Error, entry, message }
+    }
+
+    /// Returns `true` for findings of [`Severity::Error`].
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let level = match self.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+        match self.entry {
+            Some(i) => write!(f, "{level}: entry {}: {}", i + 1, self.message),
+            None => write!(f, "{level}: {}", self.message),
+        }
+    }
+}
+
+/// Runs all consistency checks on a statement.
+pub fn validate(st: &Statement) -> Vec<Finding> {
+    let mut findings = reconcile(st);
+    findings.extend(check_currencies(st));
+    findings.extend(check_dates(st));
+    findings.extend(check_identifiers(st));
+    findings.extend(check_requisites(st));
+    findings
+}
+
+/// Checks opening balance + credits − debits == closing balance for every
+/// currency that has both balances. Only booked entries count.
+pub fn reconcile(st: &Statement) -> Vec<Finding> {
+    let mut currencies: Vec<&str> = Vec::new();
+    for b in st.balances.iter().filter(|b| b.balance_type.is_opening()) {
+        if !currencies.contains(&b.currency.as_str()) {
+            currencies.push(&b.currency);
+        }
+    }
+
+    let mut findings = Vec::new();
+    for ccy in currencies {
+        let find = |closing: bool| {
+            st.balances.iter().find(|b| {
+                b.currency == ccy && if closing { b.balance_type.is_closing() } else { b.balance_type.is_opening() }
+            })
+        };
+        let (Some(opening), Some(closing)) = (find(false), find(true)) else { continue };
+        let movement = Money::checked_sum(
+            st.entries.iter().filter(|e| e.is_booked() && e.currency == ccy).map(|e| e.signed_amount()),
+        );
+        let (movement, expected) = match movement.and_then(|m| Ok((m, opening.signed_amount().checked_add(m)?))) {
+            Ok(sums) => sums,
+            Err(e) => {
+                findings.push(Finding::error(Check::Balance, None, format!("{ccy}: cannot add up the amounts: {e}")));
+                continue;
+            }
+        };
+        if expected != closing.signed_amount() {
+            findings.push(Finding::error(
+                Check::Balance,
+                None,
+                format!(
+                    "{ccy}: opening {} + movement {movement} = {expected}, but closing balance is {}",
+                    opening.signed_amount(),
+                    closing.signed_amount()
+                ),
+            ));
+        }
+    }
+    findings
+}
+
+/// Checks that entries are in a currency of the statement balances (or the
+/// account currency when there are no balances), and warns about codes
+/// unknown to ISO 4217.
+pub fn check_currencies(st: &Statement) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    let codes = st.balances.iter().map(|b| (None, &b.currency))
+        .chain(st.entries.iter().enumerate().map(|(i, e)| (Some(i), &e.currency)));
+    for (entry, code) in codes {
+        if find(code).is_none() {
+            findings.push(Finding {
+                check: Check::Currency,
+                severity: Severity::Warning,
+                entry,
+                message: format!("`{code}` is not an ISO 4217 currency"),
+            });
+        }
+    }
+
+    let mut known: Vec<&str> = st.balances.iter().map(|b| b.currency.as_str()).collect();
+    if known.is_empty() {
+        known.extend(st.account_currency.as_deref());
+    }
+    if known.is_empty() {
+        return findings;
+    }
+    for (i, e) in st.entries.iter().enumerate().filter(|(_, e)| !known.contains(&e.currency.as_str())) {
+        findings.push(Finding::error(
+            Check::Currency,
+            Some(i),
+            format!("currency {} does not match the statement currency {}", e.currency, known.join("/")),
+        ));
+    }
+    findings
+}
+
+/// Checks that booking dates fall inside the statement period and that value
+/// dates are close to booking dates.
+///
+/// Without an explicit period, the opening and closing balance dates bound it.
+pub fn check_dates(st: &Statement) -> Vec<Finding> {
+    let bounds = match &st.period {
+        Some(p) => Some((p.from.date(), p.to.date())),
+        None => st.opening_balance().zip(st.closing_balance()).map(|(o, c)| (o.date, c.date)),
+    };
+    let mut findings = Vec::new();
+    for (i, e) in st.entries.iter().enumerate() {
+        if let Some((from, to)) = bounds
+            && (e.booking_date < from || e.booking_date > to)
+        {
+            findings.push(Finding::error(
+                Check::Period,
+                Some(i),
+                format!("booking date {} is outside the statement period {from} – {to}", e.booking_date),
+            ));
+        }
+        let gap = days_between(e.booking_date, e.value_date);
+        if gap > MAX_VALUE_DATE_GAP_DAYS {
+            findings.push(Finding {
+                check: Check::ValueDate,
+                severity: Severity::Warning,
+                entry: Some(i),
+                message: format!("value date {} is {gap} days from booking date {}", e.value_date, e.booking_date),
+            });
+        }
+    }
+    findings
+}
+
+/// Checks IBANs and BICs of the account and of counterparties. Account
+/// numbers that are not shaped like an IBAN and numeric bank codes (BIK) are
+/// not checked here.
+pub fn check_identifiers(st: &Statement) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    let mut check_iban = |entry: Option<usize>, account: &str| {
+        if let Err(e) = check_account_id(account) {
+            findings.push(Finding::error(Check::Iban, entry, e));
+        }
+    };
+    check_iban(None, &st.account_id);
+    for (i, p) in parties(st) {
+        if let Some(account) = &p.account {
+            check_iban(Some(i), account);
+        }
+    }
+
+    let mut check_bic = |entry: Option<usize>, bic: &str| {
+        if !bic.bytes().all(|b| b.is_ascii_digit())
+            && let Err(e) = validate_bic(bic)
+        {
+            findings.push(Finding::error(Check::Bic, entry, e));
+        }
+    };
+    if let Some(bic) = &st.servicer_bic {
+        check_bic(None, bic);
+    }
+    for (i, p) in parties(st) {
+        if let Some(bic) = &p.bank_id {
+            check_bic(Some(i), bic);
+        }
+    }
+    findings
+}
+
+/// Checks Russian requisites of counterparties: BIK, the account control key
+/// against the BIK, INN check digits and KPP format. Numeric tax identifiers
+/// are taken for INNs.
+pub fn check_requisites(st: &Statement) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    for (i, p) in parties(st) {
+        let mut push = |check: Check, result: Result<(), String>| {
+            if let Err(e) = result {
+                findings.push(Finding::error(check, Some(i), e));
+            }
+        };
+        if let Some(bik) = p.bank_id.as_deref().filter(|id| id.bytes().all(|b| b.is_ascii_digit())) {
+            let bik_ok = validate_bik(bik);
+            let checked = bik_ok.is_ok();
+            push(Check::Bik, bik_ok);
+            if checked
+                && let Some(account) = p.account.as_deref().filter(|a| a.len() == 20)
+            {
+                push(Check::Account, validate_account(account, bik));
+            }
+        }
+        if let Some(inn) = p.tax_id.as_deref().filter(|id| id.bytes().all(|b| b.is_ascii_digit())) {
+            push(Check::Inn, validate_inn(inn));
+        }
+        if let Some(kpp) = &p.kpp {
+            push(Check::Kpp, validate_kpp(kpp));
+        }
+    }
+    findings
+}
+
+/// Counterparties and own parties of all entries and their batch transactions.
+fn parties(st: &Statement) -> impl Iterator<Item = (usize, &Counterparty)> {
+    st.entries.iter().enumerate().flat_map(|(i, e)| {
+        let tx_parties = e.transactions.iter().flat_map(|tx| [&tx.counterparty, &tx.own_party]);
+        [&e.counterparty, &e.own_party].into_iter().chain(tx_parties).flatten().map(move |p| (i, p))
+    })
+}
+
+fn days_between(a: Date, b: Date) -> i64 {
+    (a.days_since_epoch() - b.days_since_epoch()).abs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapter::statement::{Balance, BalanceType, DebitCredit, Entry, EntryStatus};
+
+    fn date(day: u8) -> Date {
+        Date::new(2025, 10, day).unwrap()
+    }
+
+    fn balance(balance_type: BalanceType, day: u8, amount: &str) -> Balance {
+        Balance {
+            balance_type,
+            kind: DebitCredit::Credit,
+            date: date(day),
+            currency: "EUR".to_string(),
+            amount: Money::parse(amount).unwrap(),
+        }
+    }
+
+    fn entry(day: u8, kind: DebitCredit, amount: &str) -> Entry {
+        Entry {
+            booking_date: date(day),
+            value_date: date(day),
+            amount: Money::parse(amount).unwrap(),
+            currency: "EUR".to_string(),
+            kind,
+            ..Default::default()
+        }
+    }
+
+    fn statement(closing: &str) -> Statement {
+        Statement {
+            balances: vec![
+                balance(BalanceType::OpeningBooked, 1, "100"),
+                balance(BalanceType::ClosingBooked, 31, closing),
+            ],
+            entries: vec![
+                entry(2, DebitCredit::Credit, "50"),
+                entry(3, DebitCredit::Debit, "20"),
+                Entry { status: EntryStatus::Pending, ..entry(4, DebitCredit::Credit, "999") },
+            ],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn overflowing_amounts_are_a_finding() {
+        let mut st = statement("130");
+        st.entries.push(entry(5, DebitCredit::Credit, "0.000000000000000001"));
+        let findings = reconcile(&st);
+        assert_eq!(findings.len(), 1);
+        assert!(findings[0].is_error() && findings[0].message.contains("cannot add up"), "{}", findings[0]);
+    }
+
+    #[test]
+    fn consistent_statement_has_no_findings() {
+        assert_eq!(validate(&statement("130")), []);
+    }
+
+    #[test]
+    fn balance_mismatch_is_reported() {
+        let findings = validate(&statement("131"));
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].check, Check::Balance);
+        assert!(findings[0].is_error());
+    }
+
+    #[test]
+    fn entry_currency_and_dates_are_checked() {
+        let mut st = statement("130");
+        st.entries[0].currency = "USD".to_string();
+        st.entries[1].booking_date = Date::new(2025, 11, 3).unwrap();
+        st.entries[2].value_date = Date::new(2024, 10, 4).unwrap();
+
+        let checks: Vec<_> = validate(&st).iter().map(|f| (f.check, f.entry, f.severity)).collect();
+        assert!(checks.contains(&(Check::Balance, None, Severity::Error)));
+        assert!(checks.contains(&(Check::Currency, Some(0), Severity::Error)));
+        assert!(checks.contains(&(Check::Period, Some(1), Severity::Error)));
+        assert!(checks.contains(&(Check::ValueDate, Some(2), Severity::Warning)));
+    }
+
+    #[test]
+    fn identifiers_are_checked() {
+        let mut st = statement("130");
+        st.account_id = "DE88370400440532013000".to_string();
+        st.servicer_bic = Some("COBADEFFXXX".to_string());
+        st.entries[0].counterparty = Some(Counterparty {
+            account: Some("40702810600014448120".to_string()),
+            bank_id: Some("044525545".to_string()),
+            ..Default::default()
+        });
+        st.entries[1].counterparty = Some(Counterparty { bank_id: Some("COBAQQFF".to_string()), ..Default::default() });
+
+        let checks: Vec<_> = validate(&st).iter().map(|f| (f.check, f.entry)).collect();
+        assert_eq!(checks, [(Check::Iban, None), (Check::Bic, Some(1))]);
+    }
+
+    #[test]
+    fn russian_requisites_are_checked() {
+        let party = |account: &str, bik: &str, inn: &str, kpp: &str| {
+            Some(Counterparty {
+                account: Some(account.to_string()),
+                bank_id: Some(bik.to_string()),
+                tax_id: Some(inn.to_string()),
+                kpp: Some(kpp.to_string()),
+                ..Default::default()
+            })
+        };
+        let mut st = statement("130");
+        st.entries[0].counterparty = party("40702810600014448120", "044525545", "7733573894", "773301001");
+        st.entries[1].counterparty = party("40702810700014448120", "044525545", "7733573895", "7733010");
+        st.entries[2].counterparty = party("40702810600014448120", "123456789", "7733573894", "773301001");
+
+        let checks: Vec<_> = validate(&st).iter().map(|f| (f.check, f.entry)).collect();
+        assert_eq!(checks, [
+            (Check::Account, Some(1)),
+            (Check::Inn, Some(1)),
+            (Check::Kpp, Some(1)),
+            (Check::Bik, Some(2)),
+        ]);
+    }
+}
@@ -1,12 +1,14 @@
 use clap::{Parser, ValueEnum};
 use std::fs::File;
-use std::io::{self, BufReader, Read, Write};
+use std::io::{self, Read, Write};
 use fin_parser::format::xml::XML;
 use fin_parser::format::csv::CSV;
-use fin_parser::format::mt940::Mt940;
-use fin_parser::adapter::adapter::Adapter;
-use fin_parser::adapter::errors::AdapterError;
+use fin_parser::format::mt940::{self, Mt940};
+use fin_parser::adapter::adapter::{Adapter, Mode, ReadOptions};
+use fin_parser::adapter::errors::{AdapterError, Location};
 use fin_parser::format::camt::CAMT;
+use fin_parser::adapter::validation::validate;
+use fin_parser::adapter::statement::FinEnvelope;
 
 #[derive(Debug, Clone,ValueEnum)]
 enum Format {
@@ -28,41 +30,153 @@ struct Cli {
     #[arg(long="in-format", value_enum)]
     in_format: Format,
 
-    #[arg(long="out-format", value_enum)]
-    out_format: Format,
+    #[arg(long="out-format", value_enum, required_unless_present="validate")]
+    out_format: Option<Format>,
+
+    /// Check balances, currencies, dates and bank requisites; exit with status 1 on errors and
+    /// convert only when `--out-format` is given and the input is consistent.
+    #[arg(long="validate")]
+    validate: bool,
+
+    /// Split batch entries into one entry per transaction (for CSV or MT940 output).
+    #[arg(long="expand-batches")]
+    expand_batches: bool,
+
+    /// Fail on the first anomaly, including unknown ISO 4217 currency codes.
+    #[arg(long="strict")]
+    strict: bool,
+
+    /// Skip transactions and records that cannot be read instead of failing.
+    #[arg(long="lenient", conflicts_with = "strict")]
+    lenient: bool,
+
+    /// Write the source of records skipped in lenient mode to this file.
+    #[arg(long="quarantine", requires = "lenient")]
+    quarantine: Option<String>,
+
+    /// Wrap each MT940 statement in a SWIFT FIN envelope ({1:}{2:}{3:}{4:...-}).
+    #[arg(long="fin-envelope")]
+    fin_envelope: bool,
+
+    /// Sender BIC for the FIN envelope when the statement has none (defaults to the servicer BIC).
+    #[arg(long="fin-sender", requires = "fin_envelope")]
+    fin_sender: Option<String>,
+
+    /// Receiver BIC for the FIN envelope when the statement has none.
+    #[arg(long="fin-receiver", requires = "fin_envelope")]
+    fin_receiver: Option<String>,
+
+    /// Use the SWIFT Cyrillic transliteration for MT940 text: written on output, reversed on input.
+    #[arg(long="transliterate")]
+    transliterate: bool,
+
+    /// End MT940 lines with CRLF, as SWIFT does.
+    #[arg(long="crlf")]
+    crlf: bool,
+
+    /// Write MT940 `:86:` in the German structured format (GVC with ?xx subfields).
+    #[arg(long="german")]
+    german: bool,
+
+    /// Derive missing MT940 opening or closing balances from the entries instead of failing.
+    #[arg(long="derive-balances")]
+    derive_balances: bool,
 }
 
-fn main() -> Result<(), AdapterError>{
+fn main() {
     let cli = Cli::parse();
-    let reader: Box<dyn Read> = match cli.input {
-        Some(path) => Box::new(
-            File::open(path).map_err(|e|AdapterError::ParseError(e.to_string()))?
-        ),
-        None => Box::new(io::stdin()),
+    let mut input = Vec::new();
+    if let Err(e) = run(&cli, &mut input) {
+        eprintln!("{}", diagnostic("error", &e, cli.input.as_deref().unwrap_or("<stdin>"), &input));
+        std::process::exit(1);
+    }
+}
+
+fn run(cli: &Cli, input: &mut Vec<u8>) -> Result<(), AdapterError> {
+    match &cli.input {
+        Some(path) => File::open(path)?.read_to_end(input)?,
+        None => io::stdin().read_to_end(input)?,
     };
-    let buf = BufReader::new(reader);
+    let buf = input.as_slice();
 
-    let statement = match cli.in_format {
-        Format::Csv => { CSV::read_from(buf)},
-        Format::Mt940 => { Mt940::read_from(buf)},
-        Format::Xml => { XML::read_from(buf)},
-        Format::Camt053 => { CAMT::read_from(buf)},
+    let mode = match (cli.strict, cli.lenient) {
+        (true, _) => Mode::Strict,
+        (_, true) => Mode::Lenient,
+        _ => Mode::Normal,
+    };
+    let options = ReadOptions { mode, transliterated: cli.transliterate };
+    let report = match cli.in_format {
+        Format::Csv => { CSV::read_with(buf, &options)},
+        Format::Mt940 => { Mt940::read_with(buf, &options)},
+        Format::Xml => { XML::read_with(buf, &options)},
+        Format::Camt053 => { CAMT::read_with(buf, &options)},
     }?;
 
+    let source = cli.input.as_deref().unwrap_or("<stdin>");
+    for w in &report.warnings {
+        eprintln!("{}", diagnostic("warning", w, source, buf));
+    }
+    if let Some(path) = &cli.quarantine {
+        report.write_quarantine(File::create(path)?)?;
+    }
+    let mut statements = report.statements;
 
-    let mut writer: Box<dyn Write> = match cli.output {
-        Some(path) => Box::new(
-            File::create(path).map_err(|e|AdapterError::WriteError(e.to_string()))?
-        ),
+    if cli.expand_batches {
+        statements.iter_mut().for_each(|st| st.expand_batches());
+    }
+
+    if cli.validate {
+        let mut failed = false;
+        for st in &statements {
+            for finding in validate(st) {
+                failed |= finding.is_error();
+                eprintln!("{}: {finding}", st.id);
+            }
+        }
+        if failed {
+            std::process::exit(1);
+        }
+    }
+    let Some(out_format) = &cli.out_format else { return Ok(()) };
+
+    let mut writer: Box<dyn Write> = match &cli.output {
+        Some(path) => Box::new(File::create(path)?),
         None => Box::new(io::stdout()),
     };
 
-    match cli.out_format {
-        Format::Csv => CSV::write_to(&mut writer, &statement),
-        Format::Mt940 => Mt940::write_to(&mut writer, &statement),
-        Format::Xml => XML::write_to(&mut writer, &statement),
-        Format::Camt053 => CAMT::write_to(&mut writer, &statement),
+    match out_format {
+        Format::Csv => CSV::write_all(&mut writer, &statements),
+        Format::Mt940 => {
+            let envelope = cli.fin_envelope.then(|| FinEnvelope {
+                sender: cli.fin_sender.clone(),
+                receiver: cli.fin_receiver.clone(),
+                ..Default::default()
+            });
+            let options = mt940::WriteOptions {
+                transliterate: cli.transliterate,
+                envelope,
+                crlf: cli.crlf,
+                german: cli.german,
+                derive_balances: cli.derive_balances,
+            };
+            Mt940::write_with(&mut writer, &statements, &options)
+        }
+        Format::Xml => XML::write_all(&mut writer, &statements),
+        Format::Camt053 => CAMT::write_all(&mut writer, &statements),
     }?;
 
-    writer.flush().map_err(|e|AdapterError::WriteError(e.to_string()))
-}
\ No newline at end of file
+    Ok(writer.flush()?)
+}
+
+/// Renders an error or warning for the terminal; those located on a line quote it with a caret under the column.
+fn diagnostic(level: &str, e: &AdapterError, source: &str, input: &[u8]) -> String {
+    let mut out = format!("{level}: {e}");
+    if let Some(Location::Line { line, column }) = e.location()
+        && let Some(text) = String::from_utf8_lossy(input).lines().nth(line - 1)
+    {
+        let gutter = " ".repeat(line.to_string().len());
+        let caret = " ".repeat(column - 1);
+        out.push_str(&format!("\n{gutter}--> {source}:{line}:{column}\n{gutter} |\n{line} | {text}\n{gutter} | {caret}^"));
+    }
+    out
+}
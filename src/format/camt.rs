@@ -1,10 +1,16 @@
 use std::io::{BufRead, Write};
-use crate::adapter::adapter::{Adapter, Statement};
-use crate::adapter::errors::{map_parse_err, AdapterError};
+use crate::adapter::adapter::{Adapter, ReadOptions, ReadReport, Statement};
+use crate::adapter::errors::{map_parse_err, AdapterError, Location};
 use quick_xml::{Reader,Writer};
 use quick_xml::events::{Event,BytesDecl,BytesStart,BytesText};
 use quick_xml::escape::unescape;
-use crate::adapter::statement::{Balance, DebitCredit, Entry};
+use crate::adapter::money::Money;
+use crate::adapter::transaction_code::{TransactionCode, SWIFT_ISSUER};
+use crate::adapter::reference::{is_creditor_reference, validate_creditor_reference};
+use crate::adapter::identifier::{is_iban, normalize_account_id, validate_bic, validate_iban};
+use crate::adapter::currency::{normalize_statement, scale};
+use crate::adapter::statement::{Batch, Charge, CurrencyAmount, CurrencyExchange, Extensions, TransactionDetails};
+use crate::adapter::statement::{Balance, BalanceType, Counterparty, Date, DateTime, DebitCredit, Entry, EntryStatus, Period};
 
 /// CAMT adapter implementing the `Adapter` trait.
 ///
@@ -12,294 +18,874 @@ use crate::adapter::statement::{Balance, DebitCredit, Entry};
 pub struct CAMT;
 
 impl Adapter for CAMT {
+    /// Reads the first `<Stmt>` of the document; use [`Adapter::read_all`] for the rest.
     fn read_from<R: BufRead>(r: R) -> Result<Statement, AdapterError> {
-        let mut reader = Reader::from_reader(r);
-        reader.config_mut().trim_text(true);
-
-        let mut st = Statement {
-            id: String::new(),
-            account_id: String::new(),
-            opening_balance: None,
-            closing_balance: None,
-            entries: Vec::new(),
-        };
+        let mut all = Self::read_all(r)?;
+        Ok(all.swap_remove(0))
+    }
+
+    fn read_all<R: BufRead>(r: R) -> Result<Vec<Statement>, AdapterError> {
+        Ok(Self::read_with(r, &ReadOptions::default())?.statements)
+    }
 
-        // Текущее состояние курсора
-        #[derive(Default)]
-        struct State {
-            in_iban: bool,
-            in_stmt_id: bool, // Id именно внутри Stmt (не Acct/Id)
-            in_amt: bool,
-            amt_ccy: String,
-            in_cdt_dbt: bool,
-            in_book_dt: bool,
-            in_val_dt: bool,
-            in_addtl: bool,
-            in_ntry_ref: bool,
-            pending: Option<Entry>,
+    /// An entry or balance that cannot be read is rejected as a whole; broken XML fails the read.
+    fn read_with<R: BufRead>(mut r: R, options: &ReadOptions) -> Result<ReadReport, AdapterError> {
+        let mut input = Vec::new();
+        r.read_to_end(&mut input)?;
+        let mut report = ReadReport::new(options.mode);
+        for mut st in read_document(&input, &mut report)? {
+            report.normalize(&mut st)?;
+            report.statements.push(st);
         }
-        let mut s = State::default();
-
-        let mut buf = Vec::new();
-
-        loop {
-            match reader.read_event_into(&mut buf) {
-                Ok(Event::Start(e)) => {
-                    match e.local_name().as_ref() {
-                        b"IBAN" => s.in_iban = true,
-                        b"Stmt" => {}
-                        b"Id" => s.in_stmt_id = true,
-                        b"Amt" => {
-                            s.in_amt = true;
-                            s.amt_ccy = attr_value(&e, b"Ccy").unwrap_or_default();
-                        }
-                        b"CdtDbtInd" => s.in_cdt_dbt = true,
-                        b"BookgDt" => s.in_book_dt = true,
-                        b"ValDt" => s.in_val_dt = true,
-                        b"AddtlNtryInf" => s.in_addtl = true,
-                        b"NtryRef" => s.in_ntry_ref = true,
-                        b"Ntry" => {
-                            s.pending = Some(Entry {
-                                booking_date: String::new(),
-                                value_date: String::new(),
-                                amount: String::new(),
-                                currency: String::from("XXX"),
-                                kind: DebitCredit::Credit,
-                                description: String::new(),
-                                reference: None,
-                            });
-                        }
-                        _ => {}
+        Ok(report)
+    }
+
+    fn write_to<W: Write>(writer: W, st: &Statement) -> Result<(), AdapterError> {
+        Self::write_all(writer, std::slice::from_ref(st))
+    }
+
+    /// Writes all statements as `<Stmt>` blocks of a single `BkToCstmrStmt` document.
+    fn write_all<W: Write>(mut writer: W, statements: &[Statement]) -> Result<(), AdapterError> {
+        let mut wr = Writer::new_with_indent(&mut writer, b' ', 2);
+        wr.write_event(Event::Decl(BytesDecl::new("1.0", Some("UTF-8"), None)))?;
+
+        // <Document xmlns="...">
+        let mut doc = BytesStart::new("Document");
+        doc.push_attribute(("xmlns", "urn:iso:std:iso:20022:tech:xsd:camt.053.001.02"));
+        wr.write_event(Event::Start(doc))?;
+
+        // <BkToCstmrStmt><GrpHdr>...</GrpHdr><Stmt>...</Stmt>...
+        start(&mut wr, "BkToCstmrStmt")?;
+        let first = statements.first();
+        let created_at = first.and_then(|st| st.created_at).unwrap_or_else(DateTime::now_utc);
+        start(&mut wr, "GrpHdr")?;
+        elem_text(&mut wr, "MsgId", first.map_or("none", |st| st.id.as_str()))?;
+        elem_text(&mut wr, "CreDtTm", &created_at.to_string())?;
+        end(&mut wr, "GrpHdr")?;
+
+        for st in statements {
+            write_statement(&mut wr, st, created_at)?;
+        }
+
+        // </BkToCstmrStmt></Document>
+        end(&mut wr, "BkToCstmrStmt")?;
+        end(&mut wr, "Document")?;
+        Ok(())
+    }
+}
+
+/// Reads every `<Stmt>` of a document; see [`Adapter::read_with`] for the handling of `report`.
+fn read_document(input: &[u8], report: &mut ReadReport) -> Result<Vec<Statement>, AdapterError> {
+    let mut reader = Reader::from_reader(input);
+    reader.config_mut().trim_text(true);
+
+    let mut statements = Vec::new();
+    let mut st = Statement::default();
+
+    // Текущее состояние курсора
+    #[derive(Default)]
+    struct State {
+        path: Vec<String>, // локальные имена открытых элементов
+        amt_ccy: String,
+        group_created_at: Option<DateTime>,
+        period_from: Option<DateTime>,
+        period_to: Option<DateTime>,
+        bal_code: String,
+        bal_amount: Option<(Money, String)>,
+        bal_kind: DebitCredit,
+        bal_date: Option<Date>,
+        book_dt: Option<Date>,
+        val_dt: Option<Date>,
+        txs: Vec<TxState>,
+        pending: Option<Entry>,
+        capture: Option<Capture>,
+        record: Option<Record>,
+    }
+    let mut s = State::default();
+
+    let mut buf = Vec::new();
+
+    loop {
+        let pos = reader.buffer_position() as usize;
+        match reader.read_event_into(&mut buf) {
+            // Lenient mode: skip the rest of a rejected entry or balance.
+            Ok(ev) if s.record.as_ref().is_some_and(|r| r.skipping) && !matches!(ev, Event::Eof) => {
+                match ev {
+                    Event::Start(_) => s.path.push(String::new()),
+                    Event::End(_) => { s.path.pop(); }
+                    _ => {}
+                }
+                if let Some(rec) = s.record.take_if(|r| r.depth == s.path.len()) {
+                    quarantine_record(report, rec, input, reader.buffer_position() as usize);
+                    s.pending = None;
+                    s.txs.clear();
+                    s.bal_amount = None;
+                    s.bal_date = None;
+                    s.bal_code.clear();
+                    s.bal_kind = DebitCredit::default();
+                }
+            }
+
+            // Inside an unmapped element: copy events verbatim until it closes.
+            Ok(ev) if s.capture.is_some() && !matches!(ev, Event::Eof) => {
+                let Some(cap) = s.capture.as_mut() else { unreachable!() };
+                match &ev {
+                    Event::Start(_) => cap.depth += 1,
+                    Event::End(_) => cap.depth -= 1,
+                    _ => {}
+                }
+                cap.out.write_event(ev)?;
+                if cap.depth == 0
+                    && let Some(cap) = s.capture.take()
+                {
+                    reader.config_mut().trim_text(true);
+                    let raw = String::from_utf8(cap.out.into_inner()).map_err(map_parse_err)?;
+                    keep_unmapped(&mut st, s.pending.as_mut(), &mut s.txs, cap.key, raw);
+                }
+            }
+
+            Ok(Event::Start(e)) => {
+                let name = String::from_utf8_lossy(e.local_name().as_ref()).into_owned();
+                if let Some(key) = unmapped_key(&s.path, &name, s.pending.is_some()) {
+                    let mut out = Writer::new(Vec::new());
+                    out.write_event(Event::Start(e))?;
+                    s.capture = Some(Capture { key, depth: 1, out });
+                    // Kept elements are copied verbatim, whitespace included.
+                    reader.config_mut().trim_text(false);
+                    buf.clear();
+                    continue;
+                }
+                match name.as_str() {
+                    "Amt" | "TtlAmt" => s.amt_ccy = attr_value(&e, b"Ccy").unwrap_or_default(),
+                    "Bal" if s.pending.is_none() => s.record = Some(Record::new(pos, &s.path, &name)),
+                    "Ntry" => {
+                        s.record = Some(Record::new(pos, &s.path, &name));
+                        s.book_dt = None;
+                        s.val_dt = None;
+                        s.txs.clear();
+                        s.pending = Some(Entry::default());
                     }
+                    "TxDtls" if s.pending.is_some() => s.txs.push(TxState::default()),
+                    _ => {}
                 }
+                s.path.push(name);
+            }
 
-                Ok(Event::Text(t)) => {
-                    let txt = read_text(t)?;
-                    if s.in_iban {
-                        st.account_id = txt;
-                    } else if s.in_stmt_id && st.id.is_empty() {
-                        st.id = txt;
-                    } else if s.in_amt {
-                        if let Some(ref mut e) = s.pending {
-                            e.amount = txt;
-                            if !s.amt_ccy.is_empty() {
-                                e.currency = s.amt_ccy.clone();
+            Ok(Event::Text(t)) => {
+                let txt = read_text(t)?;
+                let path = format!("/{}", s.path.join("/"));
+                let value = txt.clone();
+                // Errors in a value name its element and path.
+                let read = || -> Result<(), AdapterError> {
+
+                    if let Some(e) = s.pending.as_mut()
+                        && let Some(rel) = tail(&path, "Ntry")
+                    {
+                        match rel {
+                            "Amt" => {
+                                e.amount = Money::parse(&txt).map_err(map_parse_err)?;
+                                if !s.amt_ccy.is_empty() {
+                                    e.currency = s.amt_ccy.clone();
+                                }
                             }
-                        }
-                    } else if s.in_cdt_dbt {
-                        if let Some(ref mut e) = s.pending {
-                            e.kind = match txt.as_str() {
-                                "CRDT" => DebitCredit::Credit,
-                                "DBIT" => DebitCredit::Debit,
-                                other => {
-                                    return Err(AdapterError::ParseError(
-                                        format!("Unexpected CdtDbtInd `{other}`"),
-                                    ))
+                            "CdtDbtInd" => e.kind = parse_credit_debit(&txt)?,
+                            "RvslInd" => e.reversal = txt == "true" || txt == "1",
+                            "Sts" | "Sts/Cd" => e.status = parse_entry_status(&txt)?,
+                            "BookgDt/Dt" | "BookgDt/DtTm" => {
+                                s.book_dt = Some(Date::parse_iso(&txt).map_err(map_parse_err)?);
+                            }
+                            "ValDt/Dt" | "ValDt/DtTm" => {
+                                s.val_dt = Some(Date::parse_iso(&txt).map_err(map_parse_err)?);
+                            }
+                            "AddtlNtryInf" => e.description = txt,
+                            "NtryRef" => e.reference = if txt.is_empty() { None } else { Some(txt) },
+                            "AcctSvcrRef" => e.servicer_reference = Some(txt),
+                            _ if rel.starts_with("BkTxCd/") => {
+                                read_bank_tx_code(e.transaction_code.get_or_insert_with(Default::default), &rel[7..], txt);
+                            }
+                            _ if rel.starts_with("NtryDtls/Btch/") => {
+                                read_batch(e.batch.get_or_insert_with(Default::default), &rel[14..], txt, &s.amt_ccy)?;
+                            }
+                            _ if rel.starts_with("NtryDtls/TxDtls/") => {
+                                if let Some(tx) = s.txs.last_mut() {
+                                    read_tx_details(tx, e, &rel[16..], txt, &s.amt_ccy)?;
                                 }
-                            };
-                        }
-                    } else if s.in_book_dt {
-                        if let Some(ref mut e) = s.pending {
-                            e.booking_date = txt;
-                        }
-                    } else if s.in_val_dt {
-                        if let Some(ref mut e) = s.pending {
-                            e.value_date = txt;
-                        }
-                    } else if s.in_addtl {
-                        if let Some(ref mut e) = s.pending {
-                            e.description = txt;
+                            }
+                            _ if rel.starts_with("AmtDtls/") || rel.starts_with("Chrgs/") => {
+                                read_amount_details(e, rel, txt, &s.amt_ccy)?;
+                            }
+                            _ => {}
                         }
-                    } else if s.in_ntry_ref {
-                        if let Some(ref mut e) = s.pending {
-                            e.reference = if txt.is_empty() { None } else { Some(txt) };
+                    } else if let Some(rel) = tail(&path, "Stmt") {
+                        let seq = |t: &str| t.parse::<u64>().map_err(map_parse_err);
+                        match rel {
+                            "Id" if st.id.is_empty() => st.id = txt,
+                            "ElctrncSeqNb" => st.electronic_sequence = Some(seq(&txt)?),
+                            "LglSeqNb" => st.legal_sequence = Some(seq(&txt)?),
+                            "StmtPgntn/PgNb" => st.page_number = Some(txt.parse().map_err(map_parse_err)?),
+                            "CreDtTm" => st.created_at = Some(DateTime::parse_iso(&txt).map_err(map_parse_err)?),
+                            "FrToDt/FrDtTm" => s.period_from = Some(DateTime::parse_iso(&txt).map_err(map_parse_err)?),
+                            "FrToDt/ToDtTm" => s.period_to = Some(DateTime::parse_iso(&txt).map_err(map_parse_err)?),
+                            "Acct/Id/IBAN" => st.account_id = normalize_account_id(&txt),
+                            "Acct/Id/Othr/Id" => st.account_id = txt,
+                            "Bal/Tp/CdOrPrtry/Cd" => s.bal_code = txt,
+                            "Bal/Amt" => s.bal_amount = Some((Money::parse(&txt).map_err(map_parse_err)?, s.amt_ccy.clone())),
+                            "Bal/CdtDbtInd" => s.bal_kind = parse_credit_debit(&txt)?,
+                            "Bal/Dt/Dt" | "Bal/Dt/DtTm" => s.bal_date = Some(Date::parse_iso(&txt).map_err(map_parse_err)?),
+                            "Acct/Ccy" => st.account_currency = Some(txt),
+                            "Acct/Ownr/Nm" | "Acct/Ownr/Pty/Nm" => st.account_owner = Some(txt),
+                            "Acct/Svcr/FinInstnId/BIC" | "Acct/Svcr/FinInstnId/BICFI" => st.servicer_bic = Some(txt),
+                            _ => {}
                         }
+                    } else if tail(&path, "GrpHdr") == Some("CreDtTm") {
+                        s.group_created_at = Some(DateTime::parse_iso(&txt).map_err(map_parse_err)?);
+                    }
+                    Ok(())
+                };
+                let field = s.path.last().cloned().unwrap_or_default();
+                if let Err(e) = read() {
+                    // Lenient mode drops the enclosing entry or balance, or else just the value.
+                    report.reject(e.in_field(&field, &value, Location::Path(path.clone())))?;
+                    if let Some(rec) = s.record.as_mut() {
+                        rec.skipping = true;
                     }
                 }
+                // Invalid identifiers are kept as read.
+                let checked = match field.as_str() {
+                    "IBAN" => validate_iban(&value).map(drop),
+                    "BIC" | "BICFI" => validate_bic(&value),
+                    _ => Ok(()),
+                };
+                if let Err(e) = checked {
+                    report.warn(map_parse_err(e).in_field(&field, &value, Location::Path(path)))?;
+                }
+            }
 
-                Ok(Event::End(e)) => {
-                    match e.local_name().as_ref() {
-                        b"IBAN" => s.in_iban = false,
-                        b"Id" => s.in_stmt_id = false,
-                        b"Amt" => s.in_amt = false,
-                        b"CdtDbtInd" => s.in_cdt_dbt = false,
-                        b"BookgDt" => s.in_book_dt = false,
-                        b"ValDt" => s.in_val_dt = false,
-                        b"AddtlNtryInf" => s.in_addtl = false,
-                        b"NtryRef" => s.in_ntry_ref = false,
-                        b"Ntry" => {
-                            if let Some(e) = s.pending.take() {
-                                st.entries.push(e);
+            Ok(Event::End(_)) => {
+                let closed = s.path.pop();
+                if closed.as_deref() == Some("Bal") && s.pending.is_none() {
+                    let code = std::mem::take(&mut s.bal_code);
+                    let kind = std::mem::take(&mut s.bal_kind);
+                    let at = Location::Path(format!("/{}/Bal", s.path.join("/")));
+                    let rec = s.record.take();
+                    match (s.bal_amount.take(), s.bal_date.take()) {
+                        (Some((amount, currency)), Some(date)) => {
+                            // Unknown proprietary balance types are skipped.
+                            if let Some(balance_type) = balance_type_from_code(&code, &st.balances) {
+                                st.balances.push(Balance { balance_type, kind, date, currency, amount });
+                            }
+                        }
+                        (amount, _) => {
+                            report.reject(AdapterError::missing(if amount.is_none() { "Amt" } else { "Dt" }).at(at))?;
+                            if let Some(rec) = rec {
+                                quarantine_record(report, rec, input, reader.buffer_position() as usize);
                             }
                         }
-                        _ => {}
                     }
+                } else if closed.as_deref() == Some("Stmt") {
+                    if let (Some(from), Some(to)) = (s.period_from.take(), s.period_to.take()) {
+                        st.period = Some(Period { from, to });
+                    }
+                    st.created_at = st.created_at.or(s.group_created_at);
+                    statements.push(finish_statement(std::mem::take(&mut st))?);
+                } else if closed.as_deref() == Some("Ntry")
+                    && let Some(mut e) = s.pending.take()
+                {
+                    let rec = s.record.take();
+                    let Some(booking) = s.book_dt.or(s.val_dt) else {
+                        report.reject(AdapterError::missing("BookgDt").at(Location::Path(format!("/{}/Ntry", s.path.join("/")))))?;
+                        if let Some(rec) = rec {
+                            quarantine_record(report, rec, input, reader.buffer_position() as usize);
+                        }
+                        buf.clear();
+                        continue;
+                    };
+                    e.booking_date = booking;
+                    e.value_date = s.val_dt.unwrap_or(booking);
+
+                    let kind = e.kind;
+                    let mut txs: Vec<_> = s.txs.drain(..).map(|tx| tx.finish(kind)).collect();
+                    // A single payment keeps its details on the entry itself.
+                    match txs.pop() {
+                        Some(tx) if txs.is_empty() && e.batch.is_none() => merge_transaction(&mut e, tx),
+                        Some(tx) => {
+                            txs.push(tx);
+                            e.transactions = txs;
+                        }
+                        None => {}
+                    }
+                    if e.description.is_empty() {
+                        e.description = e.remittance_text().unwrap_or_default();
+                    }
+                    // `Amt/@Ccy` is mandatory; fall back to the account currency, or `XXX` (no currency).
+                    if e.currency.is_empty() {
+                        e.currency = st.account_currency.clone()
+                            .or_else(|| st.balances.first().map(|b| b.currency.clone()))
+                            .unwrap_or_else(|| "XXX".to_string());
+                    }
+                    st.entries.push(e);
                 }
+            }
 
-                Ok(Event::Eof) => break,
-                Err(e) => return Err(AdapterError::ParseError(e.to_string())),
-                _ => {}
+            // An empty unmapped element is kept as is.
+            Ok(Event::Empty(e)) => {
+                let name = String::from_utf8_lossy(e.local_name().as_ref()).into_owned();
+                if let Some(key) = unmapped_key(&s.path, &name, s.pending.is_some()) {
+                    let mut out = Writer::new(Vec::new());
+                    out.write_event(Event::Empty(e))?;
+                    let raw = String::from_utf8(out.into_inner()).map_err(map_parse_err)?;
+                    keep_unmapped(&mut st, s.pending.as_mut(), &mut s.txs, key, raw);
+                }
             }
-            buf.clear();
-        }
 
-        if st.id.is_empty() {
-            st.id = "none".to_string();
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(map_parse_err(e).at(Location::Path(format!("/{}", s.path.join("/"))))),
+            _ => {}
         }
+        buf.clear();
+    }
 
-        Ok(st)
+    if statements.is_empty() {
+        statements.push(finish_statement(st)?);
     }
 
-    fn write_to<W: Write>(mut writer: W, st: &Statement) -> Result<(), AdapterError> {
-        let mut wr = Writer::new_with_indent(&mut writer, b' ', 2);
-        wr.write_event(Event::Decl(BytesDecl::new("1.0", Some("UTF-8"), None)))
-            .map_err(map_parse_err)?;
+    Ok(statements)
+}
 
-        // <Document xmlns="...">
-        let mut doc = BytesStart::new("Document");
-        doc.push_attribute(("xmlns", "urn:iso:std:iso:20022:tech:xsd:camt.053.001.02"));
-        wr.write_event(Event::Start(doc)).map_err(map_parse_err)?;
+/// An `Ntry` or `Bal` element being read, which lenient mode can reject as a whole.
+struct Record {
+    start: usize, // byte offset of the start tag
+    depth: usize, // length of the path outside the element
+    path: String,
+    skipping: bool,
+}
 
-        // <BkToCstmrStmt><Stmt>
-        start(&mut wr, "BkToCstmrStmt")?;
-        start(&mut wr, "Stmt")?;
+impl Record {
+    fn new(start: usize, path: &[String], name: &str) -> Self {
+        Record { start, depth: path.len(), path: format!("/{}/{name}", path.join("/")), skipping: false }
+    }
+}
+
+/// Hands the source of a rejected entry or balance, ending at byte `end`, to the quarantine.
+fn quarantine_record(report: &mut ReadReport, rec: Record, input: &[u8], end: usize) {
+    let raw = String::from_utf8_lossy(&input[rec.start..end]);
+    report.quarantine(Some(Location::Path(rec.path)), raw.trim());
+}
 
-        // <Id>...</Id>
-        elem_text(&mut wr, "Id", &st.id)?;
+fn finish_statement(mut st: Statement) -> Result<Statement, AdapterError> {
+    if st.id.is_empty() {
+        st.id = "none".to_string();
+    }
+    normalize_statement(&mut st, false).map_err(map_parse_err)?;
+    Ok(st)
+}
 
-        // <Acct><Id><IBAN>...</IBAN></Id></Acct>
-        start(&mut wr, "Acct")?;
-        start(&mut wr, "Id")?;
-        elem_text(&mut wr, "IBAN", &st.account_id)?;
-        end(&mut wr, "Id")?;
-        end(&mut wr, "Acct")?;
+fn write_statement<W: Write>(wr: &mut Writer<W>, st: &Statement, group_created_at: DateTime) -> QxRes {
+    start(wr, "Stmt")?;
 
-        // Balances
-        if let Some(b) = &st.opening_balance {
-            write_balance(&mut wr, "OPBD", b).map_err(map_parse_err)?
-        }
-        if let Some(b) = &st.closing_balance {
-            write_balance(&mut wr, "CLBD", b).map_err(map_parse_err)?
-        }
+    // <Id>...</Id><ElctrncSeqNb>...</ElctrncSeqNb><LglSeqNb>...</LglSeqNb>
+    elem_text(wr, "Id", &st.id)?;
+    if let Some(n) = st.electronic_sequence {
+        elem_text(wr, "ElctrncSeqNb", &n.to_string())?;
+    }
+    if let Some(n) = st.legal_sequence {
+        elem_text(wr, "LglSeqNb", &n.to_string())?;
+    }
 
-        // Entries
-        for e in &st.entries {
-            write_entry(&mut wr, e).map_err(map_parse_err)?;
-        }
+    // <CreDtTm>...</CreDtTm> is mandatory; fall back to the group header time.
+    elem_text(wr, "CreDtTm", &st.created_at.unwrap_or(group_created_at).to_string())?;
 
-        // </Stmt></BkToCstmrStmt></Document>
-        end(&mut wr, "Stmt")?;
-        end(&mut wr, "BkToCstmrStmt")?;
-        end(&mut wr, "Document")?;
-        Ok(())
+    // <FrToDt><FrDtTm>...</FrDtTm><ToDtTm>...</ToDtTm></FrToDt>
+    if let Some(period) = &st.period {
+        start(wr, "FrToDt")?;
+        elem_text(wr, "FrDtTm", &period.from.to_string())?;
+        elem_text(wr, "ToDtTm", &period.to.to_string())?;
+        end(wr, "FrToDt")?;
     }
+
+    let mut unmapped = UnmappedWriter::new(&st.extensions, STMT_EXT, STMT_CHILDREN);
+    unmapped.before(wr, "Acct")?;
+
+    // <Acct><Id><IBAN>...</IBAN></Id><Ccy/><Ownr/><Svcr/></Acct>
+    start(wr, "Acct")?;
+    write_account_id(wr, &st.account_id)?;
+    if let Some(ccy) = &st.account_currency {
+        elem_text(wr, "Ccy", ccy)?;
+    }
+    if let Some(owner) = &st.account_owner {
+        start(wr, "Ownr")?;
+        elem_text(wr, "Nm", owner)?;
+        end(wr, "Ownr")?;
+    }
+    if let Some(bic) = &st.servicer_bic {
+        start(wr, "Svcr")?;
+        start(wr, "FinInstnId")?;
+        elem_text(wr, "BIC", bic)?;
+        end(wr, "FinInstnId")?;
+        end(wr, "Svcr")?;
+    }
+    end(wr, "Acct")?;
+
+    // Balances
+    unmapped.before(wr, "Bal")?;
+    for b in &st.balances {
+        write_balance(wr, b)?;
+    }
+
+    // Entries
+    unmapped.before(wr, "Ntry")?;
+    for e in &st.entries {
+        write_entry(wr, e)?;
+    }
+
+    unmapped.rest(wr)?;
+    end(wr, "Stmt")
 }
 
-fn write_entry<W: Write>(
-    wr: &mut Writer<W>,
-    e: &Entry,
-) -> std::result::Result<(), quick_xml::Error> {
-    wr.write_event(Event::Start(BytesStart::new("Ntry")))?;
+fn write_entry<W: Write>(wr: &mut Writer<W>, e: &Entry) -> QxRes {
+    start(wr, "Ntry")?;
 
     // <NtryRef>REF...</NtryRef>
-    if let Some(ref r) = e.reference {
-        if !r.is_empty() {
-            wr.write_event(Event::Start(BytesStart::new("NtryRef")))?;
-            wr.write_event(Event::Text(BytesText::new(r)))?;
-            wr.write_event(Event::End(BytesStart::new("NtryRef").to_end()))?;
-        }
+    if let Some(ref r) = e.reference
+        && !r.is_empty()
+    {
+        elem_text(wr, "NtryRef", r)?;
     }
 
     // <Amt Ccy="...">...</Amt>
-    let amt = e.amount.to_string();
-    wr.write_event(Event::Start(
-        BytesStart::new("Amt").with_attributes([("Ccy", e.currency.as_str())]),
-    ))?;
-    wr.write_event(Event::Text(BytesText::new(&amt)))?;
-    wr.write_event(Event::End(BytesStart::new("Amt").to_end()))?;
+    elem_amount(wr, "Amt", &e.amount, &e.currency)?;
 
     // <CdtDbtInd>CRDT|DBIT</CdtDbtInd>
-    let ind = match e.kind {
-        DebitCredit::Credit => "CRDT",
-        DebitCredit::Debit => "DBIT",
-    };
-    wr.write_event(Event::Start(BytesStart::new("CdtDbtInd")))?;
-    wr.write_event(Event::Text(BytesText::new(ind)))?;
-    wr.write_event(Event::End(BytesStart::new("CdtDbtInd").to_end()))?;
+    elem_text(wr, "CdtDbtInd", credit_debit_code(e.kind))?;
 
-    // <ValDt><Dt>YYYY-MM-DD</Dt></ValDt>
-        let vd = e.value_date.clone();
-        wr.write_event(Event::Start(BytesStart::new("ValDt")))?;
-        wr.write_event(Event::Start(BytesStart::new("Dt")))?;
-        wr.write_event(Event::Text(BytesText::new(&vd)))?;
-        wr.write_event(Event::End(BytesStart::new("Dt").to_end()))?;
-wr.write_event(Event::End(BytesStart::new("ValDt").to_end()))?;
+    // <RvslInd>true</RvslInd><Sts>BOOK|PDNG|INFO</Sts>
+    if e.reversal {
+        elem_text(wr, "RvslInd", "true")?;
+    }
+    elem_text(wr, "Sts", entry_status_code(e.status))?;
 
     // <BookgDt><Dt>YYYY-MM-DD</Dt></BookgDt>
-    let bd = e.booking_date.clone();
-    wr.write_event(Event::Start(BytesStart::new("BookgDt")))?;
-    wr.write_event(Event::Start(BytesStart::new("Dt")))?;
-    wr.write_event(Event::Text(BytesText::new(&bd)))?;
-    wr.write_event(Event::End(BytesStart::new("Dt").to_end()))?;
-    wr.write_event(Event::End(BytesStart::new("BookgDt").to_end()))?;
+    start(wr, "BookgDt")?;
+    elem_text(wr, "Dt", &e.booking_date.to_string())?;
+    end(wr, "BookgDt")?;
+
+    // <ValDt><Dt>YYYY-MM-DD</Dt></ValDt>
+    start(wr, "ValDt")?;
+    elem_text(wr, "Dt", &e.value_date.to_string())?;
+    end(wr, "ValDt")?;
+
+    if let Some(r) = &e.servicer_reference {
+        elem_text(wr, "AcctSvcrRef", r)?;
+    }
+
+    let mut unmapped = UnmappedWriter::new(&e.extensions, NTRY_EXT, NTRY_CHILDREN);
+    unmapped.before(wr, "BkTxCd")?;
+    write_bank_tx_code(wr, e)?;
+    unmapped.before(wr, "AmtDtls")?;
+
+    let has_amounts = e.instructed_amount.is_some() || e.transaction_amount.is_some() || e.exchange.is_some();
+    if e.batch.is_some() || !e.transactions.is_empty() {
+        // Batch: entry-level amounts and charges, then <NtryDtls><Btch/><TxDtls/>...</NtryDtls>
+        if has_amounts {
+            write_amount_details(wr, e)?;
+        }
+        write_charges(wr, e)?;
+        unmapped.before(wr, "NtryDtls")?;
+        start(wr, "NtryDtls")?;
+        if let Some(b) = &e.batch {
+            write_batch(wr, b)?;
+        }
+        for tx in &e.transactions {
+            write_tx_details(wr, e.kind, tx, None)?;
+        }
+        end(wr, "NtryDtls")?;
+    } else {
+        // Single payment: <NtryDtls><TxDtls>...</TxDtls></NtryDtls>
+        let tx = TransactionDetails {
+            amount: None,
+            end_to_end_id: e.end_to_end_id.clone(),
+            mandate_id: e.mandate_id.clone(),
+            servicer_reference: None,
+            remittance_info: e.remittance_info.clone(),
+            creditor_reference: e.creditor_reference.clone(),
+            counterparty: e.counterparty.clone(),
+            own_party: e.own_party.clone(),
+            extensions: Extensions::default(),
+        };
+        let has_unmapped = e.extensions.with_prefix(TXDTLS_EXT).next().is_some();
+        unmapped.before(wr, "NtryDtls")?;
+        if tx != TransactionDetails::default() || has_amounts || !e.charges.is_empty() || has_unmapped {
+            start(wr, "NtryDtls")?;
+            write_tx_details(wr, e.kind, &tx, Some(e))?;
+            end(wr, "NtryDtls")?;
+        }
+    }
+
+    // <AddtlNtryInf>...</AddtlNtryInf>; skipped when it only repeats the remittance lines.
+    unmapped.before(wr, "AddtlNtryInf")?;
+    if !e.description.is_empty() && e.remittance_text().as_ref() != Some(&e.description) {
+        elem_text(wr, "AddtlNtryInf", &e.description)?;
+    }
+
+    unmapped.rest(wr)?;
+    end(wr, "Ntry")
+}
+
+/// Writes the mandatory `BkTxCd`: the ISO 20022 domain when known and the
+/// SWIFT code as a proprietary code, so both sides survive a round trip.
+fn write_bank_tx_code<W: Write>(wr: &mut Writer<W>, e: &Entry) -> QxRes {
+    let code = e.transaction_code.clone().unwrap_or_default();
+    start(wr, "BkTxCd")?;
+    if let Some((domain, family, sub_family)) = code.iso(e.kind) {
+        start(wr, "Domn")?;
+        elem_text(wr, "Cd", &domain)?;
+        start(wr, "Fmly")?;
+        elem_text(wr, "Cd", &family)?;
+        elem_text(wr, "SubFmlyCd", &sub_family)?;
+        end(wr, "Fmly")?;
+        end(wr, "Domn")?;
+    }
+    let (proprietary, issuer) = match &code.proprietary {
+        Some(p) => (p.clone(), code.proprietary_issuer.clone()),
+        None => (code.swift_code(), Some(SWIFT_ISSUER.to_string())),
+    };
+    start(wr, "Prtry")?;
+    elem_text(wr, "Cd", &proprietary)?;
+    if let Some(issuer) = issuer {
+        elem_text(wr, "Issr", &issuer)?;
+    }
+    end(wr, "Prtry")?;
+    end(wr, "BkTxCd")
+}
+
+/// Fills a transaction code from a path relative to `BkTxCd`.
+fn read_bank_tx_code(code: &mut TransactionCode, rel: &str, txt: String) {
+    match rel {
+        "Domn/Cd" => code.domain = Some(txt),
+        "Domn/Fmly/Cd" => code.family = Some(txt),
+        "Domn/Fmly/SubFmlyCd" => code.sub_family = Some(txt),
+        "Prtry/Cd" => code.proprietary = Some(txt),
+        "Prtry/Issr" => code.proprietary_issuer = Some(txt),
+        _ => {}
+    }
+}
+
+/// Writes one `TxDtls`; `entry` supplies amount details, charges and kept
+/// elements of a single-payment entry.
+fn write_tx_details<W: Write>(wr: &mut Writer<W>, kind: DebitCredit, tx: &TransactionDetails, entry: Option<&Entry>) -> QxRes {
+    let ext = entry.map_or(&tx.extensions, |e| &e.extensions);
+    let mut unmapped = UnmappedWriter::new(ext, TXDTLS_EXT, TXDTLS_CHILDREN);
+    start(wr, "TxDtls")?;
+    if tx.servicer_reference.is_some() || tx.end_to_end_id.is_some() || tx.mandate_id.is_some() {
+        start(wr, "Refs")?;
+        for (tag, value) in [("AcctSvcrRef", &tx.servicer_reference), ("EndToEndId", &tx.end_to_end_id), ("MndtId", &tx.mandate_id)] {
+            if let Some(v) = value {
+                elem_text(wr, tag, v)?;
+            }
+        }
+        end(wr, "Refs")?;
+    }
+    match entry {
+        Some(e) => {
+            if e.instructed_amount.is_some() || e.transaction_amount.is_some() || e.exchange.is_some() {
+                write_amount_details(wr, e)?;
+            }
+            unmapped.before(wr, "Chrgs")?;
+            write_charges(wr, e)?;
+        }
+        None => {
+            if let Some(a) = &tx.amount {
+                start(wr, "AmtDtls")?;
+                start(wr, "TxAmt")?;
+                elem_amount(wr, "Amt", &a.amount, &a.currency)?;
+                end(wr, "TxAmt")?;
+                end(wr, "AmtDtls")?;
+            }
+            unmapped.before(wr, "Chrgs")?;
+        }
+    }
+    unmapped.before(wr, "RltdPties")?;
+    write_related_parties(wr, kind, tx.counterparty.as_ref(), tx.own_party.as_ref())?;
+    unmapped.before(wr, "RmtInf")?;
+    if !tx.remittance_info.is_empty() || tx.creditor_reference.is_some() {
+        write_remittance(wr, &tx.remittance_info, tx.creditor_reference.as_deref())?;
+    }
+    unmapped.rest(wr)?;
+    end(wr, "TxDtls")
+}
 
-    // <AddtlNtryInf>...</AddtlNtryInf>
-    if !e.description.is_empty() {
-        wr.write_event(Event::Start(BytesStart::new("AddtlNtryInf")))?;
-        wr.write_event(Event::Text(BytesText::new(&e.description)))?;
-        wr.write_event(Event::End(BytesStart::new("AddtlNtryInf").to_end()))?;
+fn write_batch<W: Write>(wr: &mut Writer<W>, b: &Batch) -> QxRes {
+    start(wr, "Btch")?;
+    if let Some(id) = &b.message_id {
+        elem_text(wr, "MsgId", id)?;
+    }
+    if let Some(id) = &b.payment_info_id {
+        elem_text(wr, "PmtInfId", id)?;
+    }
+    if let Some(n) = b.number_of_transactions {
+        elem_text(wr, "NbOfTxs", &n.to_string())?;
     }
+    if let Some(total) = &b.total_amount {
+        elem_amount(wr, "TtlAmt", &total.amount, &total.currency)?;
+    }
+    end(wr, "Btch")
+}
 
-    wr.write_event(Event::End(BytesStart::new("Ntry").to_end()))?;
+fn write_charges<W: Write>(wr: &mut Writer<W>, e: &Entry) -> QxRes {
+    for charge in &e.charges {
+        start(wr, "Chrgs")?;
+        elem_amount(wr, "Amt", &charge.amount.amount, &charge.amount.currency)?;
+        if let Some(code) = &charge.code {
+            start(wr, "Tp")?;
+            elem_text(wr, "Cd", code)?;
+            end(wr, "Tp")?;
+        }
+        end(wr, "Chrgs")?;
+    }
     Ok(())
 }
 
-fn write_balance<W: Write>(
+/// Writes `AmtDtls`; the exchange goes with the instructed amount, or with the
+/// transaction amount (the booked amount if unknown) when there is none.
+fn write_amount_details<W: Write>(wr: &mut Writer<W>, e: &Entry) -> QxRes {
+    start(wr, "AmtDtls")?;
+    if let Some(instructed) = &e.instructed_amount {
+        start(wr, "InstdAmt")?;
+        elem_amount(wr, "Amt", &instructed.amount, &instructed.currency)?;
+        write_exchange(wr, e.exchange.as_ref())?;
+        end(wr, "InstdAmt")?;
+    }
+    let exchange = e.exchange.as_ref().filter(|_| e.instructed_amount.is_none());
+    if e.transaction_amount.is_some() || exchange.is_some() {
+        let booked = CurrencyAmount { amount: e.amount, currency: e.currency.clone() };
+        let tx = e.transaction_amount.as_ref().unwrap_or(&booked);
+        start(wr, "TxAmt")?;
+        elem_amount(wr, "Amt", &tx.amount, &tx.currency)?;
+        write_exchange(wr, exchange)?;
+        end(wr, "TxAmt")?;
+    }
+    end(wr, "AmtDtls")
+}
+
+fn write_exchange<W: Write>(wr: &mut Writer<W>, exchange: Option<&CurrencyExchange>) -> QxRes {
+    let Some(x) = exchange else { return Ok(()) };
+    start(wr, "CcyXchg")?;
+    elem_text(wr, "SrcCcy", &x.source_currency)?;
+    if let Some(ccy) = &x.target_currency {
+        elem_text(wr, "TrgtCcy", ccy)?;
+    }
+    if let Some(ccy) = &x.unit_currency {
+        elem_text(wr, "UnitCcy", ccy)?;
+    }
+    elem_text(wr, "XchgRate", &x.rate.to_string())?;
+    end(wr, "CcyXchg")
+}
+
+/// Writes `RmtInf` with the unstructured lines and the structured creditor reference.
+fn write_remittance<W: Write>(wr: &mut Writer<W>, lines: &[String], creditor_reference: Option<&str>) -> QxRes {
+    start(wr, "RmtInf")?;
+    for line in lines {
+        elem_text(wr, "Ustrd", line)?;
+    }
+    if let Some(r) = creditor_reference {
+        start(wr, "Strd")?;
+        start(wr, "CdtrRefInf")?;
+        if is_creditor_reference(r) {
+            start(wr, "Tp")?;
+            start(wr, "CdOrPrtry")?;
+            elem_text(wr, "Cd", "SCOR")?;
+            end(wr, "CdOrPrtry")?;
+            elem_text(wr, "Issr", "ISO")?;
+            end(wr, "Tp")?;
+        }
+        elem_text(wr, "Ref", r)?;
+        end(wr, "CdtrRefInf")?;
+        end(wr, "Strd")?;
+    }
+    end(wr, "RmtInf")
+}
+
+/// Writes `RltdPties` and `RltdAgts`; the debtor is the counterparty of a credit entry.
+fn write_related_parties<W: Write>(
     wr: &mut Writer<W>,
-    tp: &str,
-    b: &Balance,
-) -> Result<(), quick_xml::Error> {
-    wr.write_event(Event::Start(BytesStart::new("Bal")))?;
-    wr.write_event(Event::Start(BytesStart::new("Tp")))?;
-    wr.write_event(Event::Start(BytesStart::new("CdOrPrtry")))?;
-    wr.write_event(Event::Start(BytesStart::new("Cd")))?;
-    wr.write_event(Event::Text(BytesText::new(tp)))?;
-    wr.write_event(Event::End(BytesStart::new("Cd").to_end()))?;
-    wr.write_event(Event::End(BytesStart::new("CdOrPrtry").to_end()))?;
-    wr.write_event(Event::End(BytesStart::new("Tp").to_end()))?;
-
-    let amt_str = b.amount.to_string();
-    wr.write_event(Event::Start(
-        BytesStart::new("Amt").with_attributes([("Ccy", b.currency.as_str())]),
-    ))?;
-    wr.write_event(Event::Text(BytesText::new(&amt_str)))?;
-    wr.write_event(Event::End(BytesStart::new("Amt").to_end()))?;
-
-    let d = b.date_yyymmdd.clone();
-    wr.write_event(Event::Start(BytesStart::new("Dt")))?;
-    wr.write_event(Event::Start(BytesStart::new("Dt")))?;
-    wr.write_event(Event::Text(BytesText::new(&d)))?;
-    wr.write_event(Event::End(BytesStart::new("Dt").to_end()))?;
-    wr.write_event(Event::End(BytesStart::new("Dt").to_end()))?;
-
-    wr.write_event(Event::End(BytesStart::new("Bal").to_end()))?;
+    kind: DebitCredit,
+    counterparty: Option<&Counterparty>,
+    own_party: Option<&Counterparty>,
+) -> QxRes {
+    let empty = Counterparty::default();
+    let own = own_party.unwrap_or(&empty);
+    let other = counterparty.unwrap_or(&empty);
+    let (debtor, creditor) = match kind {
+        DebitCredit::Credit => (other, own),
+        DebitCredit::Debit => (own, other),
+    };
+
+    let has_party = |p: &Counterparty| p.name.is_some() || p.tax_id.is_some() || p.account.is_some();
+    if has_party(debtor) || has_party(creditor) {
+        start(wr, "RltdPties")?;
+        for (p, party_tag, acct_tag) in [(debtor, "Dbtr", "DbtrAcct"), (creditor, "Cdtr", "CdtrAcct")] {
+            if p.name.is_some() || p.tax_id.is_some() {
+                start(wr, party_tag)?;
+                if let Some(name) = &p.name {
+                    elem_text(wr, "Nm", name)?;
+                }
+                if let Some(tax_id) = &p.tax_id {
+                    start(wr, "Id")?;
+                    start(wr, "OrgId")?;
+                    start(wr, "Othr")?;
+                    elem_text(wr, "Id", tax_id)?;
+                    end(wr, "Othr")?;
+                    end(wr, "OrgId")?;
+                    end(wr, "Id")?;
+                }
+                end(wr, party_tag)?;
+            }
+            if let Some(account) = &p.account {
+                start(wr, acct_tag)?;
+                write_account_id(wr, account)?;
+                end(wr, acct_tag)?;
+            }
+        }
+        end(wr, "RltdPties")?;
+    }
+
+    let has_agent = |p: &Counterparty| p.bank_id.is_some() || p.bank_name.is_some();
+    if has_agent(debtor) || has_agent(creditor) {
+        start(wr, "RltdAgts")?;
+        for (p, tag) in [(debtor, "DbtrAgt"), (creditor, "CdtrAgt")] {
+            if !has_agent(p) {
+                continue;
+            }
+            start(wr, tag)?;
+            start(wr, "FinInstnId")?;
+            if let Some(id) = &p.bank_id {
+                // Russian BIK is a 9-digit clearing code, not a BIC.
+                if id.bytes().all(|b| b.is_ascii_digit()) {
+                    start(wr, "ClrSysMmbId")?;
+                    elem_text(wr, "MmbId", id)?;
+                    end(wr, "ClrSysMmbId")?;
+                } else {
+                    elem_text(wr, "BIC", id)?;
+                }
+            }
+            if let Some(name) = &p.bank_name {
+                elem_text(wr, "Nm", name)?;
+            }
+            end(wr, "FinInstnId")?;
+            end(wr, tag)?;
+        }
+        end(wr, "RltdAgts")?;
+    }
     Ok(())
 }
 
+/// Writes `<Id><IBAN>` for a valid IBAN and `<Id><Othr><Id>` for any other account number.
+fn write_account_id<W: Write>(wr: &mut Writer<W>, account: &str) -> QxRes {
+    start(wr, "Id")?;
+    if is_iban(account) {
+        elem_text(wr, "IBAN", account)?;
+    } else {
+        start(wr, "Othr")?;
+        elem_text(wr, "Id", account)?;
+        end(wr, "Othr")?;
+    }
+    end(wr, "Id")
+}
+
+fn write_balance<W: Write>(wr: &mut Writer<W>, b: &Balance) -> QxRes {
+    start(wr, "Bal")?;
+    start(wr, "Tp")?;
+    start(wr, "CdOrPrtry")?;
+    elem_text(wr, "Cd", balance_code(b.balance_type))?;
+    end(wr, "CdOrPrtry")?;
+    end(wr, "Tp")?;
+
+    elem_amount(wr, "Amt", &b.amount, &b.currency)?;
+    elem_text(wr, "CdtDbtInd", credit_debit_code(b.kind))?;
+
+    start(wr, "Dt")?;
+    elem_text(wr, "Dt", &b.date.to_string())?;
+    end(wr, "Dt")?;
+
+    end(wr, "Bal")
+}
+
+/// ISO 20022 balance type code; both intermediate balances are `ITBD`.
+fn balance_code(balance_type: BalanceType) -> &'static str {
+    match balance_type {
+        BalanceType::OpeningBooked => "OPBD",
+        BalanceType::PreviouslyClosedBooked => "PRCD",
+        BalanceType::IntermediateOpening | BalanceType::IntermediateClosing => "ITBD",
+        BalanceType::ClosingBooked => "CLBD",
+        BalanceType::OpeningAvailable => "OPAV",
+        BalanceType::InterimAvailable => "ITAV",
+        BalanceType::ClosingAvailable => "CLAV",
+        BalanceType::ForwardAvailable => "FWAV",
+    }
+}
+
+/// Maps a balance type code back; the first `ITBD` opens the statement, later ones close it.
+fn balance_type_from_code(code: &str, seen: &[Balance]) -> Option<BalanceType> {
+    Some(match code {
+        "OPBD" => BalanceType::OpeningBooked,
+        "PRCD" => BalanceType::PreviouslyClosedBooked,
+        "ITBD" if seen.iter().any(|b| b.balance_type.is_opening()) => BalanceType::IntermediateClosing,
+        "ITBD" => BalanceType::IntermediateOpening,
+        "CLBD" => BalanceType::ClosingBooked,
+        "OPAV" => BalanceType::OpeningAvailable,
+        "ITAV" => BalanceType::InterimAvailable,
+        "CLAV" => BalanceType::ClosingAvailable,
+        "FWAV" => BalanceType::ForwardAvailable,
+        _ => return None,
+    })
+}
+
+fn credit_debit_code(kind: DebitCredit) -> &'static str {
+    match kind {
+        DebitCredit::Credit => "CRDT",
+        DebitCredit::Debit => "DBIT",
+    }
+}
+
+fn parse_credit_debit(code: &str) -> Result<DebitCredit, AdapterError> {
+    match code {
+        "CRDT" => Ok(DebitCredit::Credit),
+        "DBIT" => Ok(DebitCredit::Debit),
+        _ => Err(map_parse_err("expected CRDT or DBIT")),
+    }
+}
+
+fn entry_status_code(status: EntryStatus) -> &'static str {
+    match status {
+        EntryStatus::Booked => "BOOK",
+        EntryStatus::Pending => "PDNG",
+        EntryStatus::Information => "INFO",
+    }
+}
+
+fn parse_entry_status(code: &str) -> Result<EntryStatus, AdapterError> {
+    match code {
+        "BOOK" => Ok(EntryStatus::Booked),
+        "PDNG" => Ok(EntryStatus::Pending),
+        "INFO" => Ok(EntryStatus::Information),
+        _ => Err(map_parse_err("expected BOOK, PDNG or INFO")),
+    }
+}
+
 /* ====================== Writer helpers ====================== */
 
 type QxRes = Result<(), AdapterError>;
 
 fn start<W: Write>(wr: &mut Writer<W>, name: &str) -> QxRes {
-    wr.write_event(Event::Start(BytesStart::new(name))).map_err(map_parse_err)
+    Ok(wr.write_event(Event::Start(BytesStart::new(name)))?)
 }
 
 fn end<W: Write>(wr: &mut Writer<W>, name: &str) -> QxRes {
-    wr.write_event(Event::End(BytesStart::new(name).to_end())).map_err(map_parse_err)
+    Ok(wr.write_event(Event::End(BytesStart::new(name).to_end()))?)
 }
 
 fn text<W: Write>(wr: &mut Writer<W>, s: &str) -> QxRes {
-    wr.write_event(Event::Text(BytesText::new(s))).map_err(map_parse_err)
+    Ok(wr.write_event(Event::Text(BytesText::new(s)))?)
 }
 
 /// <name>text</name>
@@ -309,23 +895,283 @@ fn elem_text<W: Write>(wr: &mut Writer<W>, name: &str, s: &str) -> QxRes {
     end(wr, name)
 }
 
+/// <name Ccy="...">amount</name>, with the decimals of the currency.
+fn elem_amount<W: Write>(wr: &mut Writer<W>, name: &str, amount: &Money, ccy: &str) -> QxRes {
+    wr.write_event(Event::Start(BytesStart::new(name).with_attributes([("Ccy", ccy)])))?;
+    text(wr, &scale(*amount, ccy).to_string())?;
+    end(wr, name)
+}
+
+
+/// Writes kept unmapped elements of one parent at their schema position.
+struct UnmappedWriter<'a> {
+    items: Vec<(usize, &'a str)>,
+    order: &'static [&'static str],
+    next: usize,
+}
+
+impl<'a> UnmappedWriter<'a> {
+    fn new(ext: &'a Extensions, prefix: &'a str, order: &'static [&'static str]) -> Self {
+        let position = |name: &str| order.iter().position(|o| *o == name).unwrap_or(order.len());
+        let mut items: Vec<_> = ext.with_prefix(prefix).map(|(name, raw)| (position(name), raw)).collect();
+        items.sort_by_key(|(pos, _)| *pos);
+        UnmappedWriter { items, order, next: 0 }
+    }
+
+    /// Writes the kept elements that precede `name` in the schema.
+    fn before<W: Write>(&mut self, wr: &mut Writer<W>, name: &str) -> QxRes {
+        let limit = self.order.iter().position(|o| *o == name).unwrap_or(self.order.len());
+        self.until(wr, limit)
+    }
+
+    /// Writes whatever is left, including elements unknown to the schema list.
+    fn rest<W: Write>(&mut self, wr: &mut Writer<W>) -> QxRes {
+        self.until(wr, usize::MAX)
+    }
+
+    fn until<W: Write>(&mut self, wr: &mut Writer<W>, limit: usize) -> QxRes {
+        while let Some((pos, raw)) = self.items.get(self.next).copied()
+            && pos < limit
+        {
+            wr.write_indent()?;
+            wr.get_mut().write_all(raw.as_bytes())?;
+            self.next += 1;
+        }
+        Ok(())
+    }
+}
 
 /* ====================== Reader helpers ====================== */
+
+/// Returns the part of `path` below the innermost `anchor` element.
+fn tail<'a>(path: &'a str, anchor: &str) -> Option<&'a str> {
+    let needle = format!("/{anchor}/");
+    path.rfind(&needle).map(|i| &path[i + needle.len()..])
+}
+
+/// Extension key prefixes for unmapped children of `Stmt`, `Ntry` and `TxDtls`.
+const STMT_EXT: &str = "camt:Stmt/";
+const NTRY_EXT: &str = "camt:Ntry/";
+const TXDTLS_EXT: &str = "camt:TxDtls/";
+
+/// Children of `Stmt`, `Ntry` and `TxDtls` in schema order; unmapped ones are
+/// written back at their position.
+const STMT_CHILDREN: &[&str] = &[
+    "Id", "StmtPgntn", "ElctrncSeqNb", "LglSeqNb", "CreDtTm", "FrToDt", "CpyDplctInd", "RptgSrc",
+    "Acct", "RltdAcct", "Intrst", "Bal", "TxsSummry", "Ntry", "AddtlStmtInf",
+];
+const NTRY_CHILDREN: &[&str] = &[
+    "NtryRef", "Amt", "CdtDbtInd", "RvslInd", "Sts", "BookgDt", "ValDt", "AcctSvcrRef", "Avlbty",
+    "BkTxCd", "ComssnWvrInd", "AddtlInfInd", "AmtDtls", "Chrgs", "TechInptChanl", "Intrst",
+    "NtryDtls", "AddtlNtryInf",
+];
+const TXDTLS_CHILDREN: &[&str] = &[
+    "Refs", "AmtDtls", "Avlbty", "BkTxCd", "Chrgs", "Intrst", "RltdPties", "RltdAgts", "Purp",
+    "RltdRmtInf", "RmtInf", "RltdDts", "RltdPric", "RltdQties", "FinInstrmId", "Tax", "RtrInf",
+    "CorpActn", "SfkpgAcct", "AddtlTxInf", "SplmtryData",
+];
+
+/// Children the reader maps onto the model.
+const STMT_MAPPED: &[&str] = &["Id", "StmtPgntn", "ElctrncSeqNb", "LglSeqNb", "CreDtTm", "FrToDt", "Acct", "Bal", "Ntry"];
+const NTRY_MAPPED: &[&str] = &[
+    "NtryRef", "Amt", "CdtDbtInd", "RvslInd", "Sts", "BookgDt", "ValDt", "AcctSvcrRef", "BkTxCd",
+    "AmtDtls", "Chrgs", "NtryDtls", "AddtlNtryInf",
+];
+const TXDTLS_MAPPED: &[&str] = &["Refs", "AmtDtls", "Chrgs", "RltdPties", "RltdAgts", "RmtInf"];
+
+/// Extension key for `name` if it is an unmapped child of `Stmt`, `Ntry` or `TxDtls`.
+fn unmapped_key(path: &[String], name: &str, in_entry: bool) -> Option<String> {
+    let (prefix, mapped) = match path.last()?.as_str() {
+        "Stmt" => (STMT_EXT, STMT_MAPPED),
+        "Ntry" if in_entry => (NTRY_EXT, NTRY_MAPPED),
+        "TxDtls" if in_entry => (TXDTLS_EXT, TXDTLS_MAPPED),
+        _ => return None,
+    };
+    (!mapped.contains(&name)).then(|| format!("{prefix}{name}"))
+}
+
+/// Stores a raw unmapped element on the statement, the open entry or its current transaction.
+fn keep_unmapped(st: &mut Statement, entry: Option<&mut Entry>, txs: &mut [TxState], key: String, raw: String) {
+    let target = if key.starts_with(TXDTLS_EXT) {
+        txs.last_mut().map(|tx| &mut tx.details.extensions)
+    } else if key.starts_with(NTRY_EXT) {
+        entry.map(|e| &mut e.extensions)
+    } else {
+        Some(&mut st.extensions)
+    };
+    if let Some(ext) = target {
+        ext.push(key, raw);
+    }
+}
+
+/// An unmapped element being copied verbatim.
+struct Capture {
+    key: String,
+    depth: usize,
+    out: Writer<Vec<u8>>,
+}
+
+/// A `TxDtls` block being read; parties are resolved once the entry direction is known.
+#[derive(Default)]
+struct TxState {
+    details: TransactionDetails,
+    debtor: Counterparty,
+    creditor: Counterparty,
+}
+
+impl TxState {
+    /// The debtor is the counterparty of a credit entry.
+    fn finish(self, kind: DebitCredit) -> TransactionDetails {
+        let (counterparty, own) = match kind {
+            DebitCredit::Credit => (self.debtor, self.creditor),
+            DebitCredit::Debit => (self.creditor, self.debtor),
+        };
+        TransactionDetails {
+            counterparty: Some(counterparty).filter(|p| !p.is_empty()),
+            own_party: Some(own).filter(|p| !p.is_empty()),
+            ..self.details
+        }
+    }
+}
+
+/// Moves the details of the only transaction of an entry onto the entry.
+fn merge_transaction(e: &mut Entry, tx: TransactionDetails) {
+    e.end_to_end_id = tx.end_to_end_id;
+    e.mandate_id = tx.mandate_id;
+    e.servicer_reference = e.servicer_reference.take().or(tx.servicer_reference);
+    e.remittance_info = tx.remittance_info;
+    e.creditor_reference = tx.creditor_reference;
+    e.counterparty = tx.counterparty;
+    e.own_party = tx.own_party;
+    e.transaction_amount = tx.amount;
+    e.extensions.append(tx.extensions);
+}
+
+/// Maps a text node relative to `TxDtls`; amount details other than the
+/// transaction amount, and charges, belong to the entry.
+fn read_tx_details(tx: &mut TxState, e: &mut Entry, rel: &str, txt: String, ccy: &str) -> QxRes {
+    let d = &mut tx.details;
+    match rel {
+        "Refs/EndToEndId" => d.end_to_end_id = Some(txt),
+        "Refs/MndtId" => d.mandate_id = Some(txt),
+        "Refs/AcctSvcrRef" => d.servicer_reference = Some(txt),
+        "AmtDtls/TxAmt/Amt" => {
+            let amount = Money::parse(&txt).map_err(map_parse_err)?;
+            d.amount = Some(CurrencyAmount { amount, currency: ccy.to_string() });
+        }
+        "RmtInf/Ustrd" => d.remittance_info.push(txt),
+        "RmtInf/Strd/CdtrRefInf/Ref" => {
+            if is_creditor_reference(&txt) {
+                validate_creditor_reference(&txt).map_err(map_parse_err)?;
+            }
+            d.creditor_reference = Some(txt);
+        }
+        _ if rel.starts_with("AmtDtls/") || rel.starts_with("Chrgs/") => read_amount_details(e, rel, txt, ccy)?,
+        _ => {
+            if let Some(rel) = rel.strip_prefix("RltdPties/") {
+                read_related_party(&mut tx.debtor, &mut tx.creditor, rel, txt);
+            } else if let Some(rel) = rel.strip_prefix("RltdAgts/") {
+                read_related_agent(&mut tx.debtor, &mut tx.creditor, rel, txt);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Maps a text node relative to `NtryDtls/Btch`.
+fn read_batch(b: &mut Batch, rel: &str, txt: String, ccy: &str) -> QxRes {
+    match rel {
+        "MsgId" => b.message_id = Some(txt),
+        "PmtInfId" => b.payment_info_id = Some(txt),
+        "NbOfTxs" => b.number_of_transactions = Some(txt.parse().map_err(map_parse_err)?),
+        "TtlAmt" => {
+            let amount = Money::parse(&txt).map_err(map_parse_err)?;
+            b.total_amount = Some(CurrencyAmount { amount, currency: ccy.to_string() });
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Maps a text node under `AmtDtls` or `Chrgs` (entry or transaction level) onto the entry.
+fn read_amount_details(e: &mut Entry, rel: &str, txt: String, ccy: &str) -> QxRes {
+    let amount = || -> Result<CurrencyAmount, AdapterError> {
+        Ok(CurrencyAmount { amount: Money::parse(&txt).map_err(map_parse_err)?, currency: ccy.to_string() })
+    };
+    match rel {
+        "AmtDtls/InstdAmt/Amt" => e.instructed_amount = Some(amount()?),
+        "AmtDtls/TxAmt/Amt" => e.transaction_amount = Some(amount()?),
+        "Chrgs/Amt" | "Chrgs/Rcrd/Amt" => e.charges.push(Charge { amount: amount()?, code: None }),
+        "Chrgs/Tp/Cd" | "Chrgs/Rcrd/Tp/Cd" => {
+            if let Some(charge) = e.charges.last_mut() {
+                charge.code = Some(txt);
+            }
+        }
+        _ => {
+            let Some((_, field)) = rel.split_once("/CcyXchg/") else { return Ok(()) };
+            let x = e.exchange.get_or_insert_with(Default::default);
+            match field {
+                "SrcCcy" => x.source_currency = txt,
+                "TrgtCcy" => x.target_currency = Some(txt),
+                "UnitCcy" => x.unit_currency = Some(txt),
+                "XchgRate" => x.rate = Money::parse(&txt).map_err(map_parse_err)?,
+                _ => {}
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Maps a text node under `RltdPties` onto the debtor or creditor side.
+fn read_related_party(debtor: &mut Counterparty, creditor: &mut Counterparty, rel: &str, txt: String) {
+    let Some((head, rest)) = rel.split_once('/') else { return };
+    let (party, is_account) = match head {
+        "Dbtr" => (debtor, false),
+        "DbtrAcct" => (debtor, true),
+        "Cdtr" => (creditor, false),
+        "CdtrAcct" => (creditor, true),
+        _ => return,
+    };
+    // camt.053.001.08+ wraps the party in <Pty>.
+    let rest = rest.strip_prefix("Pty/").unwrap_or(rest);
+    match (is_account, rest) {
+        (true, "Id/IBAN") => party.account = Some(normalize_account_id(&txt)),
+        (true, "Id/Othr/Id") => party.account = Some(txt),
+        (false, "Nm") => party.name = Some(txt),
+        (false, "Id/OrgId/Othr/Id" | "Id/PrvtId/Othr/Id") => party.tax_id = Some(txt),
+        _ => {}
+    }
+}
+
+/// Maps a text node under `RltdAgts` onto the debtor or creditor bank.
+fn read_related_agent(debtor: &mut Counterparty, creditor: &mut Counterparty, rel: &str, txt: String) {
+    let Some((head, rest)) = rel.split_once('/') else { return };
+    let party = match head {
+        "DbtrAgt" => debtor,
+        "CdtrAgt" => creditor,
+        _ => return,
+    };
+    match rest {
+        "FinInstnId/BIC" | "FinInstnId/BICFI" => party.bank_id = Some(txt),
+        "FinInstnId/ClrSysMmbId/MmbId" if party.bank_id.is_none() => party.bank_id = Some(txt),
+        "FinInstnId/Nm" => party.bank_name = Some(txt),
+        _ => {}
+    }
+}
+
 fn read_text(e: BytesText<'_>) -> Result<String, AdapterError> {
     let s = std::str::from_utf8(e.as_ref())
-        .map_err(|err| AdapterError::ParseError(err.to_string()))?;
-    Ok(unescape(s)
-        .map_err(|err| AdapterError::ParseError(err.to_string()))?
-        .into_owned())
+        .map_err(map_parse_err)?;
+    Ok(unescape(s).map_err(map_parse_err)?.into_owned())
 }
 
 fn attr_value(e: &BytesStart<'_>, key: &[u8]) -> Option<String> {
     // безопасно пробуем извлечь значение атрибута
     for a in e.attributes().flatten() {
-        if a.key.as_ref() == key {
-            if let Ok(v) = String::from_utf8(a.value.into_owned()) {
-                return Some(v);
-            }
+        if a.key.as_ref() == key
+            && let Ok(v) = String::from_utf8(a.value.into_owned())
+        {
+            return Some(v);
         }
     }
     None
@@ -334,6 +1180,7 @@ fn attr_value(e: &BytesStart<'_>, key: &[u8]) -> Option<String> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::adapter::adapter::Mode;
 
     #[test]
     fn test_read_text_plain() {
@@ -399,6 +1246,415 @@ mod tests {
         assert_eq!(out, "<Id>123</Id>");
     }
 
+    #[test]
+    fn related_parties_round_trip() {
+        let entry = Entry {
+            booking_date: Date::new(2025, 10, 1).unwrap(),
+            value_date: Date::new(2025, 10, 1).unwrap(),
+            amount: Money::new(10000, 2),
+            currency: "RUB".to_string(),
+            kind: DebitCredit::Debit,
+            counterparty: Some(Counterparty {
+                name: Some("АО РСИЦ".to_string()),
+                account: Some("40702810600014448120".to_string()),
+                tax_id: Some("7733573894".to_string()),
+                kpp: None,
+                bank_id: Some("044525545".to_string()),
+                bank_name: Some("АО ЮниКредит Банк".to_string()),
+            }),
+            own_party: Some(Counterparty {
+                name: Some("ООО РОМАШКА".to_string()),
+                account: Some("40702810440000030888".to_string()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let st = Statement {
+            id: "1".to_string(),
+            account_id: "40702810440000030888".to_string(),
+            entries: vec![entry.clone()],
+            ..Default::default()
+        };
+
+        let mut out = Vec::new();
+        CAMT::write_to(&mut out, &st).unwrap();
+        let xml = String::from_utf8(out).unwrap();
+        assert!(xml.contains("<MmbId>044525545</MmbId>"));
+
+        let back = CAMT::read_from(xml.as_bytes()).unwrap();
+        assert_eq!(back.account_id, st.account_id);
+        assert_eq!(back.entries[0].counterparty, entry.counterparty);
+        assert_eq!(back.entries[0].own_party, entry.own_party);
+    }
+
+    #[test]
+    fn read_all_keeps_statements_apart() {
+        let xml = r#"<Document><BkToCstmrStmt>
+            <Stmt><Id>A</Id><Acct><Id><IBAN>DE01</IBAN></Id></Acct>
+              <Ntry><Amt Ccy="EUR">1.00</Amt><CdtDbtInd>CRDT</CdtDbtInd><BookgDt><Dt>2025-10-01</Dt></BookgDt></Ntry>
+            </Stmt>
+            <Stmt><Id>B</Id><Acct><Id><Othr><Id>ACC-2</Id></Othr></Id></Acct>
+              <Ntry><Amt Ccy="USD">2.00</Amt><CdtDbtInd>DBIT</CdtDbtInd><BookgDt><Dt>2025-10-02</Dt></BookgDt></Ntry>
+              <Ntry><Amt Ccy="USD">3.00</Amt><CdtDbtInd>DBIT</CdtDbtInd><BookgDt><Dt>2025-10-02</Dt></BookgDt></Ntry>
+            </Stmt>
+        </BkToCstmrStmt></Document>"#;
+
+        let all = CAMT::read_all(xml.as_bytes()).unwrap();
+        assert_eq!(all.len(), 2);
+        assert_eq!((all[0].id.as_str(), all[0].account_id.as_str(), all[0].entries.len()), ("A", "DE01", 1));
+        assert_eq!((all[1].id.as_str(), all[1].account_id.as_str(), all[1].entries.len()), ("B", "ACC-2", 2));
+
+        let mut out = Vec::new();
+        CAMT::write_all(&mut out, &all).unwrap();
+        let back = CAMT::read_all(out.as_slice()).unwrap();
+        assert_eq!(back.len(), 2);
+        for (b, a) in back.iter().zip(&all) {
+            assert_eq!((&b.id, &b.account_id), (&a.id, &a.account_id));
+            // BkTxCd is mandatory, so entries without a code come back as NMSC.
+            let strip = |es: &[Entry]| es.iter().map(|e| Entry { transaction_code: None, ..e.clone() }).collect::<Vec<_>>();
+            assert_eq!(strip(&b.entries), strip(&a.entries));
+        }
+    }
+
+    #[test]
+    fn bank_transaction_code_is_read_and_mapped() {
+        let xml = r#"<Document><BkToCstmrStmt><Stmt><Id>A</Id><Acct><Id><IBAN>DE01</IBAN></Id></Acct>
+            <Ntry><Amt Ccy="EUR">1.00</Amt><CdtDbtInd>CRDT</CdtDbtInd><BookgDt><Dt>2025-10-01</Dt></BookgDt>
+              <BkTxCd><Domn><Cd>PMNT</Cd><Fmly><Cd>RCDT</Cd><SubFmlyCd>ESCT</SubFmlyCd></Fmly></Domn>
+                <Prtry><Cd>166</Cd><Issr>DK</Issr></Prtry></BkTxCd>
+            </Ntry>
+        </Stmt></BkToCstmrStmt></Document>"#;
+
+        let st = CAMT::read_from(xml.as_bytes()).unwrap();
+        let code = st.entries[0].transaction_code.clone().unwrap();
+        assert_eq!((code.domain.as_deref(), code.family.as_deref(), code.sub_family.as_deref()), (Some("PMNT"), Some("RCDT"), Some("ESCT")));
+        assert_eq!((code.proprietary.as_deref(), code.proprietary_issuer.as_deref()), (Some("166"), Some("DK")));
+        assert_eq!(code.swift_code(), "NTRF");
+
+        let mut out = Vec::new();
+        CAMT::write_to(&mut out, &st).unwrap();
+        assert_eq!(CAMT::read_from(out.as_slice()).unwrap().entries, st.entries);
+    }
+
+    #[test]
+    fn remittance_and_references_round_trip() {
+        let xml = r#"<Document><BkToCstmrStmt><Stmt><Id>A</Id><Acct><Id><IBAN>DE01</IBAN></Id></Acct>
+            <Ntry><Amt Ccy="EUR">1.00</Amt><CdtDbtInd>DBIT</CdtDbtInd><BookgDt><Dt>2025-10-01</Dt></BookgDt>
+              <AcctSvcrRef>BANK-77</AcctSvcrRef><BkTxCd><Prtry><Cd>NDDT</Cd><Issr>SWIFT</Issr></Prtry></BkTxCd>
+              <NtryDtls><TxDtls>
+                <Refs><EndToEndId>E2E-1</EndToEndId><MndtId>MANDATE-9</MndtId></Refs>
+                <RmtInf><Ustrd>Invoice 42</Ustrd><Ustrd>Customer 7</Ustrd>
+                  <Strd><CdtrRefInf><Tp><CdOrPrtry><Cd>SCOR</Cd></CdOrPrtry><Issr>ISO</Issr></Tp><Ref>RF18539007547034</Ref></CdtrRefInf></Strd>
+                </RmtInf>
+              </TxDtls></NtryDtls>
+            </Ntry>
+        </Stmt></BkToCstmrStmt></Document>"#;
+
+        let st = CAMT::read_from(xml.as_bytes()).unwrap();
+        let e = &st.entries[0];
+        assert_eq!(e.servicer_reference.as_deref(), Some("BANK-77"));
+        assert_eq!(e.end_to_end_id.as_deref(), Some("E2E-1"));
+        assert_eq!(e.mandate_id.as_deref(), Some("MANDATE-9"));
+        assert_eq!(e.remittance_info, ["Invoice 42", "Customer 7"]);
+        assert_eq!(e.creditor_reference.as_deref(), Some("RF18539007547034"));
+        assert_eq!(e.description, "Invoice 42 Customer 7");
+
+        let mut out = Vec::new();
+        CAMT::write_to(&mut out, &st).unwrap();
+        let written = String::from_utf8(out).unwrap();
+        assert!(!written.contains("AddtlNtryInf"));
+        assert_eq!(CAMT::read_from(written.as_bytes()).unwrap().entries, st.entries);
+
+        let bad = xml.replace("RF18", "RF19");
+        assert!(CAMT::read_from(bad.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn status_and_reversal_round_trip() {
+        let xml = r#"<Document><BkToCstmrStmt><Stmt><Id>A</Id><Acct><Id><IBAN>DE01</IBAN></Id></Acct>
+            <Ntry><Amt Ccy="EUR">1.00</Amt><CdtDbtInd>CRDT</CdtDbtInd><RvslInd>true</RvslInd><Sts>BOOK</Sts><BookgDt><Dt>2025-10-01</Dt></BookgDt></Ntry>
+            <Ntry><Amt Ccy="EUR">2.00</Amt><CdtDbtInd>DBIT</CdtDbtInd><Sts><Cd>PDNG</Cd></Sts><BookgDt><Dt>2025-10-01</Dt></BookgDt></Ntry>
+        </Stmt></BkToCstmrStmt></Document>"#;
+
+        let st = CAMT::read_from(xml.as_bytes()).unwrap();
+        assert!(st.entries[0].reversal);
+        assert_eq!(st.entries[0].status, EntryStatus::Booked);
+        assert!(!st.entries[1].reversal);
+        assert_eq!(st.entries[1].status, EntryStatus::Pending);
+        assert_eq!(st.booked_movement().unwrap(), Money::parse("1.00").unwrap());
+
+        let mut out = Vec::new();
+        CAMT::write_to(&mut out, &st).unwrap();
+        let written = String::from_utf8(out).unwrap();
+        assert!(written.contains("<Sts>PDNG</Sts>"));
+        let back = CAMT::read_from(written.as_bytes()).unwrap();
+        let flags = |st: &Statement| st.entries.iter().map(|e| (e.status, e.reversal)).collect::<Vec<_>>();
+        assert_eq!(flags(&back), flags(&st));
+    }
+
+    #[test]
+    fn amount_details_and_charges_round_trip() {
+        let xml = r#"<Document><BkToCstmrStmt><Stmt><Id>A</Id><Acct><Id><IBAN>DE01</IBAN></Id></Acct>
+            <Ntry><Amt Ccy="EUR">98.50</Amt><CdtDbtInd>CRDT</CdtDbtInd><BookgDt><Dt>2025-10-01</Dt></BookgDt>
+              <BkTxCd><Prtry><Cd>NTRF</Cd><Issr>SWIFT</Issr></Prtry></BkTxCd>
+              <NtryDtls><TxDtls>
+                <AmtDtls>
+                  <InstdAmt><Amt Ccy="USD">110.00</Amt>
+                    <CcyXchg><SrcCcy>USD</SrcCcy><TrgtCcy>EUR</TrgtCcy><UnitCcy>USD</UnitCcy><XchgRate>0.9091</XchgRate></CcyXchg>
+                  </InstdAmt>
+                  <TxAmt><Amt Ccy="EUR">100.00</Amt></TxAmt>
+                </AmtDtls>
+                <Chrgs><Amt Ccy="EUR">1.50</Amt><Tp><Cd>COMM</Cd></Tp></Chrgs>
+              </TxDtls></NtryDtls>
+            </Ntry>
+        </Stmt></BkToCstmrStmt></Document>"#;
+
+        let st = CAMT::read_from(xml.as_bytes()).unwrap();
+        let e = &st.entries[0];
+        let ccy = |amount: &str, currency: &str| CurrencyAmount { amount: Money::parse(amount).unwrap(), currency: currency.to_string() };
+        assert_eq!(e.instructed_amount, Some(ccy("110.00", "USD")));
+        assert_eq!(e.transaction_amount, Some(ccy("100.00", "EUR")));
+        let x = e.exchange.as_ref().unwrap();
+        assert_eq!((x.source_currency.as_str(), x.target_currency.as_deref()), ("USD", Some("EUR")));
+        assert_eq!(x.rate, Money::parse("0.9091").unwrap());
+        assert_eq!(e.charges, [Charge { amount: ccy("1.50", "EUR"), code: Some("COMM".to_string()) }]);
+
+        let mut out = Vec::new();
+        CAMT::write_to(&mut out, &st).unwrap();
+        assert_eq!(CAMT::read_from(out.as_slice()).unwrap().entries, st.entries);
+    }
+
+    #[test]
+    fn batch_entry_keeps_transactions_apart() {
+        let xml = r#"<Document><BkToCstmrStmt><Stmt><Id>A</Id><Acct><Id><IBAN>DE01</IBAN></Id></Acct>
+            <Ntry><Amt Ccy="EUR">30.00</Amt><CdtDbtInd>CRDT</CdtDbtInd><BookgDt><Dt>2025-10-01</Dt></BookgDt>
+              <BkTxCd><Prtry><Cd>NTRF</Cd><Issr>SWIFT</Issr></Prtry></BkTxCd>
+              <NtryDtls>
+                <Btch><PmtInfId>PMT-1</PmtInfId><NbOfTxs>2</NbOfTxs><TtlAmt Ccy="EUR">30.00</TtlAmt></Btch>
+                <TxDtls>
+                  <Refs><EndToEndId>E2E-A</EndToEndId></Refs>
+                  <AmtDtls><TxAmt><Amt Ccy="EUR">10.00</Amt></TxAmt></AmtDtls>
+                  <RltdPties><Dbtr><Nm>Alice</Nm></Dbtr></RltdPties>
+                  <RmtInf><Ustrd>Invoice A</Ustrd></RmtInf>
+                </TxDtls>
+                <TxDtls>
+                  <Refs><EndToEndId>E2E-B</EndToEndId></Refs>
+                  <AmtDtls><TxAmt><Amt Ccy="EUR">20.00</Amt></TxAmt></AmtDtls>
+                  <RltdPties><Dbtr><Nm>Bob</Nm></Dbtr></RltdPties>
+                  <RmtInf><Ustrd>Invoice B</Ustrd></RmtInf>
+                </TxDtls>
+              </NtryDtls>
+              <AddtlNtryInf>SEPA batch</AddtlNtryInf>
+            </Ntry>
+        </Stmt></BkToCstmrStmt></Document>"#;
+
+        let st = CAMT::read_from(xml.as_bytes()).unwrap();
+        let e = &st.entries[0];
+        assert_eq!(e.amount, Money::parse("30.00").unwrap());
+        assert_eq!(e.description, "SEPA batch");
+        assert!(e.counterparty.is_none() && e.end_to_end_id.is_none());
+        let batch = e.batch.as_ref().unwrap();
+        assert_eq!((batch.payment_info_id.as_deref(), batch.number_of_transactions), (Some("PMT-1"), Some(2)));
+        assert_eq!(e.transactions.len(), 2);
+        let tx = &e.transactions[1];
+        assert_eq!(tx.amount.as_ref().unwrap().amount, Money::parse("20.00").unwrap());
+        assert_eq!(tx.end_to_end_id.as_deref(), Some("E2E-B"));
+        assert_eq!(tx.counterparty.as_ref().unwrap().name.as_deref(), Some("Bob"));
+        assert_eq!(tx.remittance_info, ["Invoice B"]);
+
+        let mut out = Vec::new();
+        CAMT::write_to(&mut out, &st).unwrap();
+        assert_eq!(CAMT::read_from(out.as_slice()).unwrap().entries, st.entries);
+    }
+
+    #[test]
+    fn swift_code_is_written_as_domain_and_proprietary() {
+        let entry = Entry {
+            kind: DebitCredit::Debit,
+            transaction_code: Some(TransactionCode::from_swift("NCHG", DebitCredit::Debit)),
+            ..Default::default()
+        };
+        let mut writer = Writer::new(Vec::<u8>::new());
+        write_bank_tx_code(&mut writer, &entry).unwrap();
+        let out = String::from_utf8(writer.into_inner()).unwrap();
+        assert_eq!(out, "<BkTxCd><Domn><Cd>ACMT</Cd><Fmly><Cd>MDOP</Cd><SubFmlyCd>CHRG</SubFmlyCd></Fmly></Domn>\
+<Prtry><Cd>NCHG</Cd><Issr>SWIFT</Issr></Prtry></BkTxCd>");
+    }
+
+    #[test]
+    fn statement_metadata_round_trip() {
+        let ts = |s: &str| DateTime::parse_iso(s).unwrap();
+        let st = Statement {
+            id: "STMT-1".to_string(),
+            electronic_sequence: Some(42),
+            legal_sequence: Some(7),
+            created_at: Some(ts("2025-10-02T06:00:00+03:00")),
+            period: Some(Period { from: ts("2025-10-01T00:00:00"), to: ts("2025-10-01T23:59:59") }),
+            account_id: "DE89370400440532013000".to_string(),
+            account_currency: Some("EUR".to_string()),
+            account_owner: Some("ACME GmbH".to_string()),
+            servicer_bic: Some("COBADEFFXXX".to_string()),
+            ..Default::default()
+        };
+
+        let mut out = Vec::new();
+        CAMT::write_to(&mut out, &st).unwrap();
+        let xml = String::from_utf8(out).unwrap();
+        assert!(xml.contains("<GrpHdr>"));
+        assert!(xml.contains("<CreDtTm>2025-10-02T06:00:00+03:00</CreDtTm>"));
+
+        let back = CAMT::read_from(xml.as_bytes()).unwrap();
+        assert_eq!(back, st);
+    }
+
+    #[test]
+    fn group_header_time_applies_to_statements() {
+        let xml = r#"<Document><BkToCstmrStmt>
+            <GrpHdr><MsgId>M1</MsgId><CreDtTm>2025-10-02T06:00:00</CreDtTm></GrpHdr>
+            <Stmt><Id>A</Id><Acct><Id><IBAN>DE01</IBAN></Id></Acct></Stmt>
+        </BkToCstmrStmt></Document>"#;
+        let st = CAMT::read_from(xml.as_bytes()).unwrap();
+        assert_eq!(st.created_at, Some(DateTime::parse_iso("2025-10-02T06:00:00").unwrap()));
+    }
+
+    #[test]
+    fn balances_are_read_and_written() {
+        let xml = r#"<Document><BkToCstmrStmt><Stmt><Id>A</Id><Acct><Id><IBAN>DE01</IBAN></Id></Acct>
+            <Bal><Tp><CdOrPrtry><Cd>ITBD</Cd></CdOrPrtry></Tp><Amt Ccy="EUR">10.00</Amt><CdtDbtInd>DBIT</CdtDbtInd><Dt><Dt>2025-10-01</Dt></Dt></Bal>
+            <Bal><Tp><CdOrPrtry><Cd>ITBD</Cd></CdOrPrtry></Tp><Amt Ccy="EUR">90.00</Amt><CdtDbtInd>CRDT</CdtDbtInd><Dt><Dt>2025-10-01</Dt></Dt></Bal>
+            <Bal><Tp><CdOrPrtry><Cd>CLAV</Cd></CdOrPrtry></Tp><Amt Ccy="EUR">80.00</Amt><CdtDbtInd>CRDT</CdtDbtInd><Dt><Dt>2025-10-01</Dt></Dt></Bal>
+            <Bal><Tp><CdOrPrtry><Cd>FWAV</Cd></CdOrPrtry></Tp><Amt Ccy="EUR">85.00</Amt><CdtDbtInd>CRDT</CdtDbtInd><Dt><Dt>2025-10-02</Dt></Dt></Bal>
+            <Bal><Tp><CdOrPrtry><Cd>FWAV</Cd></CdOrPrtry></Tp><Amt Ccy="EUR">95.00</Amt><CdtDbtInd>CRDT</CdtDbtInd><Dt><Dt>2025-10-03</Dt></Dt></Bal>
+        </Stmt></BkToCstmrStmt></Document>"#;
+
+        let st = CAMT::read_from(xml.as_bytes()).unwrap();
+        let types: Vec<_> = st.balances.iter().map(|b| b.balance_type).collect();
+        assert_eq!(types, [
+            BalanceType::IntermediateOpening,
+            BalanceType::IntermediateClosing,
+            BalanceType::ClosingAvailable,
+            BalanceType::ForwardAvailable,
+            BalanceType::ForwardAvailable,
+        ]);
+        assert_eq!(st.opening_balance().unwrap().signed_amount(), Money::parse("-10").unwrap());
+
+        let mut out = Vec::new();
+        CAMT::write_to(&mut out, &st).unwrap();
+        let back = CAMT::read_from(out.as_slice()).unwrap();
+        assert_eq!(back.balances, st.balances);
+    }
+
+    #[test]
+    fn unmapped_elements_round_trip() {
+        let xml = r#"<Document><BkToCstmrStmt><Stmt><Id>A</Id><CpyDplctInd>CODU</CpyDplctInd><Acct><Id><IBAN>DE01</IBAN></Id></Acct>
+            <TxsSummry><TtlNtries><NbOfNtries>1</NbOfNtries></TtlNtries></TxsSummry>
+            <Ntry><Amt Ccy="EUR">5.00</Amt><CdtDbtInd>CRDT</CdtDbtInd><Sts>BOOK</Sts><BookgDt><Dt>2025-10-01</Dt></BookgDt>
+              <Avlbty><Dt><NbOfDays>1</NbOfDays></Dt><Amt Ccy="EUR">5.00</Amt><CdtDbtInd>CRDT</CdtDbtInd></Avlbty>
+              <BkTxCd><Prtry><Cd>NTRF</Cd><Issr>SWIFT</Issr></Prtry></BkTxCd>
+              <NtryDtls><TxDtls><Refs><EndToEndId>E2E</EndToEndId></Refs><Purp><Cd>SALA</Cd></Purp><AddtlTxInf>a &amp; b</AddtlTxInf></TxDtls></NtryDtls>
+            </Ntry><AddtlStmtInf/></Stmt></BkToCstmrStmt></Document>"#;
+
+        let st = CAMT::read_from(xml.as_bytes()).unwrap();
+        assert_eq!(st.extensions.get("camt:Stmt/CpyDplctInd"), Some("<CpyDplctInd>CODU</CpyDplctInd>"));
+        assert_eq!(st.extensions.get("camt:Stmt/AddtlStmtInf"), Some("<AddtlStmtInf/>"));
+        let e = &st.entries[0];
+        assert_eq!(e.end_to_end_id.as_deref(), Some("E2E"));
+        assert_eq!(e.extensions.get("camt:TxDtls/AddtlTxInf"), Some("<AddtlTxInf>a &amp; b</AddtlTxInf>"));
+
+        let mut out = Vec::new();
+        CAMT::write_to(&mut out, &st).unwrap();
+        let written = String::from_utf8(out).unwrap();
+        let pos = |needle: &str| written.find(needle).unwrap_or_else(|| panic!("{needle} missing"));
+        assert!(pos("<CpyDplctInd>") < pos("<Acct>"));
+        assert!(pos("<TxsSummry>") < pos("<Ntry>"));
+        assert!(pos("<Avlbty>") < pos("<BkTxCd>"));
+        assert!(pos("<Purp>") < pos("<AddtlTxInf>"));
+
+        let back = CAMT::read_from(written.as_bytes()).unwrap();
+        assert_eq!(back.extensions, st.extensions);
+        assert_eq!(back.entries, st.entries);
+    }
+
+    #[test]
+    fn account_without_iban_is_written_as_other_id() {
+        let st = |account: &str| Statement { id: "A".to_string(), account_id: account.to_string(), ..Default::default() };
+
+        let mut out = Vec::new();
+        CAMT::write_to(&mut out, &st("40702810440000030888")).unwrap();
+        let xml = String::from_utf8(out).unwrap();
+        assert!(!xml.contains("<IBAN>"));
+        assert!(xml.contains("<Id>40702810440000030888</Id>"));
+        assert_eq!(CAMT::read_from(xml.as_bytes()).unwrap().account_id, "40702810440000030888");
+
+        let mut out = Vec::new();
+        CAMT::write_to(&mut out, &st("DE89370400440532013000")).unwrap();
+        assert!(String::from_utf8(out).unwrap().contains("<IBAN>DE89370400440532013000</IBAN>"));
+    }
+
+    #[test]
+    fn errors_name_element_and_path() {
+        let doc = |ntry: &str| format!(
+            "<Document><BkToCstmrStmt><Stmt><Id>S1</Id><Ntry>{ntry}</Ntry></Stmt></BkToCstmrStmt></Document>"
+        );
+        let err = CAMT::read_all(doc("<Amt Ccy=\"EUR\">1O.00</Amt>").as_bytes()).unwrap_err();
+        assert!(matches!(&err, AdapterError::InvalidField { field, value, .. } if field == "Amt" && value == "1O.00"), "{err}");
+        assert_eq!(err.location(), Some(&Location::Path("/Document/BkToCstmrStmt/Stmt/Ntry/Amt".into())));
+
+        let err = CAMT::read_all(doc("<CdtDbtInd>X</CdtDbtInd>").as_bytes()).unwrap_err();
+        assert_eq!(err.to_string(), "invalid CdtDbtInd `X`: expected CRDT or DBIT at /Document/BkToCstmrStmt/Stmt/Ntry/CdtDbtInd");
+
+        let err = CAMT::read_all(doc("<Amt Ccy=\"EUR\">1.00</Amt>").as_bytes()).unwrap_err();
+        assert_eq!(err.to_string(), "missing BookgDt at /Document/BkToCstmrStmt/Stmt/Ntry");
+
+        let doc = "<Document><BkToCstmrStmt><Stmt><Id>S1</Id><CreDtTm>2025-10-01T10:15:3é</CreDtTm></Stmt></BkToCstmrStmt></Document>";
+        assert!(CAMT::read_all(doc.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn lenient_mode_skips_entries_and_balances() {
+        let doc = "<Document><BkToCstmrStmt><Stmt><Id>S1</Id>\
+            <Bal><Tp><CdOrPrtry><Cd>OPBD</Cd></CdOrPrtry></Tp><Amt Ccy=\"EUR\">1.00</Amt><CdtDbtInd>CRDT</CdtDbtInd></Bal>\
+            <Ntry><Amt Ccy=\"EUR\">1O.00</Amt><CdtDbtInd>CRDT</CdtDbtInd><BookgDt><Dt>2025-10-01</Dt></BookgDt></Ntry>\
+            <Ntry><Amt Ccy=\"EUR\">2.00</Amt><CdtDbtInd>DBIT</CdtDbtInd><BookgDt><Dt>2025-10-01</Dt></BookgDt></Ntry>\
+            </Stmt></BkToCstmrStmt></Document>";
+        assert!(CAMT::read_all(doc.as_bytes()).is_err());
+
+        let lenient = ReadOptions { mode: Mode::Lenient, ..Default::default() };
+        let report = CAMT::read_with(doc.as_bytes(), &lenient).unwrap();
+        let st = &report.statements[0];
+        assert!(st.balances.is_empty());
+        assert_eq!(st.entries.len(), 1);
+        assert_eq!(st.entries[0].amount, Money::parse("2.00").unwrap());
+
+        let warnings: Vec<_> = report.warnings.iter().map(|w| w.to_string()).collect();
+        assert_eq!(warnings, [
+            "missing Dt at /Document/BkToCstmrStmt/Stmt/Bal",
+            "invalid Amt `1O.00`: invalid amount `1O.00` at /Document/BkToCstmrStmt/Stmt/Ntry/Amt",
+        ]);
+        assert_eq!(report.rejected.len(), 2);
+        assert!(report.rejected[0].raw.starts_with("<Bal>") && report.rejected[0].raw.ends_with("</Bal>"));
+        assert!(report.rejected[1].raw.contains("1O.00") && report.rejected[1].raw.ends_with("</Ntry>"));
+        assert_eq!(report.rejected[1].location, Some(Location::Path("/Document/BkToCstmrStmt/Stmt/Ntry".into())));
+    }
+
+    #[test]
+    fn invalid_identifiers_are_reported() {
+        let doc = "<Document><BkToCstmrStmt><Stmt><Id>S1</Id>\
+            <Acct><Id><IBAN>DE88370400440532013000</IBAN></Id><Svcr><FinInstnId><BICFI>COBAQQFF</BICFI></FinInstnId></Svcr></Acct>\
+            </Stmt></BkToCstmrStmt></Document>";
+        let report = CAMT::read_with(doc.as_bytes(), &ReadOptions::default()).unwrap();
+        assert_eq!(report.statements[0].account_id, "DE88370400440532013000");
+        let warnings: Vec<_> = report.warnings.iter().map(|w| w.to_string()).collect();
+        assert_eq!(warnings, [
+            "invalid IBAN `DE88370400440532013000`: IBAN `DE88370400440532013000` has wrong check digits at /Document/BkToCstmrStmt/Stmt/Acct/Id/IBAN",
+            "invalid BICFI `COBAQQFF`: BIC `COBAQQFF`: unknown country `QQ` at /Document/BkToCstmrStmt/Stmt/Acct/Svcr/FinInstnId/BICFI",
+        ]);
+
+        let strict = ReadOptions { mode: Mode::Strict, ..Default::default() };
+        assert!(CAMT::read_with(doc.as_bytes(), &strict).is_err());
+        assert!(CAMT::read_with(doc.replace("DE88", "DE89").replace("QQ", "DE").as_bytes(), &strict).is_ok());
+    }
+
     #[test]
     fn text_is_escaped() {
         let mut writer = Writer::new(Vec::<u8>::new());
@@ -1,10 +1,12 @@
-use std::io::{Read,Write};
+use std::io::{BufRead, Read, Write};
 use regex::Regex;
 use csv::{StringRecord, WriterBuilder};
-use crate::adapter::errors::AdapterError;
-use crate::adapter::adapter::Adapter;
+use crate::adapter::errors::{map_parse_err, AdapterError, Location};
+use crate::adapter::adapter::{Adapter, ReadOptions, ReadReport};
 use crate::adapter::adapter::Statement;
-use crate::adapter::statement::{DebitCredit, Entry};
+use crate::adapter::money::Money;
+use crate::adapter::currency::{find, scale};
+use crate::adapter::statement::{Counterparty, Date, DebitCredit, Entry, Extensions};
 use serde::{Serialize};
 
 /// CSV adapter implementing the `Adapter` trait.
@@ -40,80 +42,235 @@ pub struct ItemCsv {
     bank_name: String,
 }
 
-impl Adapter for CSV {
-    fn read_from<R: Read>(reader: R) -> Result<Statement,AdapterError>{
+/// Column names of the [`ItemCsv`] layout, in field order.
+const ITEM_COLUMNS: [&str; 13] = [
+    "tx_data", "tx_number", "tx_description",
+    "debit_account_number", "debit_inn", "debit_account_name", "debit_amount",
+    "credit_account_number", "credit_inn", "credit_account_name", "credit_amount",
+    "bank_bik", "bank_name",
+];
+
+/// Columns of the bank export layout mapped by [`parse_row`].
+const BANK_COLUMNS: [usize; 8] = [1, 4, 8, 9, 13, 14, 17, 20];
+
+/// Extension key prefix for columns the model has no field for.
+const EXT_PREFIX: &str = "csv:";
+
+impl ItemCsv {
+    fn fields(&self) -> [&str; 13] {
+        [
+            &self.tx_data, &self.tx_number, &self.tx_description,
+            &self.debit_account_number, &self.debit_inn, &self.debit_account_name, &self.debit_amount,
+            &self.credit_account_number, &self.credit_inn, &self.credit_account_name, &self.credit_amount,
+            &self.bank_bik, &self.bank_name,
+        ]
+    }
+
+    /// Party on the debit side of the row; the bank column describes it when it is the counterparty.
+    fn debit_party(&self, with_bank: bool) -> Counterparty {
+        self.party(&self.debit_account_number, &self.debit_inn, &self.debit_account_name, with_bank)
+    }
+
+    /// Party on the credit side of the row; the bank column describes it when it is the counterparty.
+    fn credit_party(&self, with_bank: bool) -> Counterparty {
+        self.party(&self.credit_account_number, &self.credit_inn, &self.credit_account_name, with_bank)
+    }
+
+    /// The INN cell may carry the KPP as `INN/KPP`.
+    fn party(&self, account: &str, inn: &str, name: &str, with_bank: bool) -> Counterparty {
+        let opt = |s: &str| (!s.trim().is_empty()).then(|| s.trim().to_string());
+        let (inn, kpp) = inn.split_once('/').unwrap_or((inn, ""));
+        Counterparty {
+            name: opt(name),
+            account: opt(account),
+            tax_id: opt(inn),
+            kpp: opt(kpp),
+            bank_id: if with_bank { opt(&self.bank_bik) } else { None },
+            bank_name: if with_bank { opt(&self.bank_name) } else { None },
+        }
+    }
+}
+
+impl CSV {
+    /// Reads the rows into one statement; rows that cannot be read go through [`ReadReport::reject`].
+    fn read_rows<R: Read>(reader: R, report: &mut ReadReport) -> Result<Statement, AdapterError> {
         let mut entries = Vec::new();
 
         let mut csv_reader = csv::ReaderBuilder::new()
             .has_headers(true)   // первая строка — имена полей
+            .flexible(true)
             .from_reader(reader);
+        let headers = csv_reader.headers().map_err(csv_err)?.clone();
+        let item_layout = headers.iter().any(|h| h == ITEM_COLUMNS[0]);
 
         for (i,row) in csv_reader.records().enumerate() {
-            let rec = match row {
-                Ok(r) => r,
-                Err(e) => return Err(AdapterError::ParseError(format!("строка {}: {e}", i + 1))),
-            };
-
-            match parse_row(&rec)? {
-                Some(tx) => entries.push(Entry{
-                    booking_date: tx.tx_data.clone(),
-                    value_date: tx.tx_data.clone(),
-                    amount: if tx.credit_amount.is_empty() {tx.debit_amount} else {tx.credit_amount.clone()},
-                    currency: "RUB".to_string(),
-                    kind: if tx.credit_amount.is_empty() { DebitCredit::Debit } else { DebitCredit::Credit },
-                    description: tx.tx_description,
-                    reference: Some(tx.tx_number),
-                }),
-                None => continue,
+            let rec = row.map_err(csv_err)?;
+            let at = Location::Line { line: rec.position().map_or(i + 2, |p| p.line() as usize), column: 1 };
+            match read_entry(&headers, &rec, item_layout, &at) {
+                Ok(Some(entry)) => entries.push(entry),
+                Ok(None) if is_incomplete_row(&rec, item_layout) => {
+                    report.warn(map_parse_err("row without date or amount skipped").at(at.clone()))?;
+                    if report.is_lenient() {
+                        report.quarantine(Some(at), record_to_line(&rec));
+                    }
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    report.reject(e)?;
+                    report.quarantine(Some(at), record_to_line(&rec));
+                }
             }
-
         }
 
         Ok(Statement{
             id: Self::undefined(),
-            opening_balance: None,
-            closing_balance:None,
             account_id: Self::undefined(),
-            entries
+            entries,
+            ..Default::default()
         })
     }
+}
+
+impl Adapter for CSV {
+    /// Reads a bank export, or the [`ItemCsv`] layout this adapter writes.
+    fn read_from<R: Read>(reader: R) -> Result<Statement,AdapterError>{
+        Self::read_rows(reader, &mut ReadReport::default())
+    }
 
+    fn read_with<R: BufRead>(reader: R, options: &ReadOptions) -> Result<ReadReport, AdapterError> {
+        let mut report = ReadReport::new(options.mode);
+        let mut st = Self::read_rows(reader, &mut report)?;
+        report.normalize(&mut st)?;
+        report.statements.push(st);
+        Ok(report)
+    }
 
-    fn write_to<W: Write>(mut writer: W, st: &Statement) -> Result<(), AdapterError>{
+    fn write_to<W: Write>(writer: W, st: &Statement) -> Result<(), AdapterError>{
+        Self::write_all(writer, std::slice::from_ref(st))
+    }
+
+    /// Writes the entries of all statements as one table with a single header row.
+    fn write_all<W: Write>(mut writer: W, statements: &[Statement]) -> Result<(), AdapterError>{
         let mut builder = WriterBuilder::new().from_writer(&mut writer);
-        for entry in &st.entries {
-            let mut debit_amount = "";
-            let mut credit_amount ="";
+        let entries = || statements.iter().flat_map(|st| &st.entries);
+
+        // Kept columns follow the fixed ones, in order of first appearance.
+        let mut extra: Vec<&str> = Vec::new();
+        for (name, _) in entries().flat_map(|e| e.extensions.with_prefix(EXT_PREFIX)) {
+            if !extra.contains(&name) {
+                extra.push(name);
+            }
+        }
+        if entries().next().is_some() {
+            builder.write_record(ITEM_COLUMNS.iter().chain(&extra)).map_err(csv_err)?;
+        }
 
+        for entry in entries() {
+            let mut debit_amount = String::new();
+            let mut credit_amount = String::new();
+
+            let amount = scale(entry.amount, &entry.currency).to_string();
             if entry.kind == DebitCredit::Credit {
-                credit_amount = &entry.amount
+                credit_amount = amount
             }else{
-                debit_amount = &entry.amount
+                debit_amount = amount
             }
 
+            let empty = Counterparty::default();
+            let own = entry.own_party.as_ref().unwrap_or(&empty);
+            let other = entry.counterparty.as_ref().unwrap_or(&empty);
+            let (debit, credit) = match entry.kind {
+                DebitCredit::Debit => (own, other),
+                DebitCredit::Credit => (other, own),
+            };
+            let field = |v: &Option<String>| v.clone().unwrap_or_else(Self::undefined);
+            let inn = |p: &Counterparty| match (&p.tax_id, &p.kpp) {
+                (inn, Some(kpp)) => format!("{}/{kpp}", inn.as_deref().unwrap_or_default()),
+                (inn, None) => field(inn),
+            };
+
             let raw = ItemCsv{
-                tx_data: entry.booking_date.clone(),
+                tx_data: entry.booking_date.format_dmy(),
                 tx_number: entry.reference.clone().unwrap_or_else(|| "none".to_string()),
                 tx_description: entry.description.clone(),
-                debit_account_number: entry.reference.clone().unwrap_or_else(|| "none".to_string()),
-                debit_inn: Self::undefined(),
-                debit_account_name: Self::undefined(),
-                debit_amount: debit_amount.to_string(),
-                credit_account_number: Self::undefined(),
-                credit_inn: Self::undefined(),
-                credit_account_name: "".to_string(),
-                credit_amount: credit_amount.to_string(),
-                bank_bik: Self::undefined(),
-                bank_name: Self::undefined(),
+                debit_account_number: field(&debit.account),
+                debit_inn: inn(debit),
+                debit_account_name: field(&debit.name),
+                debit_amount,
+                credit_account_number: field(&credit.account),
+                credit_inn: inn(credit),
+                credit_account_name: field(&credit.name),
+                credit_amount,
+                bank_bik: field(&other.bank_id),
+                bank_name: field(&other.bank_name),
             };
-            builder.serialize(raw).map_err(|e| AdapterError::ParseError(format!("{e}")))?;
+            let kept = extra.iter().map(|name| {
+                entry.extensions.with_prefix(EXT_PREFIX).find(|(k, _)| k == name).map_or("", |(_, v)| v)
+            });
+            builder.write_record(raw.fields().into_iter().chain(kept)).map_err(csv_err)?;
         }
 
-        builder.flush().map_err(|e| AdapterError::ParseError(format!("{e}")))?;
+        builder.flush()?;
         Ok(())
     }
 }
 
+/// Reads one row into an entry; `None` for rows without a date or an amount.
+fn read_entry(headers: &StringRecord, rec: &StringRecord, item_layout: bool, at: &Location) -> Result<Option<Entry>, AdapterError> {
+    if rec.len() != headers.len() {
+        let reason = format!("expected {} fields, found {}", headers.len(), rec.len());
+        return Err(map_parse_err(reason).at(at.clone()));
+    }
+    let parsed = if item_layout { parse_item_row(headers, rec) } else { parse_row(rec)? };
+    let Some(tx) = parsed else { return Ok(None) };
+
+    let date = Date::parse_dmy(&tx.tx_data)
+        .map_err(|e| AdapterError::invalid("date", &tx.tx_data, e).at(at.clone()))?;
+    // Credit rows carry our account on the credit side, debit rows on the debit side.
+    let (own_party, counterparty) = if tx.credit_amount.is_empty() {
+        (tx.debit_party(false), tx.credit_party(true))
+    } else {
+        (tx.credit_party(false), tx.debit_party(true))
+    };
+    let reference = if item_layout {
+        Some(tx.tx_number.clone()).filter(|n| !n.is_empty() && n != "none")
+    } else {
+        Some(tx.tx_number.clone())
+    };
+    let currency = own_party.account.as_deref().and_then(account_currency).unwrap_or("RUB");
+    let amount = if tx.credit_amount.is_empty() {&tx.debit_amount} else {&tx.credit_amount};
+    Ok(Some(Entry{
+        booking_date: date,
+        value_date: date,
+        amount: Money::parse(amount).map_err(|e| AdapterError::invalid("amount", amount, e).at(at.clone()))?,
+        currency: currency.to_string(),
+        kind: if tx.credit_amount.is_empty() { DebitCredit::Debit } else { DebitCredit::Credit },
+        description: tx.tx_description,
+        reference,
+        counterparty: Some(counterparty).filter(|p| !p.is_empty()),
+        own_party: Some(own_party).filter(|p| !p.is_empty()),
+        extensions: unused_columns(headers, rec, item_layout),
+        ..Default::default()
+    }))
+}
+
+/// Whether a row without date or amount looks like a transaction: any non-empty row of
+/// the [`ItemCsv`] layout, or a dated row of a bank export (which has title and total rows).
+fn is_incomplete_row(rec: &StringRecord, item_layout: bool) -> bool {
+    if item_layout {
+        rec.iter().any(|v| !v.trim().is_empty())
+    } else {
+        get(rec, 1).is_some_and(|d| Date::parse_dmy(d).is_ok())
+    }
+}
+
+/// A row written back as CSV, for the quarantine.
+fn record_to_line(rec: &StringRecord) -> String {
+    let mut out = WriterBuilder::new().flexible(true).from_writer(Vec::new());
+    let _ = out.write_record(rec);
+    out.into_inner().map(|b| String::from_utf8_lossy(&b).into_owned()).unwrap_or_default()
+}
+
 fn parse_row(row: &StringRecord) -> Result<Option<ItemCsv>, AdapterError>{
     let tx_data    = get(row, 1).unwrap_or("").to_string();
     let tx_number    = get(row, 14).unwrap_or("").to_string();
@@ -166,7 +323,71 @@ fn parse_row(row: &StringRecord) -> Result<Option<ItemCsv>, AdapterError>{
         bank_name,
     }))
 }
-fn get<'a>(rec: &'a StringRecord, idx: usize) -> Option<&'a str> {
+
+/// Reads a row of the [`ItemCsv`] layout; `undefined` cells are empty.
+fn parse_item_row(headers: &StringRecord, row: &StringRecord) -> Option<ItemCsv> {
+    let col = |name: &str| {
+        let value = headers.iter().position(|h| h == name).and_then(|i| get(row, i)).unwrap_or("");
+        if value == CSV::undefined() { String::new() } else { value.to_string() }
+    };
+    let tx = ItemCsv {
+        tx_data: col("tx_data"),
+        tx_number: col("tx_number"),
+        tx_description: col("tx_description"),
+        debit_account_number: col("debit_account_number"),
+        debit_inn: col("debit_inn"),
+        debit_account_name: col("debit_account_name"),
+        debit_amount: col("debit_amount"),
+        credit_account_number: col("credit_account_number"),
+        credit_inn: col("credit_inn"),
+        credit_account_name: col("credit_account_name"),
+        credit_amount: col("credit_amount"),
+        bank_bik: col("bank_bik"),
+        bank_name: col("bank_name"),
+    };
+    if tx.tx_data.is_empty() || (tx.debit_amount.is_empty() && tx.credit_amount.is_empty()) {
+        return None;
+    }
+    Some(tx)
+}
+
+/// Non-empty cells of columns the layout does not map, keyed by header
+/// (or `#<index>` for an unnamed column).
+fn unused_columns(headers: &StringRecord, row: &StringRecord, item_layout: bool) -> Extensions {
+    let mut ext = Extensions::default();
+    for (i, value) in row.iter().enumerate() {
+        let header = headers.get(i).unwrap_or("").trim_start_matches('\u{feff}');
+        let mapped = if item_layout { ITEM_COLUMNS.contains(&header) } else { BANK_COLUMNS.contains(&i) };
+        if mapped || value.trim().is_empty() {
+            continue;
+        }
+        let name = if header.is_empty() { format!("#{i}") } else { header.to_string() };
+        ext.push(format!("{EXT_PREFIX}{name}"), value);
+    }
+    ext
+}
+
+/// Currency of a Russian 20-digit account number, from its digits 6–8 (`810` for roubles).
+fn account_currency(account: &str) -> Option<&'static str> {
+    if account.len() != 20 || !account.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    find(&account[5..8]).map(|c| c.code)
+}
+
+/// I/O failures keep their source; malformed CSV is reported at its line.
+fn csv_err(e: csv::Error) -> AdapterError {
+    if e.is_io_error() {
+        return AdapterError::Io(e.into());
+    }
+    let err = map_parse_err(&e);
+    match e.position() {
+        Some(p) => err.at(Location::Line { line: p.line() as usize, column: 1 }),
+        None => err,
+    }
+}
+
+fn get(rec: &StringRecord, idx: usize) -> Option<&str> {
     rec.get(idx).map(|s| s.trim()).filter(|s| !s.is_empty())
 }
 
@@ -177,7 +398,7 @@ fn parse_counterparty(block: &str) -> (Option<String>, Option<String>, Option<St
         .filter(|s| !s.is_empty())
         .collect();
 
-    let account = lines.get(0).map(|s| s.to_string());
+    let account = lines.first().map(|s| s.to_string());
     let inn     = lines.get(1).map(|s| s.to_string());
     let name    = lines.get(2).map(|s| s.to_string());
 
@@ -199,6 +420,49 @@ fn parse_bik_and_bank(line: &str) -> Option<(String, String)> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_account_currency() {
+        assert_eq!(account_currency("40702810600014448120"), Some("RUB"));
+        assert_eq!(account_currency("40702840600014448120"), Some("USD"));
+        assert_eq!(account_currency("40702999600014448120"), Some("XXX"));
+        assert_eq!(account_currency("ACC1"), None);
+    }
+
+    #[test]
+    fn test_invalid_cell_reports_its_line() {
+        let input = format!("{}\n01.10.2025,1,a,,,,5.00,,,,,,\n32.10.2025,2,b,,,,5.00,,,,,,\n", ITEM_COLUMNS.join(","));
+        let err = CSV::read_from(input.as_bytes()).unwrap_err();
+        assert!(matches!(&err, AdapterError::InvalidField { field, value, .. } if field == "date" && value == "32.10.2025"), "{err}");
+        assert_eq!(err.location(), Some(&Location::Line { line: 3, column: 1 }));
+    }
+
+    #[test]
+    fn test_lenient_mode_quarantines_rows() {
+        use crate::adapter::adapter::Mode;
+        let input = format!(
+            "{}\n01.10.2025,1,a,,,,5.00,,,,,,\n32.10.2025,2,b,,,,5.00,,,,,,\n,3,c,,,,,,,,,,\n01.10.2025,4\n",
+            ITEM_COLUMNS.join(",")
+        );
+        let lenient = ReadOptions { mode: Mode::Lenient, ..Default::default() };
+        let report = CSV::read_with(input.as_bytes(), &lenient).unwrap();
+        assert_eq!(report.statements[0].entries.len(), 1);
+        let warnings: Vec<_> = report.warnings.iter().map(|w| w.to_string()).collect();
+        assert_eq!(warnings, [
+            "invalid date `32.10.2025`: invalid date 2025-10-32 at line 3, column 1",
+            "row without date or amount skipped at line 4, column 1",
+            "expected 13 fields, found 2 at line 5, column 1",
+        ]);
+        let raw: Vec<_> = report.rejected.iter().map(|r| r.raw.as_str()).collect();
+        assert_eq!(raw, ["32.10.2025,2,b,,,,5.00,,,,,,\n", ",3,c,,,,,,,,,,\n", "01.10.2025,4\n"]);
+
+        // Outside lenient mode a row without amount is only a warning.
+        let input = format!("{}\n01.10.2025,1,a,,,,5.00,,,,,,\n,3,c,,,,,,,,,,\n", ITEM_COLUMNS.join(","));
+        let report = CSV::read_with(input.as_bytes(), &ReadOptions::default()).unwrap();
+        assert_eq!((report.warnings.len(), report.rejected.len()), (1, 0));
+        let strict = ReadOptions { mode: Mode::Strict, ..Default::default() };
+        assert!(CSV::read_with(input.as_bytes(), &strict).is_err());
+    }
+
     #[test]
     fn test_parse_bik_and_bank_basic() {
         let input = "БИК 042202603 ВОЛГО-ВЯТСКИЙ БАНК ПАО СБЕРБАНК, г.Нижний Новгород";
@@ -210,6 +474,94 @@ mod tests {
         assert_eq!(bank, "ВОЛГО-ВЯТСКИЙ БАНК ПАО СБЕРБАНК, г.Нижний Новгород");
     }
 
+    #[test]
+    fn read_assigns_parties_by_direction() {
+        let data = "\u{feff},Дата проводки,,,Счет,,,,,Сумма по дебету,,,,Сумма по кредиту,№ документа,,ВО,Банк (БИК и наименование),,,Назначение платежа,,
+,20.02.2024,,,\"40702810440000030888
+7735602068
+ООО РОМАШКА\",,,,\"40702810600014448120
+7733573894
+АО РСИЦ\",1540.00,,,,,1,,01,\"БИК 044525545 АО ЮниКредит Банк, г.Москва\",,,Оплата,,
+,20.02.2024,,,\"30233810642000600001
+7707083893
+ПАО СБЕРБАНК\",,,,\"40702810440000030888
+7735602068
+ООО РОМАШКА\",,,,,10000.00,616174,,01,\"БИК 042202603 ВОЛГО-ВЯТСКИЙ БАНК ПАО СБЕРБАНК\",,,Займ,,
+";
+        let st = CSV::read_from(data.as_bytes()).unwrap();
+        assert_eq!(st.entries.len(), 2);
+
+        let debit = &st.entries[0];
+        assert_eq!(debit.kind, DebitCredit::Debit);
+        let cp = debit.counterparty.as_ref().unwrap();
+        assert_eq!(cp.name.as_deref(), Some("АО РСИЦ"));
+        assert_eq!(cp.account.as_deref(), Some("40702810600014448120"));
+        assert_eq!(cp.tax_id.as_deref(), Some("7733573894"));
+        assert_eq!(cp.bank_id.as_deref(), Some("044525545"));
+        assert_eq!(debit.own_party.as_ref().unwrap().name.as_deref(), Some("ООО РОМАШКА"));
+
+        let credit = &st.entries[1];
+        assert_eq!(credit.kind, DebitCredit::Credit);
+        assert_eq!(credit.counterparty.as_ref().unwrap().tax_id.as_deref(), Some("7707083893"));
+        assert_eq!(credit.own_party.as_ref().unwrap().account.as_deref(), Some("40702810440000030888"));
+    }
+
+    #[test]
+    fn unused_columns_round_trip() {
+        let data = "\u{feff},Дата проводки,,,Счет,,,,,Сумма по дебету,,,,Сумма по кредиту,№ документа,,ВО,Банк (БИК и наименование),,,Назначение платежа,,
+,20.02.2024,,,\"40702810440000030888
+7735602068
+ООО РОМАШКА\",,,,\"40702810600014448120
+7733573894
+АО РСИЦ\",1540.00,,,,,1,,01,\"БИК 044525545 АО ЮниКредит Банк, г.Москва\",,,Оплата,x,
+";
+        let st = CSV::read_from(data.as_bytes()).unwrap();
+        let e = &st.entries[0];
+        assert_eq!(e.extensions.get("csv:ВО"), Some("01"));
+        assert_eq!(e.extensions.get("csv:#21"), Some("x"));
+
+        let mut out = Vec::new();
+        CSV::write_to(&mut out, &st).unwrap();
+        let written = String::from_utf8(out.clone()).unwrap();
+        assert!(written.starts_with("tx_data,tx_number,"));
+        assert!(written.lines().next().unwrap().ends_with(",bank_name,ВО,#21"));
+
+        let back = CSV::read_from(out.as_slice()).unwrap();
+        assert_eq!(back.entries, st.entries);
+
+        let mut again = Vec::new();
+        CSV::write_to(&mut again, &back).unwrap();
+        assert_eq!(again, out);
+    }
+
+    #[test]
+    fn inn_cell_carries_kpp() {
+        let row = ItemCsv {
+            tx_data: "20.02.2024".to_string(),
+            tx_number: "1".to_string(),
+            tx_description: String::new(),
+            debit_account_number: "40702810440000030888".to_string(),
+            debit_inn: "7735602068/773501001".to_string(),
+            debit_account_name: String::new(),
+            debit_amount: "10.00".to_string(),
+            credit_account_number: String::new(),
+            credit_inn: "7707083893".to_string(),
+            credit_account_name: String::new(),
+            credit_amount: String::new(),
+            bank_bik: String::new(),
+            bank_name: String::new(),
+        };
+        let own = row.debit_party(false);
+        assert_eq!(own.tax_id.as_deref(), Some("7735602068"));
+        assert_eq!(own.kpp.as_deref(), Some("773501001"));
+        assert_eq!(row.credit_party(true).kpp, None);
+
+        let st = Statement { entries: vec![Entry { own_party: Some(own), ..Default::default() }], ..Default::default() };
+        let mut out = Vec::new();
+        CSV::write_to(&mut out, &st).unwrap();
+        assert!(String::from_utf8(out).unwrap().contains(",7735602068/773501001,"));
+    }
+
     #[test]
     fn test_parse_counterparty() {
         let input = "40702810440000030888
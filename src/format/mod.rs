@@ -5,4 +5,6 @@ pub mod mt940;
 /// BANK-specific adapters for xml-formated files.
 pub mod xml;
 /// BANK-specific adapters for camt-formated files.
-pub mod camt;
\ No newline at end of file
+pub mod camt;
+/// SWIFT X character set and Cyrillic transliteration for MT messages.
+pub mod swift;
\ No newline at end of file
@@ -1,76 +1,424 @@
-use std::io::{BufRead,BufReader, Read, Write};
+use std::borrow::Cow;
+use std::io::{BufRead, Write};
 use std::string::ToString;
-use crate::adapter::adapter::{Adapter, Statement};
+use crate::adapter::adapter::{Adapter, ReadOptions, ReadReport, Statement};
 use crate::adapter::statement::Balance as StBalance;
-use crate::adapter::statement::{DebitCredit, Entry};
-use crate::adapter::errors::{map_parse_err, AdapterError};
+use crate::adapter::statement::{BalanceType, Counterparty, Date, DateTime, DebitCredit, Entry, EntryStatus};
+use crate::adapter::statement::{Charge, CurrencyAmount, Extensions, FinEnvelope};
+use crate::adapter::errors::{map_parse_err, map_write_err, AdapterError, Location};
+use crate::adapter::money::Money;
+use crate::adapter::transaction_code::TransactionCode;
+use crate::adapter::reference::{is_creditor_reference, validate_creditor_reference};
+use crate::adapter::identifier::{check_account_id, is_bic, looks_like_bic, normalize_account_id, validate_bic};
+use crate::adapter::currency::scale;
+use crate::format::swift::{detransliterate, fin_header, parse_fin_header, parse_fin_trailer, to_charset_x, transliterate};
 
 #[derive(Debug)]
 struct MT940Statement {
     reference: String,        // :20:
+    related_reference: Option<String>, // :21:
     account_id: String,       // :25:
-    opening_balance: Balance, // :60F:
+    servicer_bic: Option<String>, // :25: BIC/account
+    statement_number: Option<u64>, // :28C: statement number
+    sequence_number: Option<u32>,  // :28C: sequence number
+    created_at: Option<DateTime>,  // :13D:
+    opening_balance: (BalanceType, Balance), // :60F: / :60M:
     transactions: Vec<Transaction>, // :61: + :86:
-    closing_balance: Balance, // :62F:
+    closing_balance: (BalanceType, Balance), // :62F: / :62M:
+    closing_available: Option<Balance>, // :64:
+    forward_available: Vec<Balance>, // :65:
+    extensions: Extensions, // unknown tags
+    envelope: Option<FinEnvelope>, // {1:}{2:}{3:} ... {5:}
 }
 
 #[derive(Debug)]
 struct Balance {
     credit: bool,
-    date_yyymmdd: String,
+    date: Date,
     currency: String,
-    amount: String,
+    amount: Money,
+}
+
+impl Balance {
+    /// The amount, negative for a debit balance.
+    fn signed(&self) -> Money {
+        if self.credit { self.amount } else { -self.amount }
+    }
+}
+
+impl MT940Statement {
+    /// Whether `next` is the page following this one.
+    fn continues_with(&self, next: &MT940Statement) -> bool {
+        self.closing_balance.0 == BalanceType::IntermediateClosing
+            && next.opening_balance.0 == BalanceType::IntermediateOpening
+            && self.reference == next.reference
+            && self.account_id == next.account_id
+            && self.statement_number == next.statement_number
+    }
+
+    /// Adds the transactions and closing balances of the next page; the joined
+    /// statement has no page number.
+    fn append_page(&mut self, next: MT940Statement) {
+        self.transactions.extend(next.transactions);
+        self.closing_balance = next.closing_balance;
+        self.closing_available = next.closing_available;
+        self.forward_available = next.forward_available;
+        self.extensions.append(next.extensions);
+        self.sequence_number = None;
+    }
 }
 
 #[derive(Debug)]
 struct Transaction {
-    date_yyymmdd: String,
-    entry_mmdd: String,
+    value_date: Date,
+    booking_date: Date,
     is_credit: bool,
-    amount: String,
+    reversal: bool,
+    funds_code: Option<char>,
+    amount: Money,
     type_code: String,
     reference: String,
-    description: String,
+    bank_reference: Option<String>,
+    original_amount: Option<CurrencyAmount>, // /OCMT/
+    charges: Option<CurrencyAmount>,         // /CHGS/
+    supplementary: Option<String>,           // rest of the supplementary details
+    info: Info86,
+    extensions: Extensions,                  // unknown tags up to the next :61:
+}
+
+/// Contents of a `:86:` field.
+#[derive(Debug, Default, PartialEq)]
+struct Info86 {
+    text: String,
+    counterparty: Option<Counterparty>,
+    remittance: Option<String>,
+    creditor_reference: Option<String>,
+    end_to_end_id: Option<String>,
+    mandate_id: Option<String>,
+    gvc: Option<String>,                // German business transaction code
+    customer_reference: Option<String>, // German `KREF+`
+    creditor_id: Option<String>,        // German `CRED+`
 }
 
 const PREFIX_TX: &str = ":61:";
 const PREFIX_TX_ID: &str = ":20:";
+const PREFIX_RELATED_REF: &str = ":21:";
 const PREFIX_ACCOUNT_ID: &str = ":25:";
+const PREFIX_STMT_NO: &str = ":28C:";
+const PREFIX_STMT_NO_OLD: &str = ":28:";
+const PREFIX_CREATED_AT: &str = ":13D:";
 const PREFIX_OPN_BALANCE: &str = ":60F:";
+const PREFIX_OPN_BALANCE_INTERIM: &str = ":60M:";
 const PREFIX_CLS_BALANCE: &str = ":62F:";
+const PREFIX_CLS_BALANCE_INTERIM: &str = ":62M:";
+const PREFIX_CLS_AVAILABLE: &str = ":64:";
+const PREFIX_FWD_AVAILABLE: &str = ":65:";
 const PREFIX_TX_DESC: &str = ":86:";
 
+/// Key prefix of unknown tags kept in [`Extensions`].
+const EXT_PREFIX: &str = "mt940:";
+
 /// Mt940 adapter implementing the `Adapter` trait.
 ///
 /// Converts between MT940 and internal `Statement` representation.
 pub struct Mt940;
 
+/// Options for [`Mt940::write_with`].
+#[derive(Debug, Clone, Default)]
+pub struct WriteOptions {
+    /// Write Cyrillic text with the SWIFT transliteration and keep the whole
+    /// message within the SWIFT X character set.
+    pub transliterate: bool,
+    /// Wrap each statement in a SWIFT FIN envelope. Fields missing from
+    /// [`Statement::envelope`] are taken from here; the sender defaults to the
+    /// servicer BIC.
+    pub envelope: Option<FinEnvelope>,
+    /// End lines with `\r\n`, as SWIFT and most bank validators expect.
+    pub crlf: bool,
+    /// Write `:86:` in the German structured form: business transaction code
+    /// (GVC), booking text and `?xx` subfields with SEPA purpose keywords.
+    pub german: bool,
+    /// Derive the opening and closing booked balances MT940 requires when the
+    /// statement lacks them, instead of failing: a missing one from the other
+    /// and the booked entries, both from a zero opening balance when neither is known.
+    pub derive_balances: bool,
+}
+
+impl Mt940 {
+    /// Writes statements like [`Adapter::write_all`], applying `options`.
+    ///
+    /// With [`WriteOptions::transliterate`] counterparty names and descriptions
+    /// are transliterated (and read back with [`ReadOptions::transliterated`]);
+    /// any other character outside the X character set is replaced.
+    pub fn write_with<W: Write>(mut writer: W, statements: &[Statement], options: &WriteOptions) -> Result<(), AdapterError> {
+        for st in statements {
+            let mut st = Cow::Borrowed(st);
+            if options.transliterate {
+                map_text(st.to_mut(), transliterate);
+            }
+            for mut text in messages(&st, MESSAGE_SIZE, options)? {
+                if options.transliterate {
+                    text = to_charset_x(&text);
+                }
+                if let Some(defaults) = &options.envelope {
+                    let envelope = envelope_for(&st, defaults);
+                    // The envelope's `-}` takes the place of the message terminator.
+                    let body = text.strip_suffix("-\n").unwrap_or(&text);
+                    text = format!("{}\n{body}-}}\n", fin_header(&envelope).map_err(map_write_err)?);
+                }
+                if options.crlf {
+                    text = text.replace('\n', "\r\n");
+                }
+                writer.write_all(text.as_bytes())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The envelope of `st`, completed from `defaults` and the servicer BIC. The
+/// checksum is left out: SWIFT adds the trailer to the message it delivers.
+fn envelope_for(st: &Statement, defaults: &FinEnvelope) -> FinEnvelope {
+    let own = st.envelope.clone().unwrap_or_default();
+    let or = |field: Option<String>, default: &Option<String>| field.or_else(|| default.clone());
+    FinEnvelope {
+        sender: or(own.sender, &defaults.sender).or_else(|| st.servicer_bic.clone()),
+        receiver: or(own.receiver, &defaults.receiver),
+        message_type: or(own.message_type, &defaults.message_type),
+        mur: or(own.mur, &defaults.mur),
+        checksum: None,
+    }
+}
+
+/// Applies `f` to the free text of every entry: descriptions, remittance information and party names.
+fn map_text(st: &mut Statement, f: fn(&str) -> String) {
+    for e in &mut st.entries {
+        e.description = f(&e.description);
+        e.remittance_info.iter_mut().for_each(|r| *r = f(r));
+        for party in e.counterparty.iter_mut().chain(e.own_party.iter_mut()) {
+            party.name = party.name.as_deref().map(f);
+        }
+    }
+}
+
 impl Adapter for Mt940 {
+    /// Reads the first message of the input; use [`Adapter::read_all`] for multi-message files.
     fn read_from<R: BufRead>(r: R) -> Result<Statement, AdapterError> {
-        let reader = BufReader::new(r);
-        let mt_st = parse_mt940_min(reader).map_err(map_parse_err)?;
-        Ok(mt_st.into())
+        let mut all = Self::read_all(r)?;
+        Ok(all.swap_remove(0))
+    }
+
+    fn read_all<R: BufRead>(r: R) -> Result<Vec<Statement>, AdapterError> {
+        Ok(Self::read_with(r, &ReadOptions::default())?.statements)
     }
 
+    fn read_with<R: BufRead>(r: R, options: &ReadOptions) -> Result<ReadReport, AdapterError> {
+        let mut report = ReadReport::new(options.mode);
+        for message in parse_mt940(r, &mut report)? {
+            let mut st = Statement::from(message);
+            report.normalize(&mut st)?;
+            if options.transliterated {
+                map_text(&mut st, detransliterate);
+            }
+            report.statements.push(st);
+        }
+        Ok(report)
+    }
 
+    /// Writes the statement as one message, or as several pages when it
+    /// exceeds the SWIFT message size.
     fn write_to<W: Write>(mut writer: W, st: &Statement) -> Result<(), AdapterError> {
-        writeln!(writer,":20:{}",st.id).map_err(map_parse_err)?;
-        writeln!(writer,":25:{}",st.account_id).map_err(map_parse_err)?;
+        for message in messages(st, MESSAGE_SIZE, &WriteOptions::default())? {
+            writer.write_all(message.as_bytes())?;
+        }
+        Ok(())
+    }
+}
 
-        if let Some(opening_balance) = st.opening_balance.as_ref() {
-            writeln!(writer, ":60F:{}", balance_to_str(opening_balance)).map_err(map_parse_err)?;
+/// Renders `st` as MT940 messages of at most `max_size` characters (counting
+/// `\r\n` line ends). A statement that does not fit is split into pages
+/// numbered in `:28C:`, each closing with a `:62M:` intermediate balance that
+/// the next page opens with as `:60M:`. Of `options`, `german` and
+/// `derive_balances` apply here.
+fn messages(st: &Statement, max_size: usize, options: &WriteOptions) -> Result<Vec<String>, AdapterError> {
+    let size = |lines: &[String]| lines.iter().map(|l| l.chars().count() + 2).sum::<usize>();
+    // MT940 reports booked entries only.
+    let booked: Vec<&Entry> = st.entries.iter().filter(|e| e.is_booked()).collect();
+    let entries = booked.iter().map(|e| entry_lines(e, options.german)).collect::<Result<Vec<_>, _>>().map_err(map_write_err)?;
+
+    let (opening, closing) = booked_balances(st, options.derive_balances)?;
+    let mut trailer = vec![format!("{}{}", balance_tag(closing.balance_type), balance_to_str(&closing))];
+    if let Some(b) = st.balance(BalanceType::ClosingAvailable) {
+        trailer.push(format!("{PREFIX_CLS_AVAILABLE}{}", balance_to_str(b)));
+    }
+    for b in st.balances.iter().filter(|b| b.balance_type == BalanceType::ForwardAvailable) {
+        trailer.push(format!("{PREFIX_FWD_AVAILABLE}{}", balance_to_str(b)));
+    }
+    trailer.extend(extension_lines(&st.extensions));
+
+    // Each page has room for the header, an opening and a closing balance line
+    // (30 characters at most), the trailer and the `-` terminator.
+    let fixed = size(&header_lines(st, Some(99_999))) + 2 * 32 + size(&trailer) + 3;
+    let mut pages = Vec::new();
+    let mut start = 0;
+    let mut used = fixed;
+    for (i, lines) in entries.iter().enumerate() {
+        if i > start && used + size(lines) > max_size {
+            pages.push(start..i);
+            start = i;
+            used = fixed;
         }
+        used += size(lines);
+    }
+    pages.push(start..entries.len());
 
-        for entry in &st.entries {
-            writeln!(writer,":61:{}",entry.booking_date).map_err(map_parse_err)?;
-            writeln!(writer,":86:{}",entry.description).map_err(map_parse_err)?;
+    let count = pages.len();
+    let mut opening = opening;
+    let mut messages = Vec::new();
+    for (i, range) in pages.into_iter().enumerate() {
+        let page = if count > 1 { Some(i as u32 + 1) } else { st.page_number };
+        let mut lines = header_lines(st, page);
+        lines.push(format!("{}{}", balance_tag(opening.balance_type), balance_to_str(&opening)));
+        let movement = Money::checked_sum(booked[range.clone()].iter().map(|e| e.signed_amount())).map_err(map_write_err)?;
+        lines.extend(entries[range].iter().flatten().cloned());
+        if i + 1 == count {
+            lines.extend(trailer.iter().cloned());
+        } else {
+            let amount = opening.signed_amount().checked_add(movement).map_err(map_write_err)?;
+            let intermediate = signed_balance(BalanceType::IntermediateClosing, amount, closing.date, &opening.currency);
+            lines.push(format!("{PREFIX_CLS_BALANCE_INTERIM}{}", balance_to_str(&intermediate)));
+            opening = StBalance { balance_type: BalanceType::IntermediateOpening, ..intermediate };
         }
+        lines.push("-".to_string());
+        messages.push(lines.iter().map(|l| format!("{l}\n")).collect());
+    }
+    Ok(messages)
+}
+
+/// The opening and closing booked balances MT940 requires. Only with `derive`
+/// is a missing one derived from the other and the booked movement, and without
+/// either the statement opened at zero on the date of its first entry.
+fn booked_balances(st: &Statement, derive: bool) -> Result<(StBalance, StBalance), AdapterError> {
+    match (st.opening_balance(), st.closing_balance()) {
+        (Some(opening), Some(closing)) => return Ok((opening.clone(), closing.clone())),
+        (None, _) if !derive => return Err(AdapterError::missing(PREFIX_OPN_BALANCE)),
+        (_, None) if !derive => return Err(AdapterError::missing(PREFIX_CLS_BALANCE)),
+        _ => {}
+    }
+    let booked = || st.entries.iter().filter(|e| e.is_booked());
+    let currency = st.opening_balance().or(st.closing_balance()).map(|b| b.currency.clone())
+        .or_else(|| st.account_currency.clone())
+        .or_else(|| booked().next().map(|e| e.currency.clone()))
+        .ok_or_else(|| AdapterError::missing(PREFIX_OPN_BALANCE))?;
+    let fallback = st.created_at.map(|c| c.date());
+    let first = st.period.map(|p| p.from.date()).or_else(|| booked().map(|e| e.booking_date).min()).or(fallback);
+    let last = st.period.map(|p| p.to.date()).or_else(|| booked().map(|e| e.booking_date).max()).or(fallback);
+    let movement = st.booked_movement().map_err(map_write_err)?;
 
-        if let Some(closing_balance) = st.closing_balance.as_ref() {
-            writeln!(writer, ":62F:{}", balance_to_str(closing_balance)).map_err(map_parse_err)?;
+    match (st.opening_balance(), st.closing_balance()) {
+        (Some(opening), _) => {
+            let amount = opening.signed_amount().checked_add(movement).map_err(map_write_err)?;
+            let closing = signed_balance(BalanceType::ClosingBooked, amount, last.unwrap_or(opening.date), &currency);
+            Ok((opening.clone(), closing))
         }
+        (None, Some(closing)) => {
+            let amount = closing.signed_amount().checked_sub(movement).map_err(map_write_err)?;
+            let opening = signed_balance(BalanceType::OpeningBooked, amount, first.unwrap_or(closing.date), &currency);
+            Ok((opening, closing.clone()))
+        }
+        (None, None) => {
+            let (Some(first), Some(last)) = (first, last) else {
+                return Err(AdapterError::missing(PREFIX_OPN_BALANCE));
+            };
+            let opening = signed_balance(BalanceType::OpeningBooked, Money::zero(), first, &currency);
+            let closing = signed_balance(BalanceType::ClosingBooked, movement, last, &currency);
+            Ok((opening, closing))
+        }
+    }
+}
 
-        Ok(())
+/// A balance of `amount`, debit when negative.
+fn signed_balance(balance_type: BalanceType, amount: Money, date: Date, currency: &str) -> StBalance {
+    StBalance {
+        balance_type,
+        kind: if amount.is_negative() { DebitCredit::Debit } else { DebitCredit::Credit },
+        date,
+        currency: currency.to_string(),
+        amount: amount.abs(),
+    }
+}
+
+/// The fields each page of a statement starts with, up to `:13D:`.
+fn header_lines(st: &Statement, page: Option<u32>) -> Vec<String> {
+    let mut lines = vec![format!(":20:{}", swift_reference(&st.id))];
+    if let Some(related) = &st.related_reference {
+        lines.push(format!(":21:{}", swift_reference(related)));
+    }
+    let account = match &st.servicer_bic {
+        Some(bic) => format!("{bic}/{}", st.account_id),
+        None => st.account_id.clone(),
+    };
+    lines.push(format!(":25:{}", truncate(&account, ACCOUNT_WIDTH)));
+    // `:28C:` is mandatory; without a known sequence the statement is number 1.
+    let number = st.electronic_sequence.or(st.legal_sequence).unwrap_or(1) % 100_000;
+    lines.push(match page {
+        Some(page) => format!(":28C:{number}/{}", page % 100_000),
+        None => format!(":28C:{number}"),
+    });
+    if let Some(created_at) = &st.created_at {
+        lines.push(format!(":13D:{}", created_at.format_13d()));
+    }
+    lines
+}
+
+/// The `:61:` field of a booked entry with its supplementary details, `:86:` and unknown tags.
+fn entry_lines(entry: &Entry, german: bool) -> Result<Vec<String>, String> {
+    let mut lines = vec![format!(":61:{}", transaction_61_to_str(entry))];
+    if let Some(details) = supplementary_61_to_str(entry)? {
+        lines.push(continuation(&truncate(&details, SUPPLEMENTARY_WIDTH)));
+    }
+    let mut info = if german { german_86_lines(entry) } else { wrap_86(&info_86_to_str(entry)) };
+    info[0].insert_str(0, PREFIX_TX_DESC);
+    lines.extend(info);
+    lines.extend(extension_lines(&entry.extensions));
+    Ok(lines)
+}
+
+/// The unknown tags kept by the reader, wrapped to 65 characters.
+fn extension_lines(extensions: &Extensions) -> Vec<String> {
+    let mut lines = Vec::new();
+    for (tag, value) in extensions.with_prefix(EXT_PREFIX) {
+        let mut wrapped = wrap(value, LINE_WIDTH).into_iter();
+        lines.push(format!(":{tag}:{}", wrapped.next().unwrap_or_default()));
+        lines.extend(wrapped.map(|l| continuation(&l)));
+    }
+    lines
+}
+
+/// A reference as SWIFT accepts it (16x): at most 16 characters, not starting
+/// or ending with `/` and without `//`; an empty one becomes `NONREF`.
+fn swift_reference(reference: &str) -> String {
+    let mut out = reference.to_string();
+    while out.contains("//") {
+        out = out.replace("//", "/");
+    }
+    let out = truncate(out.trim_matches('/'), REFERENCE_WIDTH);
+    let out = out.trim_matches('/');
+    if out.is_empty() { "NONREF".to_string() } else { out.to_string() }
+}
+
+/// The first `width` characters of `s`.
+fn truncate(s: &str, width: usize) -> String {
+    s.chars().take(width).collect()
+}
+
+/// MT940 tag for an opening or closing booked balance.
+fn balance_tag(balance_type: BalanceType) -> &'static str {
+    match balance_type {
+        BalanceType::IntermediateOpening => PREFIX_OPN_BALANCE_INTERIM,
+        BalanceType::IntermediateClosing => PREFIX_CLS_BALANCE_INTERIM,
+        BalanceType::ClosingBooked => PREFIX_CLS_BALANCE,
+        _ => PREFIX_OPN_BALANCE,
     }
 }
 
@@ -80,145 +428,1013 @@ fn balance_to_str(balance: &StBalance) -> String {
         DebitCredit::Debit => 'D',
     };
 
-    let date_short = if balance.date_yyymmdd.len() == 8 {
-        &balance.date_yyymmdd[2..]
+    let amount_str = swift_amount(balance.amount, &balance.currency);
+
+    format!("{}{}{}{}", sign, balance.date.format_yymmdd(), balance.currency, amount_str)
+}
+
+/// Amount with the decimals of its currency and a decimal comma, which SWIFT
+/// requires even without decimals (`1000,`).
+fn swift_amount(amount: Money, currency: &str) -> String {
+    let s = scale(amount, currency).format_with(',');
+    if s.contains(',') { s } else { s + "," }
+}
+
+/// Fields of one MT940 message collected while scanning lines.
+#[derive(Default)]
+struct MessageParts {
+    line: usize, // line of the first field
+    raw: Vec<String>, // lines of the message, for the quarantine
+    reference: String,
+    related_reference: Option<String>,
+    account_id: String,
+    servicer_bic: Option<String>,
+    statement_number: Option<u64>,
+    sequence_number: Option<u32>,
+    created_at: Option<DateTime>,
+    opening_balance: Option<(BalanceType, Balance)>,
+    closing_balance: Option<(BalanceType, Balance)>,
+    closing_available: Option<Balance>,
+    forward_available: Vec<Balance>,
+    transactions: Vec<Transaction>,
+    extensions: Extensions,
+    envelope: Option<FinEnvelope>,
+}
+
+impl MessageParts {
+    /// Where an unknown tag belongs: the current transaction until the closing balance, else the statement.
+    fn extensions_mut(&mut self) -> &mut Extensions {
+        match self.transactions.last_mut() {
+            Some(tx) if self.closing_balance.is_none() => &mut tx.extensions,
+            _ => &mut self.extensions,
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.reference.is_empty() && self.account_id.is_empty() && self.opening_balance.is_none()
+    }
+
+    /// Completes the message; a missing mandatory tag is reported at the message's first line.
+    fn finish(self) -> Result<MT940Statement, AdapterError> {
+        let line = self.line;
+        let missing = |tag: &str| {
+            let e = AdapterError::missing(tag);
+            if line > 0 { e.at(Location::Line { line, column: 1 }) } else { e }
+        };
+        if self.reference.is_empty() { return Err(missing(PREFIX_TX_ID)); }
+        if self.account_id.is_empty() { return Err(missing(PREFIX_ACCOUNT_ID)); }
+        let opening_balance = self.opening_balance.ok_or_else(|| missing(":60a:"))?;
+        let closing_balance = self.closing_balance.ok_or_else(|| missing(":62a:"))?;
+
+        Ok(MT940Statement {
+            reference: self.reference,
+            related_reference: self.related_reference,
+            account_id: self.account_id,
+            servicer_bic: self.servicer_bic,
+            statement_number: self.statement_number,
+            sequence_number: self.sequence_number,
+            created_at: self.created_at,
+            opening_balance,
+            transactions: self.transactions,
+            closing_balance,
+            closing_available: self.closing_available,
+            forward_available: self.forward_available,
+            extensions: self.extensions,
+            envelope: self.envelope,
+        })
+    }
+}
+
+/// Tags that end the fields of a transaction: the next transaction or a closing balance.
+const TX_END_TAGS: [&str; 5] = ["61", "62F", "62M", "64", "65"];
+
+/// Tags that occur once per message; a repeated one starts the next message.
+const ONCE_TAGS: [&str; 8] = ["20", "21", "25", "28", "13D", "60", "62", "64"];
+
+/// Lines of a `:86:` field: 6 lines of 65 characters.
+const INFO_86_LINES: usize = 6;
+const INFO_86_WIDTH: usize = 65;
+
+/// Longest line of field content in a written message, after the tag.
+const LINE_WIDTH: usize = 65;
+/// Length limits of references (16x), the `:25:` account (35x) and the `:61:` supplementary details (34x).
+const REFERENCE_WIDTH: usize = 16;
+const ACCOUNT_WIDTH: usize = 35;
+const SUPPLEMENTARY_WIDTH: usize = 34;
+/// Longest text block of a SWIFT message; longer statements are written as pages.
+const MESSAGE_SIZE: usize = 2000;
+
+/// One field of a message: the tag and the value with its continuation lines.
+struct Field {
+    tag: String,        // e.g. `61`; `{` for FIN header blocks, `-` for the message end
+    lines: Vec<String>, // the value on the tag line, then continuation lines
+    line: usize,        // line number of the tag
+}
+
+impl Field {
+    /// The tag as written, e.g. `:61:`.
+    fn name(&self) -> String {
+        match self.tag.as_str() {
+            "{" => "FIN header".to_string(),
+            "-" => "FIN trailer".to_string(),
+            tag => format!(":{tag}:"),
+        }
+    }
+
+    /// The tag under which [`ONCE_TAGS`] lists it: `:28C:` and `:28:`, `:60F:` and `:60M:`
+    /// (and so on) count as one.
+    fn once_key(&self) -> Option<&str> {
+        let key = match self.tag.as_str() {
+            "28C" | "60F" | "60M" | "62F" | "62M" => &self.tag[..2],
+            tag => tag,
+        };
+        ONCE_TAGS.contains(&key).then_some(key)
+    }
+
+    /// Source text of the field, for the quarantine.
+    fn raw(&self) -> String {
+        match self.tag.as_str() {
+            "" | "-" | "{" => self.lines.join("\n"),
+            tag => format!(":{tag}:{}", self.lines.join("\n")),
+        }
+    }
+}
+
+/// Splits the input into fields: a line starting with a tag opens a field and
+/// the lines up to the next tag continue it. A `-` line ends the message.
+fn tokenize(input: impl BufRead) -> Result<Vec<Field>, AdapterError> {
+    let mut fields: Vec<Field> = Vec::new();
+    for (i, raw_line) in input.lines().enumerate() {
+        push_line(&mut fields, &raw_line?, i + 1);
+    }
+    Ok(fields)
+}
+
+/// Adds a line to the fields. FIN header blocks up to `{4:` become a field
+/// tagged `{`; the end of the text block `-}` with the trailer becomes a `-` field.
+fn push_line(fields: &mut Vec<Field>, line: &str, line_no: usize) {
+    if line.is_empty() { return; }
+    let new = |tag: &str, text: &str| Field { tag: tag.to_string(), lines: vec![text.to_string()], line: line_no };
+    if let Some((tag, value)) = split_tag(line) {
+        fields.push(new(tag, value));
+    } else if line == "-" || line.starts_with("-}") {
+        // The trailer may be followed by the header of the next message.
+        match line.find("{1:") {
+            Some(i) => {
+                fields.push(new("-", &line[..i]));
+                push_line(fields, &line[i..], line_no);
+            }
+            None => fields.push(new("-", line)),
+        }
+    } else if line.starts_with('{') {
+        // The first line of the text block may follow `{4:` directly.
+        let (header, text) = line.find("{4:").map_or((line, ""), |i| line.split_at(i + 3));
+        match fields.last_mut() {
+            Some(f) if f.tag == "{" && !f.lines.concat().contains("{4:") => f.lines.push(header.to_string()),
+            _ => fields.push(new("{", header)),
+        }
+        push_line(fields, text, line_no);
     } else {
-        &balance.date_yyymmdd
-    };
+        match fields.last_mut() {
+            Some(f) if f.tag != "-" && f.tag != "{" => f.lines.push(line.to_string()),
+            // Text outside any field.
+            _ => fields.push(new("", line)),
+        }
+    }
+}
 
-    let amount_str = format!("{:.2}", balance.amount).replace('.', ",");
-
-    format!("{}{}{}{}", sign, date_short, balance.currency, amount_str)
-}
-
-
-fn parse_mt940_min(input: BufReader<impl Read>) -> Result<MT940Statement, String> {
-    let mut reference = String::new();
-    let mut account_id = String::new();
-    let mut opening_balance: Option<Balance> = None;
-    let mut closing_balance: Option<Balance> = None;
-    let mut transactions: Vec<Transaction> = Vec::new();
-    let mut last_tx_needs_86 = false;
-
-    for raw_line in input.lines() {
-        let line = raw_line.unwrap();
-        if line.is_empty() { continue; }
-
-        if let Some(rest) = line.strip_prefix(PREFIX_TX_ID) {
-            reference = rest.trim().to_string();
-        } else if let Some(rest) = line.strip_prefix(PREFIX_ACCOUNT_ID) {
-            account_id = rest.trim().to_string();
-        } else if let Some(rest) = line.strip_prefix(PREFIX_OPN_BALANCE) {
-            opening_balance = Some(parse_balance_field(rest.trim())?);
-        } else if let Some(rest) = line.strip_prefix(PREFIX_TX) {
-            let (tx, _) = parse_transaction_61(rest.trim())?;
-            transactions.push(tx);
-            last_tx_needs_86 = true;
-        } else if let Some(rest) = line.strip_prefix(PREFIX_TX_DESC) {
-            if last_tx_needs_86 {
-                if let Some(last) = transactions.last_mut() {
-                    if !last.description.is_empty() {
-                        last.description.push(' ');
-                    }
-                    last.description.push_str(rest.trim());
+/// Joins the lines of a `:86:` field. Structured text (`/CODE/...`) and lines
+/// filling the whole width are wrapped text and joined directly; other line
+/// breaks are the author's and kept.
+fn join_86(lines: &[String]) -> String {
+    let structured = lines.first().is_some_and(|l| l.starts_with('/'));
+    let mut out = String::new();
+    for (i, line) in lines.iter().enumerate() {
+        if i > 0 && !structured && lines[i - 1].chars().count() < INFO_86_WIDTH {
+            out.push('\n');
+        }
+        out.push_str(line);
+    }
+    out
+}
+
+/// Wraps `:86:` text into lines of 65 characters, keeping its line breaks;
+/// text beyond 6 lines is cut.
+fn wrap_86(text: &str) -> Vec<String> {
+    let mut lines = wrap(text, INFO_86_WIDTH);
+    lines.truncate(INFO_86_LINES);
+    lines.iter_mut().skip(1).for_each(|l| *l = continuation(l));
+    lines
+}
+
+/// A continuation line the reader keeps in its field: a leading `:`, `-` or
+/// `{` would start a new field, end the message or open a FIN header, so it is dropped.
+fn continuation(line: &str) -> String {
+    line.trim_start_matches([':', '-', '{']).to_string()
+}
+
+/// Splits `text` into lines of at most `width` characters, keeping its line breaks.
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    for paragraph in text.split('\n') {
+        let chars: Vec<char> = paragraph.chars().collect();
+        if chars.is_empty() {
+            lines.push(String::new());
+        }
+        lines.extend(chars.chunks(width).map(|c| c.iter().collect::<String>()));
+    }
+    lines
+}
+
+/// A record being skipped in lenient mode.
+enum Skip {
+    /// A `:61:` with its `:86:` and other fields, up to the next transaction or balance.
+    Transaction { location: Location, raw: Vec<String> },
+    /// The rest of the current message.
+    Message,
+}
+
+/// Field-by-field state of [`parse_mt940`].
+#[derive(Default)]
+struct Scanner {
+    messages: Vec<MT940Statement>,
+    msg: MessageParts,
+    seen: Vec<String>, // keys of the once-only tags of the current message
+    tx_start: usize, // index in `msg.raw` of the current `:61:`
+    tx_line: usize,  // and its line
+    tx_86: Vec<String>, // lines of the `:86:` of the current transaction
+    open_61: Option<usize>, // line of a `:61:` still waiting for its `:86:`
+    skip: Option<Skip>,
+    page: Option<u32>, // last page read into the last message
+}
+
+/// Parses every message in the input. A message ends with a `-` line or where
+/// a tag that occurs once per message repeats, so tags may come in any order.
+///
+/// A field that cannot be read rejects its transaction (`:61:` and the fields
+/// up to the next one) or else its whole message; see [`ReadReport::reject`].
+fn parse_mt940(input: impl BufRead, report: &mut ReadReport) -> Result<Vec<MT940Statement>, AdapterError> {
+    let mut sc = Scanner::default();
+    for field in tokenize(input)? {
+        sc.field(field, report)?;
+    }
+
+    sc.flush_transaction(report);
+    if !sc.msg.is_empty() || sc.skip.is_some() {
+        sc.finish_message(report)?;
+    } else if sc.messages.is_empty() && report.rejected.is_empty() {
+        std::mem::take(&mut sc.msg).finish()?;
+    }
+    Ok(sc.messages)
+}
+
+impl Scanner {
+    fn field(&mut self, f: Field, report: &mut ReadReport) -> Result<(), AdapterError> {
+        if f.tag == "-" {
+            self.read_trailer(&f, report)?;
+        }
+        let once = f.once_key().map(str::to_string);
+        let boundary = f.tag == "-" || f.tag == "{" || once.as_ref().is_some_and(|key| self.seen.contains(key));
+        if boundary {
+            self.flush_transaction(report);
+            if !self.msg.is_empty() || self.skip.is_some() {
+                self.finish_message(report)?;
+            } else {
+                self.msg = MessageParts::default();
+                self.seen.clear();
+            }
+            if f.tag == "-" {
+                return Ok(());
+            }
+        }
+        self.seen.extend(once);
+
+        match &mut self.skip {
+            Some(Skip::Message) => {
+                self.msg.raw.push(f.raw());
+                return Ok(());
+            }
+            Some(Skip::Transaction { raw, .. }) if !TX_END_TAGS.contains(&f.tag.as_str()) => {
+                raw.push(f.raw());
+                self.msg.raw.push(f.raw());
+                return Ok(());
+            }
+            _ => {}
+        }
+        self.flush_transaction(report);
+
+        if self.msg.raw.is_empty() {
+            self.msg.line = f.line;
+        }
+        if f.tag == "61" {
+            self.tx_start = self.msg.raw.len();
+            self.tx_line = f.line;
+        }
+        self.msg.raw.push(f.raw());
+
+        let Err(e) = self.read_field(&f, report) else { return Ok(()) };
+        report.reject(e)?;
+        // Lenient mode: skip the transaction the field belongs to, or else the message.
+        let in_tx = f.tag == "61" || (f.tag == "86" && !self.tx_86.is_empty());
+        if in_tx && self.msg.closing_balance.is_none() {
+            if f.tag != "61" {
+                self.msg.transactions.pop();
+            }
+            let location = Location::Line { line: self.tx_line, column: 1 };
+            self.skip = Some(Skip::Transaction { location, raw: self.msg.raw[self.tx_start..].to_vec() });
+        } else {
+            self.skip = Some(Skip::Message);
+        }
+        self.open_61 = None;
+        self.tx_86.clear();
+        Ok(())
+    }
+
+    /// Reads one field into the current message.
+    fn read_field(&mut self, f: &Field, report: &mut ReadReport) -> Result<(), AdapterError> {
+        // Errors point at the field value.
+        let name = f.name();
+        let value = f.lines[0].trim();
+        let column = if f.tag.starts_with(|c: char| c.is_ascii_alphanumeric()) { name.len() + 1 } else { 1 };
+        let location = Location::Line { line: f.line, column };
+        let invalid = |e: String| map_parse_err(e).in_field(&name, value, location.clone());
+        let msg = &mut self.msg;
+
+        let single_line = match name.as_str() {
+            PREFIX_TX_ID => {
+                msg.reference = value.to_string();
+                true
+            }
+            PREFIX_RELATED_REF => {
+                msg.related_reference = Some(value.to_string());
+                true
+            }
+            PREFIX_ACCOUNT_ID => {
+                let (bic, account) = parse_account_25(value);
+                // Invalid identifiers are kept as read.
+                let prefix = value.split_once('/').map(|(p, _)| p).filter(|p| bic.is_none() && looks_like_bic(p));
+                if let Err(e) = prefix.map_or(Ok(()), validate_bic).and_then(|()| check_account_id(&account)) {
+                    report.warn(invalid(e))?;
+                }
+                msg.servicer_bic = bic;
+                msg.account_id = account;
+                true
+            }
+            PREFIX_STMT_NO | PREFIX_STMT_NO_OLD => {
+                let (number, sequence) = parse_statement_number_28c(value).map_err(invalid)?;
+                msg.statement_number = Some(number);
+                msg.sequence_number = sequence;
+                true
+            }
+            PREFIX_CREATED_AT => {
+                msg.created_at = Some(DateTime::parse_13d(value).map_err(invalid)?);
+                true
+            }
+            PREFIX_OPN_BALANCE | PREFIX_OPN_BALANCE_INTERIM => {
+                let balance_type = if name == PREFIX_OPN_BALANCE { BalanceType::OpeningBooked } else { BalanceType::IntermediateOpening };
+                msg.opening_balance = Some((balance_type, parse_balance_field(&name, value).map_err(|e| e.at(location.clone()))?));
+                true
+            }
+            PREFIX_CLS_BALANCE | PREFIX_CLS_BALANCE_INTERIM => {
+                let balance_type = if name == PREFIX_CLS_BALANCE { BalanceType::ClosingBooked } else { BalanceType::IntermediateClosing };
+                msg.closing_balance = Some((balance_type, parse_balance_field(&name, value).map_err(|e| e.at(location.clone()))?));
+                self.check_86(report)?;
+                true
+            }
+            PREFIX_CLS_AVAILABLE => {
+                msg.closing_available = Some(parse_balance_field(&name, value).map_err(|e| e.at(location.clone()))?);
+                true
+            }
+            PREFIX_FWD_AVAILABLE => {
+                msg.forward_available.push(parse_balance_field(&name, value).map_err(|e| e.at(location.clone()))?);
+                true
+            }
+            PREFIX_TX => {
+                self.check_86(report)?;
+                self.tx_86.clear();
+                let mut tx = parse_transaction_61(value).map_err(invalid)?;
+                // Continuation lines hold the supplementary details.
+                if f.lines.len() > 1 {
+                    let details = f.lines[1..].join("\n");
+                    let at = Location::Line { line: f.line + 1, column: 1 };
+                    parse_supplementary_61(details.trim(), &mut tx)
+                        .map_err(|e| map_parse_err(e).in_field(&name, details.trim(), at))?;
                 }
-                last_tx_needs_86 = false;
+                self.msg.transactions.push(tx);
+                self.open_61 = Some(f.line);
+                false
+            }
+            // The first `:86:` after a `:61:` describes it, and further ones continue it.
+            PREFIX_TX_DESC if self.open_61.take().is_some() || (!self.tx_86.is_empty() && msg.closing_balance.is_none()) => {
+                self.tx_86.extend(f.lines.iter().cloned());
+                if let Some(last) = msg.transactions.last_mut() {
+                    last.info = parse_info_86(join_86(&self.tx_86).trim()).map_err(invalid)?;
+                }
+                false
+            }
+            _ if f.tag == "{" => {
+                msg.envelope = Some(parse_fin_header(&f.lines.concat()).map_err(invalid)?);
+                false
+            }
+            // Statement-level `:86:` and unknown tags are kept as is.
+            _ if !f.tag.is_empty() => {
+                msg.extensions_mut().push(format!("{EXT_PREFIX}{}", f.tag), f.lines.join("\n"));
+                false
+            }
+            _ => {
+                let at = Location::Line { line: f.line, column: 1 };
+                report.warn(map_parse_err("text outside a field ignored").at(at))?;
+                false
+            }
+        };
+        if single_line && f.lines.len() > 1 {
+            let at = Location::Line { line: f.line + 1, column: 1 };
+            report.warn(map_parse_err(format!("continuation line of {name} ignored")).at(at))?;
+        }
+        Ok(())
+    }
+
+    /// Reads the checksum from the FIN trailer after the end of the text block.
+    fn read_trailer(&mut self, f: &Field, report: &mut ReadReport) -> Result<(), AdapterError> {
+        let trailer = f.lines[0].strip_prefix("-}").unwrap_or_default();
+        if trailer.is_empty() {
+            return Ok(());
+        }
+        match parse_fin_trailer(trailer) {
+            Ok(checksum) => self.msg.envelope.get_or_insert_default().checksum = checksum,
+            Err(e) => {
+                let at = Location::Line { line: f.line, column: 3 };
+                report.warn(map_parse_err(e).in_field(&f.name(), trailer, at))?;
             }
-        } else if let Some(rest) = line.strip_prefix(PREFIX_CLS_BALANCE) {
-            closing_balance = Some(parse_balance_field(rest.trim())?);
         }
+        Ok(())
     }
 
-    let opening_balance = opening_balance.ok_or("missing :60F: opening balance")?;
-    let closing_balance = closing_balance.ok_or("missing :62F: closing balance")?;
-    if reference.is_empty() { return Err("missing :20: reference".into()); }
-    if account_id.is_empty() { return Err("missing :25: account id".into()); }
+    /// Reports a `:61:` that was not followed by its `:86:`.
+    fn check_86(&mut self, report: &mut ReadReport) -> Result<(), AdapterError> {
+        match self.open_61.take() {
+            Some(line) => report.warn(AdapterError::missing(PREFIX_TX_DESC).at(Location::Line { line, column: 1 })),
+            None => Ok(()),
+        }
+    }
 
-    Ok(MT940Statement {
-        reference,
-        account_id,
-        opening_balance,
-        transactions,
-        closing_balance,
-    })
+    /// Quarantines the transaction being skipped, if any.
+    fn flush_transaction(&mut self, report: &mut ReadReport) {
+        if let Some(Skip::Transaction { location, raw }) = self.skip.take_if(|s| matches!(s, Skip::Transaction { .. })) {
+            report.quarantine(Some(location), raw.join("\n"));
+        }
+    }
+
+    /// Completes the current message, or quarantines it when it was skipped or lacks a mandatory tag.
+    fn finish_message(&mut self, report: &mut ReadReport) -> Result<(), AdapterError> {
+        let skipped = self.skip.take().is_some();
+        if !skipped {
+            self.check_86(report)?;
+        }
+        self.open_61 = None;
+        self.tx_86.clear();
+        self.seen.clear();
+        let mut msg = std::mem::take(&mut self.msg);
+        let location = (msg.line > 0).then_some(Location::Line { line: msg.line, column: 1 });
+        let raw = std::mem::take(&mut msg.raw).join("\n");
+        if !skipped {
+            match msg.finish().and_then(|m| self.push_page(m, location.clone(), report)) {
+                Ok(()) => return Ok(()),
+                Err(e) => report.reject(e)?,
+            }
+        }
+        report.quarantine(location, raw);
+        Ok(())
+    }
+
+    /// Adds a message, joining it to the previous one when it is the next page
+    /// of the same statement: same `:20:`, account and statement number, the
+    /// following page number, and a `:60M:` that takes over the `:62M:` balance.
+    fn push_page(&mut self, m: MT940Statement, location: Option<Location>, report: &mut ReadReport) -> Result<(), AdapterError> {
+        let page = m.sequence_number;
+        let at = |e: AdapterError| match &location {
+            Some(l) => e.at(l.clone()),
+            None => e,
+        };
+        if let Some(prev) = self.messages.last_mut()
+            && self.page.is_some_and(|p| page == Some(p + 1))
+            && prev.continues_with(&m)
+        {
+            let (closing, opening) = (&prev.closing_balance.1, &m.opening_balance.1);
+            if (closing.signed(), &closing.currency) != (opening.signed(), &opening.currency) {
+                let value = format!("{}{}", opening.currency, opening.signed());
+                let reason = format!("page {} opens with a balance other than {}{} closing page {}", page.unwrap_or_default(), closing.currency, closing.signed(), page.unwrap_or_default() - 1);
+                return Err(at(AdapterError::invalid(PREFIX_OPN_BALANCE_INTERIM, value, reason)));
+            }
+            prev.append_page(m);
+            self.page = page;
+            return Ok(());
+        }
+        if m.opening_balance.0 == BalanceType::IntermediateOpening && let Some(p) = page.filter(|&p| p > 1) {
+            let e = AdapterError::invalid(PREFIX_STMT_NO, format!("{}/{p}", m.statement_number.unwrap_or_default()), "the previous page is missing");
+            report.warn(at(e))?;
+        }
+        self.page = page;
+        self.messages.push(m);
+        Ok(())
+    }
+}
+
+/// Splits a line starting with a field tag (`:NS:`, `:34F:`, ...) into the tag and its value.
+fn split_tag(line: &str) -> Option<(&str, &str)> {
+    let rest = line.strip_prefix(':')?;
+    let (tag, value) = rest.split_once(':')?;
+    let valid = (2..=3).contains(&tag.len()) && tag.bytes().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit());
+    valid.then_some((tag, value))
 }
 
-fn parse_balance_field(s: &str) -> Result<Balance, String> {
-    if s.len() < 1 + 6 + 3 {
-        return Err(format!("balance too short: `{s}`"));
+/// Splits `:25:` into an optional leading `BIC/` and the account number;
+/// an IBAN is stored in electronic format.
+fn parse_account_25(s: &str) -> (Option<String>, String) {
+    match s.split_once('/') {
+        Some((bic, account)) if is_bic(bic) => (Some(bic.to_string()), normalize_account_id(account)),
+        _ => (None, normalize_account_id(s)),
     }
-    let sign = &s[0..1];
+}
+
+/// Parses `:28C:` (`5n[/5n]`): statement number and optional sequence number.
+fn parse_statement_number_28c(s: &str) -> Result<(u64, Option<u32>), String> {
+    let bad = || format!(":28C: invalid statement number `{s}`");
+    let (number, sequence) = match s.split_once('/') {
+        Some((n, seq)) => (n, Some(seq.parse::<u32>().map_err(|_| bad())?)),
+        None => (s, None),
+    };
+    Ok((number.parse::<u64>().map_err(|_| bad())?, sequence))
+}
+
+fn parse_balance_field(tag: &str, s: &str) -> Result<Balance, AdapterError> {
+    let invalid = |reason: String| AdapterError::invalid(tag, s, reason);
+    let (Some(sign), Some(date), Some(currency), Some(amount_str)) = (s.get(0..1), s.get(1..7), s.get(7..10), s.get(10..)) else {
+        return Err(invalid("too short or not ASCII".to_string()));
+    };
     let credit = match sign {
         "C" => true,
         "D" => false,
-        _ => return Err(format!("unknown balance sign `{sign}`")),
+        _ => return Err(invalid(format!("unknown balance sign `{sign}`"))),
     };
 
-    let date = &s[1..1 + 6];
-    let currency = &s[1 + 6..1 + 6 + 3];
-    let amount_str = &s[1 + 6 + 3..];
-
     Ok(Balance {
         credit,
-        date_yyymmdd: date.to_string(),
+        date: Date::parse_yymmdd(date).map_err(invalid)?,
         currency: currency.to_string(),
-        amount: amount_str.to_string(),
+        amount: Money::parse(amount_str).map_err(invalid)?,
     })
 }
 
-fn parse_transaction_61(s: &str) -> Result<(Transaction, ()), String> {
-    if s.len() < 6 + 4 + 1 + 1 {
-        return Err(format!(":61: too short: `{s}`"));
-    }
+/// Parses the `:61:` statement line `6!n[4!n]2a[1!a]15d1!a3!c16x[//16x]`: value
+/// date, optional entry date, mark, funds code, amount, transaction type, and
+/// the references of the account owner and of the bank.
+fn parse_transaction_61(s: &str) -> Result<Transaction, String> {
+    let value_date = Date::parse_yymmdd(s.get(..6).ok_or_else(|| format!(":61: too short: `{s}`"))?)?;
+    let mut rest = &s[6..];
 
-    let date = &s[0..6];
-    let entry = &s[6..10];
+    // The entry date is present when digits follow the value date.
+    let booking_date = match rest.get(..4) {
+        Some(mmdd) if mmdd.bytes().all(|b| b.is_ascii_digit()) => {
+            rest = &rest[4..];
+            compose_booking_date(&value_date, mmdd)?
+        }
+        _ => value_date,
+    };
 
-    let sign = &s[10..11];
-    let is_credit = match sign {
-        "C" => true,
-        "D" => false,
-        _ => return Err(format!(":61: bad sign `{sign}`")),
+    // Mark: C/D, or RC/RD for a reversal of a credit (booked as debit) or of a debit.
+    let (is_credit, reversal, mark_len) = match rest.as_bytes() {
+        [b'R', b'C', ..] => (false, true, 2),
+        [b'R', b'D', ..] => (true, true, 2),
+        [b'C', ..] => (true, false, 1),
+        [b'D', ..] => (false, false, 1),
+        _ => return Err(format!(":61: bad debit/credit mark in `{s}`")),
     };
+    rest = &rest[mark_len..];
 
-    let mut i = 11;
-    let bytes = s.as_bytes();
-    while i < s.len() && (bytes[i].is_ascii_digit() || bytes[i] == b',' || bytes[i] == b'.') {
-        i += 1;
+    // Funds code: the third letter of the currency code, when given.
+    let funds_code = rest.chars().next().filter(|c| c.is_ascii_alphabetic());
+    if funds_code.is_some() {
+        rest = &rest[1..];
     }
 
-    if i == 11 {
+    let amount_len = rest.find(|c: char| !(c.is_ascii_digit() || c == ',' || c == '.')).unwrap_or(rest.len());
+    if amount_len == 0 {
         return Err(format!(":61: amount missing in `{s}`"));
     }
+    let amount = Money::parse(&rest[..amount_len])?;
+    rest = &rest[amount_len..];
+
+    // Transaction type: N/F/S followed by a three-character identification code.
+    let type_code = match rest.get(..4) {
+        Some(code) if code.starts_with(['N', 'F', 'S']) && code.bytes().all(|c| c.is_ascii_alphanumeric()) => code,
+        _ => return Err(format!(":61: type code missing in `{s}`")),
+    };
+    rest = &rest[4..];
+
+    // Reference for the account owner, optionally followed by `//` and the bank's reference.
+    let (reference, bank_reference) = match rest.split_once("//") {
+        Some((own, bank)) => (own.to_string(), Some(bank.to_string()).filter(|b| !b.is_empty())),
+        None => (rest.to_string(), None),
+    };
+
+    Ok(Transaction {
+        value_date,
+        booking_date,
+        is_credit,
+        reversal,
+        funds_code,
+        amount,
+        type_code: type_code.to_string(),
+        reference,
+        bank_reference,
+        original_amount: None,
+        charges: None,
+        supplementary: None,
+        info: Info86::default(),
+        extensions: Extensions::default(),
+    })
+}
+
+/// Reads the `:61:` supplementary details line: `/OCMT/` (original amount) and
+/// `/CHGS/` (charges), e.g. `/OCMT/USD110,00//CHGS/EUR1,50/`; other text is kept as is.
+fn parse_supplementary_61(s: &str, tx: &mut Transaction) -> Result<(), String> {
+    let mut rest = s.to_string();
+    for code in ["/OCMT/", "/CHGS/"] {
+        let Some(start) = rest.find(code) else { continue };
+        let value_start = start + code.len();
+        let end = rest[value_start..].find('/').map_or(rest.len(), |i| value_start + i);
+        let amount = Some(parse_currency_amount(&rest[value_start..end])?);
+        if code == "/OCMT/" {
+            tx.original_amount = amount;
+        } else {
+            tx.charges = amount;
+        }
+        rest.replace_range(start..(end + 1).min(rest.len()), "");
+    }
+    tx.supplementary = Some(rest.trim().to_string()).filter(|r| !r.is_empty());
+    Ok(())
+}
+
+/// Parses `3!a15d`: a currency code directly followed by an amount.
+fn parse_currency_amount(s: &str) -> Result<CurrencyAmount, String> {
+    match s.get(..3) {
+        Some(ccy) if ccy.bytes().all(|c| c.is_ascii_uppercase()) => Ok(CurrencyAmount {
+            currency: ccy.to_string(),
+            amount: Money::parse(&s[3..])?,
+        }),
+        _ => Err(format!("invalid currency amount `{s}`")),
+    }
+}
+
+/// Renders the `:61:` supplementary details: the original amount, total charges and other details.
+fn supplementary_61_to_str(entry: &Entry) -> Result<Option<String>, String> {
+    let mut out = String::new();
+    if let Some(ocmt) = &entry.instructed_amount {
+        out.push_str(&format!("/OCMT/{}{}/", ocmt.currency, swift_amount(ocmt.amount, &ocmt.currency)));
+    }
+    // `/CHGS/` holds a single amount: the charges in the first charge's currency.
+    if let Some(first) = entry.charges.first() {
+        let currency = &first.amount.currency;
+        let total = Money::checked_sum(entry.charges.iter()
+            .filter(|c| &c.amount.currency == currency)
+            .map(|c| c.amount.amount))?;
+        out.push_str(&format!("/CHGS/{currency}{}/", swift_amount(total, currency)));
+    }
+    if let Some(details) = &entry.supplementary_details {
+        out.push_str(details);
+    }
+    Ok((!out.is_empty()).then_some(out))
+}
+
+/// Renders the `:61:` field: value date, entry date, mark, funds code, amount, type code and references.
+fn transaction_61_to_str(entry: &Entry) -> String {
+    let mark = match (entry.kind, entry.reversal) {
+        (DebitCredit::Credit, false) => "C",
+        (DebitCredit::Debit, false) => "D",
+        (DebitCredit::Debit, true) => "RC",
+        (DebitCredit::Credit, true) => "RD",
+    };
+    let type_code = entry.transaction_code.as_ref().map_or_else(|| TransactionCode::default().swift_code(), TransactionCode::swift_code);
+    let reference = swift_reference(entry.reference.as_deref()
+        .or(entry.end_to_end_id.as_deref())
+        .unwrap_or_default());
+    let bank_reference = entry.servicer_reference.as_deref()
+        .map(swift_reference)
+        .map(|r| format!("//{r}"))
+        .unwrap_or_default();
+    format!(
+        "{}{}{}{}{}{}{}{}",
+        entry.value_date.format_yymmdd(),
+        entry.booking_date.format_mmdd(),
+        mark,
+        entry.funds_code.map(String::from).unwrap_or_default(),
+        swift_amount(entry.amount, &entry.currency),
+        type_code,
+        reference,
+        bank_reference,
+    )
+}
+
+/// Field codes recognised in a structured `:86:` (`/CODE/value/...`).
+const INFO_86_CODES: [&str; 5] = ["CNTP", "INN", "EREF", "MARF", "REMI"];
+
+/// Splits a `:86:` field into free text, counterparty details and references.
+///
+/// Structured fields follow the `/CNTP/account/bic/name/city/` convention, with
+/// `/INN/` carrying the counterparty tax id, `/EREF/` the end-to-end id, `/MARF/`
+/// the mandate id, and `/REMI/USTD//text/` or `/REMI/STRD/CUR/reference/` the
+/// remittance information. Anything else is kept as free text. Within the
+/// values, `?/` stands for `/` and `??` for `?` (see [`escape_86`]).
+fn parse_info_86(s: &str) -> Result<Info86, String> {
+    if let Some(info) = parse_german_86(s) {
+        return Ok(info);
+    }
+    if !s.starts_with('/') || !INFO_86_CODES.iter().any(|c| s.contains(&format!("/{c}/"))) {
+        return Ok(Info86 { text: s.to_string(), ..Default::default() });
+    }
+
+    let tokens = split_86(&s[1..]);
+    let mut info = Info86::default();
+    let mut cp = Counterparty::default();
+    let mut text: Vec<&str> = Vec::new();
+    let opt = |v: Option<&String>| v.filter(|v| !v.is_empty()).cloned();
+
+    let mut i = 0;
+    while i < tokens.len() {
+        match tokens[i].as_str() {
+            "CNTP" => {
+                cp.account = opt(tokens.get(i + 1));
+                cp.bank_id = opt(tokens.get(i + 2));
+                cp.name = opt(tokens.get(i + 3));
+                i += 5;
+            }
+            "INN" => {
+                cp.tax_id = opt(tokens.get(i + 1));
+                i += 2;
+            }
+            "EREF" => {
+                info.end_to_end_id = opt(tokens.get(i + 1));
+                i += 2;
+            }
+            "MARF" => {
+                info.mandate_id = opt(tokens.get(i + 1));
+                i += 2;
+            }
+            "REMI" => match tokens.get(i + 1).map(String::as_str) {
+                // `/REMI/USTD//text/` and `/REMI/STRD/CUR/reference/`
+                Some("USTD") => {
+                    info.remittance = opt(tokens.get(i + 3));
+                    i += 4;
+                }
+                Some("STRD") => {
+                    info.creditor_reference = opt(tokens.get(i + 3));
+                    i += 4;
+                }
+                _ => i += 1,
+            },
+            "" => i += 1,
+            other => {
+                text.push(other);
+                i += 1;
+            }
+        }
+    }
+
+    if let Some(rf) = info.creditor_reference.as_deref().filter(|r| is_creditor_reference(r)) {
+        validate_creditor_reference(rf)?;
+    }
+    info.text = text.join("/");
+    info.counterparty = (!cp.is_empty()).then_some(cp);
+    Ok(info)
+}
+
+/// Escapes a value of a structured `:86:`: `/` would end the value and `?`
+/// starts an escape, so both are preceded by `?`.
+fn escape_86(value: &str) -> String {
+    value.replace('?', "??").replace('/', "?/")
+}
+
+/// Splits structured `:86:` text at the `/` not escaped by [`escape_86`] and unescapes the parts.
+fn split_86(s: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut token = String::new();
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '?' => token.push(chars.next_if(|&n| n == '/' || n == '?').unwrap_or(c)),
+            '/' => tokens.push(std::mem::take(&mut token)),
+            _ => token.push(c),
+        }
+    }
+    tokens.push(token);
+    tokens
+}
+
+/// Issuer of the German business transaction codes (GVC) in [`TransactionCode::proprietary`].
+const GVC_ISSUER: &str = "DK";
+
+/// Length of a German `?xx` subfield value.
+const GERMAN_SUBFIELD_WIDTH: usize = 27;
+
+/// SEPA keywords of the German purpose lines, in the order they are written.
+const SEPA_KEYWORDS: [&str; 12] = ["EREF", "KREF", "MREF", "CRED", "DEBT", "COAM", "OAMT", "ABWA", "ABWE", "IBAN", "BIC", "SVWZ"];
+
+/// Reads the German structured `:86:`: a three-digit business transaction code
+/// (GVC) followed by `?xx` subfields, e.g. `166?00GUTSCHRIFT?20EREF+...?32NAME`.
+///
+/// `?00` is the booking text, `?20`-`?29` and `?60`-`?63` the purpose lines
+/// with their SEPA keywords, `?30` the BLZ or BIC, `?31` the account or IBAN
+/// and `?32`-`?33` the counterparty name; other subfields are ignored.
+/// Returns `None` for any other `:86:`.
+fn parse_german_86(s: &str) -> Option<Info86> {
+    let s: String = s.chars().filter(|&c| c != '\n' && c != '\r').collect();
+    let gvc = s.get(..3).filter(|g| g.bytes().all(|c| c.is_ascii_digit()))?;
+    let is_subfield = |t: &str| t.len() >= 2 && t.as_bytes()[..2].iter().all(u8::is_ascii_digit);
+    let mut tokens = s[3..].strip_prefix('?')?.split('?');
+    let first = tokens.next().filter(|t| is_subfield(t))?;
+
+    let mut subfields: Vec<(u8, String)> = Vec::new();
+    for token in std::iter::once(first).chain(tokens) {
+        match subfields.last_mut() {
+            // A `?` inside a value.
+            Some((_, value)) if !is_subfield(token) => {
+                value.push('?');
+                value.push_str(token);
+            }
+            _ => subfields.push((token[..2].parse().ok()?, token[2..].to_string())),
+        }
+    }
 
-    let amount_str = &s[11..i];
-    let start_code = i;
+    let mut info = Info86 { gvc: Some(gvc.to_string()), ..Default::default() };
+    let mut cp = Counterparty::default();
+    let mut purpose = String::new();
+    let mut name = String::new();
+    let opt = |v: &str| (!v.trim().is_empty()).then(|| v.trim().to_string());
+    for (code, value) in &subfields {
+        match code {
+            0 => info.text = value.trim().to_string(),
+            20..=29 | 60..=63 => purpose.push_str(value),
+            30 => cp.bank_id = opt(value),
+            31 => cp.account = opt(value),
+            32 | 33 => name.push_str(value),
+            _ => {}
+        }
+    }
+    cp.name = opt(&name);
+    info.counterparty = (!cp.is_empty()).then_some(cp);
 
-    while i < s.len() && bytes[i].is_ascii_alphabetic() {
-        i += 1;
+    // The purpose text comes first; keywords the model has no field for follow as given.
+    let (mut text, mut rest) = (Vec::new(), Vec::new());
+    for (keyword, value) in split_sepa_purpose(&purpose) {
+        let value = value.trim();
+        match keyword {
+            Some("EREF") => info.end_to_end_id = opt(value).filter(|v| v != "NOTPROVIDED"),
+            Some("KREF") => info.customer_reference = opt(value).filter(|v| v != "NONREF"),
+            Some("MREF") => info.mandate_id = opt(value),
+            Some("CRED") => info.creditor_id = opt(value),
+            Some("SVWZ") | None => text.push(value.to_string()),
+            Some(other) => rest.push(format!("{other}+{value}")),
+        }
     }
+    text.extend(rest);
+    info.remittance = opt(&text.join(" "));
+    Some(info)
+}
 
-    if i == start_code {
-        return Err(format!(":61: type code missing in `{s}`"));
+/// Splits German purpose text at its SEPA keywords (`EREF+`, `SVWZ+`, ...);
+/// text before the first keyword has none.
+fn split_sepa_purpose(purpose: &str) -> Vec<(Option<&'static str>, &str)> {
+    let mut starts: Vec<(usize, &'static str)> = SEPA_KEYWORDS.iter()
+        .flat_map(|&k| purpose.match_indices(&format!("{k}+")).map(move |(i, _)| (i, k)).collect::<Vec<_>>())
+        .collect();
+    starts.sort();
+    let mut parts = Vec::new();
+    let first = starts.first().map_or(purpose.len(), |&(i, _)| i);
+    if !purpose[..first].trim().is_empty() {
+        parts.push((None, &purpose[..first]));
+    }
+    for (n, &(i, keyword)) in starts.iter().enumerate() {
+        let end = starts.get(n + 1).map_or(purpose.len(), |&(j, _)| j);
+        parts.push((Some(keyword), &purpose[(i + keyword.len() + 1).min(end)..end]));
     }
+    parts
+}
 
-    let type_code = &s[start_code..i];
-    let reference = s[i..].to_string();
+/// Renders the German structured `:86:` read by [`parse_german_86`], as lines
+/// that break only before a subfield. Purpose lines that do not fit into the
+/// six lines of the field are left out.
+fn german_86_lines(entry: &Entry) -> Vec<String> {
+    let clean = |s: &str| s.replace(['?', '\n', '\r'], " ");
+    let chunks = |s: &str| -> Vec<String> {
+        let chars: Vec<char> = clean(s).chars().collect();
+        chars.chunks(GERMAN_SUBFIELD_WIDTH).map(|c| c.iter().collect()).collect()
+    };
+
+    let mut purpose = Vec::new();
+    let mut push = |keyword: &str, value: Option<&str>| {
+        if let Some(value) = value.filter(|v| !v.is_empty()) {
+            purpose.extend(chunks(&format!("{keyword}+{value}")));
+        }
+    };
+    push("EREF", entry.end_to_end_id.as_deref());
+    push("KREF", entry.reference.as_deref().filter(|r| *r != "NONREF"));
+    push("MREF", entry.mandate_id.as_deref());
+    push("CRED", entry.creditor_id.as_deref());
+    let remittance = entry.remittance_text().unwrap_or_else(|| entry.description.clone());
+    push("SVWZ", Some(&remittance));
+
+    let mut head = gvc_for(entry);
+    if !entry.description.is_empty() && entry.description != remittance {
+        head.push_str(&format!("?00{}", chunks(&entry.description).swap_remove(0)));
+    }
+    let mut tail = Vec::new();
+    if let Some(cp) = &entry.counterparty {
+        tail.extend(cp.bank_id.as_deref().map(|v| format!("?30{}", clean(v))));
+        tail.extend(cp.account.as_deref().map(|v| format!("?31{}", clean(v))));
+        let name = cp.name.as_deref().map(chunks).unwrap_or_default();
+        tail.extend(name.iter().take(2).zip(["?32", "?33"]).map(|(v, code)| format!("{code}{v}")));
+    }
 
-    Ok((
-        Transaction {
-            date_yyymmdd: date.to_string(),
-            entry_mmdd: entry.to_string(),
-            is_credit,
-            amount:amount_str.to_string(),
-            type_code: type_code.to_string(),
-            reference,
-            description: String::new(),
-        },
-        (),
-    ))
+    let codes = (20..=29).chain(60..=63);
+    let mut purpose: Vec<String> = purpose.iter().zip(codes).map(|(v, code)| format!("?{code}{v}")).collect();
+    loop {
+        let lines = pack_lines(std::iter::once(&head).chain(&purpose).chain(&tail));
+        if lines.len() <= INFO_86_LINES || purpose.is_empty() {
+            return lines;
+        }
+        purpose.pop();
+    }
 }
 
+/// Packs subfields into lines of at most 65 characters, breaking only between subfields.
+fn pack_lines<'a>(subfields: impl Iterator<Item = &'a String>) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for subfield in subfields {
+        match lines.last_mut() {
+            Some(line) if line.chars().count() + subfield.chars().count() <= INFO_86_WIDTH => line.push_str(subfield),
+            _ => lines.push(subfield.clone()),
+        }
+    }
+    lines.iter_mut().skip(1).for_each(|l| *l = continuation(l));
+    lines
+}
+
+/// The German business transaction code of an entry: as read, or a SEPA
+/// transfer or direct debit code for its direction.
+fn gvc_for(entry: &Entry) -> String {
+    let code = entry.transaction_code.as_ref();
+    if let Some(tc) = code
+        && tc.proprietary_issuer.as_deref() == Some(GVC_ISSUER)
+        && let Some(gvc) = tc.proprietary.as_ref().filter(|g| g.len() == 3 && g.bytes().all(|c| c.is_ascii_digit()))
+    {
+        return gvc.clone();
+    }
+    let direct_debit = code.is_some_and(|tc| tc.swift_code().ends_with("DDT"));
+    match (direct_debit, entry.kind) {
+        (true, DebitCredit::Debit) => "105",
+        (true, DebitCredit::Credit) => "171",
+        (false, DebitCredit::Debit) => "116",
+        (false, DebitCredit::Credit) => "166",
+    }
+    .to_string()
+}
+
+/// Renders the `:86:` field; parties and references use the structured form read by [`parse_info_86`].
+fn info_86_to_str(entry: &Entry) -> String {
+    let cp = entry.counterparty.as_ref().filter(|cp| !cp.is_empty());
+    let structured = cp.is_some()
+        || entry.end_to_end_id.is_some()
+        || entry.mandate_id.is_some()
+        || entry.creditor_reference.is_some()
+        || !entry.remittance_info.is_empty();
+    if !structured {
+        return entry.description.clone();
+    }
+
+    let v = |f: &Option<String>| f.as_deref().map(escape_86).unwrap_or_default();
+    let mut out = String::from("/");
+    if let Some(cp) = cp {
+        out.push_str(&format!("CNTP/{}/{}/{}//", v(&cp.account), v(&cp.bank_id), v(&cp.name)));
+        if let Some(inn) = &cp.tax_id {
+            out.push_str(&format!("INN/{}/", escape_86(inn)));
+        }
+    }
+    if let Some(eref) = &entry.end_to_end_id {
+        out.push_str(&format!("EREF/{}/", escape_86(eref)));
+    }
+    if let Some(mandate) = &entry.mandate_id {
+        out.push_str(&format!("MARF/{}/", escape_86(mandate)));
+    }
+    if let Some(rf) = &entry.creditor_reference {
+        out.push_str(&format!("REMI/STRD/CUR/{}/", escape_86(rf)));
+    }
+    let remittance = entry.remittance_text().unwrap_or_else(|| entry.description.clone());
+    if !remittance.is_empty() {
+        out.push_str(&format!("REMI/USTD//{}/", escape_86(&remittance)));
+    }
+    out
+}
 
 impl From<bool> for DebitCredit {
     fn from(is_credit: bool) -> Self {
@@ -229,20 +1445,44 @@ impl From<bool> for DebitCredit {
 impl From<(&Transaction, &str)> for Entry {
     fn from((tx, currency): (&Transaction, &str)) -> Self {
         Entry {
-            booking_date: compose_booking_date(&tx.date_yyymmdd, &tx.entry_mmdd),
-            value_date: tx.date_yyymmdd.clone(),
-            amount: format!("{:.2}", tx.amount),
+            booking_date: tx.booking_date,
+            value_date: tx.value_date,
+            amount: tx.amount,
             currency: currency.to_string(),
+            funds_code: tx.funds_code,
             kind: DebitCredit::from(tx.is_credit),
-            description: format!("{} type:{}",tx.description.clone(),tx.type_code.as_str()) ,
-            reference: Some(tx.reference.clone()),
+            status: EntryStatus::Booked,
+            reversal: tx.reversal,
+            description: if tx.info.text.is_empty() { tx.info.remittance.clone().unwrap_or_default() } else { tx.info.text.clone() },
+            reference: match &tx.info.customer_reference {
+                Some(kref) if tx.reference == "NONREF" => Some(kref.clone()),
+                _ => Some(tx.reference.clone()).filter(|r| !r.is_empty()),
+            },
+            remittance_info: tx.info.remittance.iter().cloned().collect(),
+            creditor_reference: tx.info.creditor_reference.clone(),
+            end_to_end_id: tx.info.end_to_end_id.clone(),
+            mandate_id: tx.info.mandate_id.clone(),
+            creditor_id: tx.info.creditor_id.clone(),
+            servicer_reference: tx.bank_reference.clone(),
+            supplementary_details: tx.supplementary.clone(),
+            instructed_amount: tx.original_amount.clone(),
+            charges: tx.charges.iter().map(|amount| Charge { amount: amount.clone(), code: None }).collect(),
+            counterparty: tx.info.counterparty.clone(),
+            own_party: None,
+            transaction_code: Some(TransactionCode {
+                proprietary: tx.info.gvc.clone(),
+                proprietary_issuer: tx.info.gvc.as_ref().map(|_| GVC_ISSUER.to_string()),
+                ..TransactionCode::from_swift(&tx.type_code, DebitCredit::from(tx.is_credit))
+            }),
+            extensions: tx.extensions.clone(),
+            ..Default::default()
         }
     }
 }
 
 impl From<&MT940Statement> for Statement {
     fn from(s: &MT940Statement) -> Self {
-        let currency = s.opening_balance.currency.clone();
+        let currency = s.opening_balance.1.currency.clone();
         let entries = s
             .transactions
             .iter()
@@ -251,20 +1491,29 @@ impl From<&MT940Statement> for Statement {
 
         Statement {
             id: s.reference.clone(),
+            related_reference: s.related_reference.clone(),
+            electronic_sequence: s.statement_number,
+            page_number: s.sequence_number,
+            created_at: s.created_at,
             account_id: s.account_id.clone(),
-            opening_balance: Some(StBalance{
-                kind: DebitCredit::from(s.closing_balance.credit),
-                date_yyymmdd: s.opening_balance.date_yyymmdd.clone(),
-                currency: s.opening_balance.currency.clone(),
-                amount: s.opening_balance.amount.clone(),
-            }),
-            closing_balance: Some(StBalance{
-                kind: DebitCredit::from(s.closing_balance.credit),
-                date_yyymmdd: s.closing_balance.date_yyymmdd.clone(),
-                currency: s.closing_balance.currency.clone(),
-                amount: s.closing_balance.amount.clone(),
-            }),
+            account_currency: Some(currency),
+            servicer_bic: s.servicer_bic.clone(),
+            envelope: s.envelope.clone(),
+            balances: std::iter::once((s.opening_balance.0, &s.opening_balance.1))
+                .chain(std::iter::once((s.closing_balance.0, &s.closing_balance.1)))
+                .chain(s.closing_available.iter().map(|b| (BalanceType::ClosingAvailable, b)))
+                .chain(s.forward_available.iter().map(|b| (BalanceType::ForwardAvailable, b)))
+                .map(|(balance_type, b)| StBalance {
+                    balance_type,
+                    kind: DebitCredit::from(b.credit),
+                    date: b.date,
+                    currency: b.currency.clone(),
+                    amount: b.amount,
+                })
+                .collect(),
             entries,
+            extensions: s.extensions.clone(),
+            ..Default::default()
         }
     }
 }
@@ -275,48 +1524,893 @@ impl From<MT940Statement> for Statement {
     }
 }
 
-fn compose_booking_date(value_date_yyyymmdd: &str, entry_mmdd: &str) -> String {
-    let year = &value_date_yyyymmdd[0..4];
-    format!("{year}{entry_mmdd}")
+/// Resolves the `:61:` entry date (MMDD) against the value date, handling the
+/// December/January rollover.
+fn compose_booking_date(value_date: &Date, entry_mmdd: &str) -> Result<Date, String> {
+    value_date.resolve_mmdd(entry_mmdd)
 }
 
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::adapter::adapter::Mode;
     #[test]
     fn test_parse_balance_field() {
         let s: &str = "C251001EUR1000,00";
-        let b = parse_balance_field(s).unwrap();
-        assert_eq!(b.credit, true);
-        assert_eq!(b.date_yyymmdd, "251001");
+        let b = parse_balance_field(PREFIX_OPN_BALANCE, s).unwrap();
+        assert!(b.credit);
+        assert_eq!(b.date, Date::new(2025, 10, 1).unwrap());
         assert_eq!(b.currency, "EUR");
-        assert_eq!(b.amount, "1000,00");
+        assert_eq!(b.amount, Money::new(100000, 2));
 
         let s: &str = "D261001RUB2000,10";
-        let b = parse_balance_field(s).unwrap();
-        assert_eq!(b.credit, false);
-        assert_eq!(b.date_yyymmdd, "261001");
+        let b = parse_balance_field(PREFIX_OPN_BALANCE, s).unwrap();
+        assert!(!b.credit);
+        assert_eq!(b.date, Date::new(2026, 10, 1).unwrap());
         assert_eq!(b.currency, "RUB");
-        assert_eq!(b.amount, "2000,10");
+        assert_eq!(b.amount, Money::new(200010, 2));
+    }
+
+    #[test]
+    fn test_non_ascii_balances_are_errors() {
+        for value in ["Ä51001EUR10,00", "C251001EU€10,00"] {
+            let err = parse_balance_field(PREFIX_OPN_BALANCE, value).unwrap_err();
+            assert!(matches!(err, AdapterError::InvalidField { ref field, .. } if field == PREFIX_OPN_BALANCE), "{err}");
+        }
+        let input = ":20:ST1\n:25:ACC1\n:60F:C251001EU€10,00\n:62F:C251001EUR10,00\n";
+        let err = Mt940::read_from(input.as_bytes()).unwrap_err();
+        assert_eq!(err.location(), Some(&Location::Line { line: 3, column: 6 }));
     }
 
     #[test]
     fn test_compose_booking_date(){
-        let s: &str = "202051207";
-        let b = compose_booking_date(s, "1115");
-        assert_eq!(b, "20201115");
+        let value = Date::new(2020, 12, 7).unwrap();
+        let b = compose_booking_date(&value, "1115").unwrap();
+        assert_eq!(b, Date::new(2020, 11, 15).unwrap());
+
+        let value = Date::new(2025, 12, 31).unwrap();
+        let b = compose_booking_date(&value, "0102").unwrap();
+        assert_eq!(b, Date::new(2026, 1, 2).unwrap());
+    }
+
+    #[test]
+    fn test_read_all_splits_messages() {
+        let input = ":20:ST1
+:25:ACC1
+:60F:C251001EUR1000,00
+:61:2510011001C100,00NTRFNONREF
+:86:first
+:62F:C251001EUR1100,00
+:20:ST2
+:25:ACC2
+:60F:C251002USD50,00
+:62F:C251002USD50,00
+";
+        let all = Mt940::read_all(input.as_bytes()).unwrap();
+        assert_eq!(all.len(), 2);
+        assert_eq!(all[0].id, "ST1");
+        assert_eq!(all[0].account_id, "ACC1");
+        assert_eq!(all[0].entries.len(), 1);
+        assert_eq!(all[1].id, "ST2");
+        assert_eq!(all[1].account_id, "ACC2");
+        assert!(all[1].entries.is_empty());
+
+        let mut out = Vec::new();
+        Mt940::write_all(&mut out, &all).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains(":20:ST1\n:25:ACC1"));
+        assert!(out.contains(":20:ST2\n:25:ACC2"));
+    }
+
+    #[test]
+    fn test_statement_metadata_round_trip() {
+        let input = ":20:ST1
+:21:REL-7
+:25:DEUTDEFFXXX/DE89370400440532013000
+:28C:00123/2
+:13D:2510011015+0200
+:60F:C251001EUR1000,00
+:62F:C251001EUR1000,00
+";
+        let st = Mt940::read_from(input.as_bytes()).unwrap();
+        assert_eq!(st.related_reference.as_deref(), Some("REL-7"));
+        assert_eq!(st.servicer_bic.as_deref(), Some("DEUTDEFFXXX"));
+        assert_eq!(st.account_id, "DE89370400440532013000");
+        assert_eq!(st.electronic_sequence, Some(123));
+        assert_eq!(st.page_number, Some(2));
+        assert_eq!(st.account_currency.as_deref(), Some("EUR"));
+        assert_eq!(st.created_at.unwrap().format_13d(), "2510011015+0200");
+
+        let mut out = Vec::new();
+        Mt940::write_to(&mut out, &st).unwrap();
+        let back = Mt940::read_from(out.as_slice()).unwrap();
+        assert_eq!(back, st);
+    }
+
+    #[test]
+    fn test_all_balance_types_round_trip() {
+        let input = ":20:ST1
+:25:ACC1
+:60M:D251001EUR10,00
+:61:2510011001C100,00NTRFNONREF
+:62M:C251001EUR90,00
+:64:C251001EUR80,00
+:65:C251002EUR85,00
+:65:C251003EUR95,00
+";
+        let st = Mt940::read_from(input.as_bytes()).unwrap();
+        let types: Vec<_> = st.balances.iter().map(|b| b.balance_type).collect();
+        assert_eq!(types, [
+            BalanceType::IntermediateOpening,
+            BalanceType::IntermediateClosing,
+            BalanceType::ClosingAvailable,
+            BalanceType::ForwardAvailable,
+            BalanceType::ForwardAvailable,
+        ]);
+        assert_eq!(st.opening_balance().unwrap().kind, DebitCredit::Debit);
+        assert_eq!(st.closing_balance().unwrap().kind, DebitCredit::Credit);
+
+        let mut out = Vec::new();
+        Mt940::write_to(&mut out, &st).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        for tag in [":60M:D251001EUR10,00", ":62M:C251001EUR90,00", ":64:C251001EUR80,00", ":65:C251003EUR95,00"] {
+            assert!(out.contains(tag), "{tag} missing in {out}");
+        }
+    }
+
+    #[test]
+    fn test_amounts_follow_currency_decimals() {
+        let input = ":20:ST1
+:25:ACC1
+:60F:C251001RUR1000,5
+:62F:C251001RUR1000,50
+";
+        let mut st = Mt940::read_from(input.as_bytes()).unwrap();
+        assert_eq!(st.balances[0].currency, "RUB");
+
+        st.balances[0].currency = "JPY".into();
+        st.balances[0].amount = Money::parse("1000.00").unwrap();
+        st.balances[1].currency = "KWD".into();
+        let mut out = Vec::new();
+        Mt940::write_to(&mut out, &st).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains(":60F:C251001JPY1000,\n"), "{out}");
+        assert!(out.contains(":62F:C251001KWD1000,500\n"), "{out}");
+
+        let strict = ReadOptions { mode: Mode::Strict, ..Default::default() };
+        assert!(Mt940::read_with(out.replace("KWD", "ABC").as_bytes(), &strict).is_err());
+        assert!(Mt940::read_with(out.as_bytes(), &strict).is_ok());
+    }
+
+    #[test]
+    fn test_transliterated_round_trip() {
+        let input = ":20:ST1
+:25:40702810600014448120
+:60F:C251001RUB1000,00
+:61:2510011001D100,00NTRFNONREF
+:86:/CNTP/40702810900000000001/044525225/ООО «Ромашка»///EREF/E2E-1//REMI/USTD//Оплата по счёту №5 (Invoice 5)/
+:62F:C251001RUB900,00
+";
+        let st = Mt940::read_from(input.as_bytes()).unwrap();
+        let mut out = Vec::new();
+        Mt940::write_with(&mut out, std::slice::from_ref(&st), &WriteOptions { transliterate: true, ..Default::default() }).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.chars().all(crate::format::swift::is_charset_x), "{out}");
+        assert!(out.contains("/OOO ''ROMAQKA''/"), "{out}");
+        assert!(out.contains("/OPLATA PO ScoTU 'N5 (Invoice 5)'/"), "{out}");
+
+        let options = ReadOptions { transliterated: true, ..Default::default() };
+        let back = Mt940::read_with(out.as_bytes(), &options).unwrap().statements.remove(0);
+        let entry = &back.entries[0];
+        assert_eq!(entry.counterparty.as_ref().unwrap().name.as_deref(), Some("ООО 'РОМАШКА'"));
+        assert_eq!(entry.remittance_info, ["ОПЛАТА ПО СЧЁТУ N5 (Invoice 5)"]);
+        assert_eq!(entry.end_to_end_id.as_deref(), Some("E2E-1"));
+    }
+
+    #[test]
+    fn test_errors_point_at_the_field() {
+        let input = ":20:ST1\n:25:ACC1\n:60F:C25x001EUR10,00\n:62F:C251001EUR10,00\n";
+        let err = Mt940::read_all(input.as_bytes()).unwrap_err();
+        assert!(matches!(&err, AdapterError::InvalidField { field, value, .. } if field == ":60F:" && value == "C25x001EUR10,00"), "{err}");
+        assert_eq!(err.location(), Some(&Location::Line { line: 3, column: 6 }));
+
+        let err = Mt940::read_all(":20:ST1\n:25:ACC1\n:60F:C251001EUR10,00\n".as_bytes()).unwrap_err();
+        assert_eq!(err.to_string(), "missing :62a: at line 1, column 1");
+    }
+
+    #[test]
+    fn test_lenient_mode_quarantines_bad_records() {
+        let input = ":20:ST1
+:25:ACC1
+:60F:C251001EUR10,00
+:61:2510011001C5,00NTRFREF1
+:86:first
+:61:2510321001C7,00NTRFREF2
+:86:bad date
+:61:2510011001C3,00NTRFREF3
+:62F:C251001EUR18,00
+:20:ST2
+:25:ACC2
+:60F:C251001EUR10,00
+";
+        let err = Mt940::read_all(input.as_bytes()).unwrap_err();
+        assert_eq!(err.location(), Some(&Location::Line { line: 6, column: 5 }));
+
+        let lenient = ReadOptions { mode: Mode::Lenient, ..Default::default() };
+        let report = Mt940::read_with(input.as_bytes(), &lenient).unwrap();
+        assert_eq!(report.statements.len(), 1);
+        let refs: Vec<_> = report.statements[0].entries.iter().map(|e| e.reference.as_deref()).collect();
+        assert_eq!(refs, [Some("REF1"), Some("REF3")]);
+
+        let messages: Vec<_> = report.warnings.iter().map(|w| w.to_string()).collect();
+        assert_eq!(messages.len(), 3, "{messages:?}");
+        assert!(messages[0].starts_with("invalid :61: `2510321001C7,00NTRFREF2`"), "{messages:?}");
+        assert_eq!(messages[1], "missing :86: at line 8, column 1");
+        assert_eq!(messages[2], "missing :62a: at line 10, column 1");
+
+        assert_eq!(report.rejected.len(), 2);
+        assert_eq!(report.rejected[0].raw, ":61:2510321001C7,00NTRFREF2\n:86:bad date");
+        assert_eq!(report.rejected[0].location, Some(Location::Line { line: 6, column: 1 }));
+        assert!(report.rejected[1].raw.starts_with(":20:ST2\n"));
+
+        let strict = ReadOptions { mode: Mode::Strict, ..Default::default() };
+        let fixed = input.replace("2510321001", "2510011001");
+        let err = Mt940::read_with(fixed.as_bytes(), &strict).unwrap_err();
+        assert_eq!(err.to_string(), "missing :86: at line 8, column 1");
+    }
+
+    #[test]
+    fn test_fields_with_continuation_lines_in_any_order() {
+        let input = ":25:ACC1
+:20:ST1
+:60F:C251001EUR10,00
+:13D:2510011200+0100
+:61:2510011001C5,00NTRFREF1
+/OCMT/USD6,00/
+:86:/EREF/E2E-1//REMI/USTD//Invoice 42 for
+ October/
+:86:/CNTP/DE89370400440532013000/COBADEFFXXX/ACME GmbH///
+:61:2510011001D2,00NCHGREF2
+:86:Fee for
+the card
+:62F:C251001EUR13,00
+-
+:25:ACC2
+:20:ST2
+:60F:C251001EUR1,00
+:62F:C251001EUR1,00
+";
+        let all = Mt940::read_all(input.as_bytes()).unwrap();
+        assert_eq!(all.len(), 2);
+        assert_eq!((all[0].id.as_str(), all[0].account_id.as_str()), ("ST1", "ACC1"));
+        assert!(all[0].created_at.is_some());
+        assert_eq!((all[1].id.as_str(), all[1].account_id.as_str()), ("ST2", "ACC2"));
+
+        let e = &all[0].entries[0];
+        assert!(e.instructed_amount.is_some());
+        assert_eq!(e.end_to_end_id.as_deref(), Some("E2E-1"));
+        assert_eq!(e.remittance_info, ["Invoice 42 for October"]);
+        assert_eq!(e.counterparty.as_ref().and_then(|cp| cp.name.as_deref()), Some("ACME GmbH"));
+        assert_eq!(all[0].entries[1].description, "Fee for\nthe card");
+
+        // A repeated `:20:` starts the next message without a terminator.
+        let two = input.replace("-\n:25:ACC2\n:20:ST2", ":20:ST2\n:25:ACC2");
+        assert_eq!(Mt940::read_all(two.as_bytes()).unwrap()[1].account_id, "ACC2");
+    }
+
+    #[test]
+    fn test_long_descriptions_are_wrapped() {
+        let long = "Payment for services rendered under contract 2025-117 ".repeat(3);
+        let input = ":20:ST1\n:25:ACC1\n:60F:C251001EUR10,00\n:61:2510011001C5,00NTRFREF1\n:86:x\n:61:2510011001C5,00NTRFREF2\n:86:x\n:62F:C251001EUR20,00\n";
+        let mut st = Mt940::read_from(input.as_bytes()).unwrap();
+        st.entries[0].description = long.trim().to_string();
+        st.entries[1].description = "Two\nlines".to_string();
+        let mut out = Vec::new();
+        Mt940::write_to(&mut out, &st).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.lines().all(|l| l.chars().count() <= 4 + INFO_86_WIDTH), "{out}");
+        assert!(out.contains(":86:Two\nlines\n"), "{out}");
+
+        let back = Mt940::read_from(out.as_bytes()).unwrap();
+        assert_eq!(back.entries[0].description, long.trim());
+        assert_eq!(back.entries[1].description, "Two\nlines");
+
+        assert_eq!(wrap_86(&"x".repeat(500)).len(), INFO_86_LINES);
+    }
+
+    #[test]
+    fn test_wrapping_at_a_space_keeps_it() {
+        let input = ":20:ST1\n:25:ACC1\n:60F:C251001EUR10,00\n:61:2510011001C5,00NTRFREF1\n:86:x\n:62F:C251001EUR15,00\n";
+        let mut st = Mt940::read_from(input.as_bytes()).unwrap();
+        let text = format!("{} tail words here", "a".repeat(INFO_86_WIDTH - 1));
+        st.entries[0].description = text.clone();
+        let mut out = Vec::new();
+        Mt940::write_to(&mut out, &st).unwrap();
+        assert_eq!(Mt940::read_from(out.as_slice()).unwrap().entries[0].description, text);
+
+        // In structured text the words would run together.
+        let remittance = format!("{} тарифа для", "b".repeat(INFO_86_WIDTH - 20));
+        st.entries[0].remittance_info = vec![remittance.clone()];
+        let mut out = Vec::new();
+        Mt940::write_to(&mut out, &st).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("тарифа \nдля/"), "{out}");
+        assert_eq!(Mt940::read_from(out.as_bytes()).unwrap().entries[0].remittance_info, [remittance]);
+    }
+
+    #[test]
+    fn test_wrapped_lines_do_not_start_fields() {
+        let input = ":20:ST1\n:25:ACC1\n:60F:C251001EUR10,00\n:61:2510011001C5,00NTRFREF1\n:86:x\n:62F:C251001EUR15,00\n";
+        let mut st = Mt940::read_from(input.as_bytes()).unwrap();
+        st.entries[0].description = format!("{}:62F:D251031EUR5,00", "x".repeat(INFO_86_WIDTH));
+        st.entries[0].supplementary_details = Some("-}{1:F01".to_string());
+        let mut out = Vec::new();
+        Mt940::write_to(&mut out, &st).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains(&format!(":86:{}\n62F:D251031EUR5,00\n", "x".repeat(INFO_86_WIDTH))), "{out}");
+        assert!(out.contains("\n}{1:F01\n"), "{out}");
+
+        let all = Mt940::read_all(out.as_bytes()).unwrap();
+        assert_eq!(all.len(), 1);
+        assert_eq!(all[0].balances, st.balances);
+        assert_eq!(all[0].entries[0].description, format!("{}62F:D251031EUR5,00", "x".repeat(INFO_86_WIDTH)));
+        assert_eq!(pack_lines(["?20".to_string() + &"a".repeat(60), ":21b".to_string()].iter()), ["?20".to_string() + &"a".repeat(60), "21b".to_string()]);
+    }
+
+    #[test]
+    fn test_written_messages_follow_the_spec() {
+        let input = ":20:ST1\n:25:ACC1\n:60F:C251001EUR10,00\n:61:2510011001C5,00NTRFREF1\n:86:x\n:62F:C251001EUR15,00\n";
+        let mut st = Mt940::read_from(input.as_bytes()).unwrap();
+        st.id = "/STATEMENT//2025-10-01/LONG".to_string();
+        st.entries[0].reference = Some("CUSTOMER-REFERENCE-0001".to_string());
+        st.entries[0].servicer_reference = Some("BANK-REFERENCE-0001".to_string());
+        st.entries[0].supplementary_details = Some("Supplementary details beyond thirty-four".to_string());
+        st.extensions.push(format!("{EXT_PREFIX}NS"), "y".repeat(100));
+
+        let mut out = Vec::new();
+        Mt940::write_with(&mut out, &[st], &WriteOptions { crlf: true, ..Default::default() }).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.split_terminator("\r\n").all(|l| !l.contains('\n')), "{out}");
+        assert!(out.lines().all(|l| l.chars().count() <= 5 + LINE_WIDTH), "{out}");
+        assert!(out.starts_with(":20:STATEMENT/2025-1\r\n:25:ACC1\r\n:28C:1\r\n"), "{out}");
+        assert!(out.contains(":61:2510011001C5,00NTRFCUSTOMER-REFEREN//BANK-REFERENCE-0\r\nSupplementary details beyond thirt\r\n"), "{out}");
+        assert!(out.ends_with(&format!("{}\r\n-\r\n", "y".repeat(35))), "{out}");
+
+        let back = Mt940::read_from(out.as_bytes()).unwrap();
+        assert_eq!(back.id, "STATEMENT/2025-1");
+        assert_eq!(back.electronic_sequence, Some(1));
+        assert_eq!(back.entries[0].reference.as_deref(), Some("CUSTOMER-REFEREN"));
+        assert_eq!(back.entries[0].amount, Money::new(500, 2));
+        assert_eq!(swift_reference("//"), "NONREF");
+    }
+
+    #[test]
+    fn test_missing_balances_are_derived_on_request() {
+        let input = ":20:ST1\n:25:ACC1\n:60F:C251001EUR10,00\n:61:2510031003D25,00NTRFREF1\n:86:x\n:62F:D251003EUR15,00\n";
+        let st = Mt940::read_from(input.as_bytes()).unwrap();
+        let derive = WriteOptions { derive_balances: true, ..Default::default() };
+        let written = |st: &Statement| {
+            let mut out = Vec::new();
+            Mt940::write_with(&mut out, std::slice::from_ref(st), &derive).unwrap();
+            Mt940::read_from(out.as_slice()).unwrap().balances
+        };
+
+        let mut no_closing = st.clone();
+        no_closing.balances.retain(|b| b.balance_type.is_opening());
+        let err = Mt940::write_to(Vec::new(), &no_closing).unwrap_err();
+        assert!(matches!(err, AdapterError::MissingField { ref field, .. } if field == PREFIX_CLS_BALANCE), "{err:?}");
+        assert_eq!(written(&no_closing), st.balances);
+        let mut no_opening = st.clone();
+        no_opening.balances.retain(|b| b.balance_type.is_closing());
+        assert_eq!(written(&no_opening)[0], StBalance { date: st.entries[0].booking_date, ..st.balances[0].clone() });
+        let mut none = st.clone();
+        none.balances.clear();
+        assert!(matches!(Mt940::write_to(Vec::new(), &none), Err(AdapterError::MissingField { .. })));
+        assert_eq!(written(&none)[1].signed_amount(), Money::parse("-25").unwrap());
+
+        none.entries.clear();
+        assert!(matches!(Mt940::write_with(Vec::new(), &[none], &derive), Err(AdapterError::MissingField { .. })));
+    }
+
+    #[test]
+    fn test_pages_are_joined() {
+        let input = ":20:ST1
+:25:ACC1
+:28C:123/1
+:60F:C251001EUR100,00
+:61:2510011001C50,00NTRFREF1
+:86:first
+:62M:C251001EUR150,00
+-
+:20:ST1
+:25:ACC1
+:28C:123/2
+:60M:C251001EUR150,00
+:61:2510011001D20,00NTRFREF2
+:86:second
+:62F:C251001EUR130,00
+:64:C251001EUR130,00
+-
+:20:ST2
+:25:ACC1
+:28C:124/1
+:60F:C251002EUR130,00
+:62F:C251002EUR130,00
+-
+";
+        let all = Mt940::read_all(input.as_bytes()).unwrap();
+        assert_eq!(all.len(), 2);
+        let st = &all[0];
+        assert_eq!((st.electronic_sequence, st.page_number), (Some(123), None));
+        assert_eq!(st.entries.len(), 2);
+        assert_eq!(st.opening_balance().unwrap().balance_type, BalanceType::OpeningBooked);
+        assert_eq!(st.closing_balance().unwrap().balance_type, BalanceType::ClosingBooked);
+        assert!(st.balance(BalanceType::ClosingAvailable).is_some());
+
+        let broken = input.replace(":60M:C251001EUR150,00", ":60M:C251001EUR151,00");
+        let err = Mt940::read_all(broken.as_bytes()).unwrap_err();
+        assert_eq!(err.location(), Some(&Location::Line { line: 9, column: 1 }));
+        assert!(err.to_string().contains("page 2"), "{err}");
+
+        let report = Mt940::read_with(broken.as_bytes(), &ReadOptions { mode: Mode::Lenient, ..Default::default() }).unwrap();
+        assert_eq!(report.statements.len(), 2);
+        assert_eq!(report.rejected.len(), 1);
+
+        let missing = input.replacen(":28C:123/1", ":28C:122/1", 1);
+        let report = Mt940::read_with(missing.as_bytes(), &ReadOptions::default()).unwrap();
+        assert_eq!(report.statements.len(), 3);
+        assert_eq!(report.warnings.len(), 1);
+    }
+
+    #[test]
+    fn test_large_statements_are_paged() {
+        let mut input = String::from(":20:ST1\n:25:ACC1\n:28C:7\n:60F:C251001EUR100,00\n");
+        for i in 0..60 {
+            input.push_str(&format!(":61:2510011001D1,00NTRFREF{i}\n:86:Payment number {i} for services\n"));
+        }
+        input.push_str(":62F:D251001EUR40,00\n:64:D251001EUR40,00\n");
+        let st = Mt940::read_from(input.as_bytes()).unwrap();
+
+        let pages = messages(&st, MESSAGE_SIZE, &WriteOptions::default()).unwrap();
+        assert!(pages.len() > 1);
+        for (i, page) in pages.iter().enumerate() {
+            assert!(page.len() + page.lines().count() <= MESSAGE_SIZE, "{page}");
+            assert!(page.contains(&format!(":28C:7/{}\n", i + 1)), "{page}");
+            assert!(page.ends_with("\n-\n"), "{page}");
+        }
+        assert!(pages[0].contains(":60F:C251001EUR100,00\n"));
+        assert!(pages[1].contains(":60M:"));
+        assert!(pages[0].contains(":62M:"));
+        assert!(pages.last().unwrap().contains(":62F:D251001EUR40,00\n:64:"));
+
+        let mut out = Vec::new();
+        Mt940::write_to(&mut out, &st).unwrap();
+        assert_eq!(Mt940::read_all(out.as_slice()).unwrap(), vec![st.clone()]);
+
+        // Running balances that cannot be computed fail the write instead of panicking.
+        let mut huge = st;
+        huge.entries[0].amount = Money::parse("0.000000000000000001").unwrap();
+        assert!(matches!(messages(&huge, MESSAGE_SIZE, &WriteOptions::default()), Err(AdapterError::Write(_))));
+    }
+
+    #[test]
+    fn test_german_86() {
+        let input = ":20:ST1
+:25:10020030/1234567890
+:60F:C251001EUR100,00
+:61:2510011001C50,00NTRFNONREF//BANK-1
+:86:166?00SEPA-GUTSCHRIFT?109310?20EREF+E2E-4711?21KREF+KUNDE-1?22SVWZ+Rechnung 2025-117 vo
+?23m 30.09.2025?30COBADEFFXXX?31DE89370400440532013000?32Max Mustermann Handelsges
+?33ellschaft mbH?34000
+:61:2510011001D20,00NDDTNONREF
+:86:105?00SEPA-LASTSCHRIFT?20EREF+NOTPROVIDED?21MREF+M-1?22CRED+DE98ZZZ09999999999?23
+SVWZ+Beitrag? Oktober?32Verein e.V.
+:62F:C251001EUR130,00
+";
+        let st = Mt940::read_from(input.as_bytes()).unwrap();
+        let e = &st.entries[0];
+        assert_eq!(e.description, "SEPA-GUTSCHRIFT");
+        assert_eq!(e.reference.as_deref(), Some("KUNDE-1"));
+        assert_eq!(e.end_to_end_id.as_deref(), Some("E2E-4711"));
+        assert_eq!(e.remittance_info, vec!["Rechnung 2025-117 vom 30.09.2025"]);
+        let cp = e.counterparty.as_ref().unwrap();
+        assert_eq!(cp.bank_id.as_deref(), Some("COBADEFFXXX"));
+        assert_eq!(cp.account.as_deref(), Some("DE89370400440532013000"));
+        assert_eq!(cp.name.as_deref(), Some("Max Mustermann Handelsgesellschaft mbH"));
+        let tc = e.transaction_code.as_ref().unwrap();
+        assert_eq!((tc.proprietary.as_deref(), tc.proprietary_issuer.as_deref()), (Some("166"), Some(GVC_ISSUER)));
+        assert_eq!(tc.swift.as_deref(), Some("NTRF"));
+
+        let e = &st.entries[1];
+        assert_eq!(e.end_to_end_id, None);
+        assert_eq!(e.mandate_id.as_deref(), Some("M-1"));
+        assert_eq!(e.creditor_id.as_deref(), Some("DE98ZZZ09999999999"));
+        assert_eq!(e.remittance_info, vec!["Beitrag? Oktober"]);
+        assert_eq!(e.counterparty.as_ref().unwrap().name.as_deref(), Some("Verein e.V."));
+
+        let mut out = Vec::new();
+        Mt940::write_with(&mut out, std::slice::from_ref(&st), &WriteOptions { german: true, ..Default::default() }).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains(":86:166?00SEPA-GUTSCHRIFT?20EREF+E2E-4711?21KREF+KUNDE-1\n"), "{out}");
+        assert!(out.contains(":86:105?00SEPA-LASTSCHRIFT?20MREF+M-1?21CRED+DE98ZZZ09999999999"), "{out}");
+        assert!(out.lines().all(|l| l.chars().count() <= 4 + INFO_86_WIDTH), "{out}");
+        let back = Mt940::read_from(out.as_bytes()).unwrap();
+        assert_eq!(back.entries[0].remittance_info, st.entries[0].remittance_info);
+        assert_eq!(back.entries[1].remittance_info, vec!["Beitrag  Oktober"]);
+        assert_eq!(back.entries[0].counterparty, st.entries[0].counterparty);
+        assert_eq!(back.entries[1].creditor_id, st.entries[1].creditor_id);
+        assert_eq!(back.entries[0].transaction_code, st.entries[0].transaction_code);
+
+        // Without German data the code follows the direction and the lines stay within the field.
+        let mut plain = st.entries[1].clone();
+        plain.transaction_code = Some(TransactionCode::from_swift("NTRF", DebitCredit::Debit));
+        plain.description = "x".repeat(100);
+        plain.remittance_info = vec!["y".repeat(400)];
+        let lines = german_86_lines(&plain);
+        assert!(lines[0].starts_with("116?00xxx"), "{lines:?}");
+        assert!(lines.len() <= INFO_86_LINES && lines.last().unwrap().ends_with("?32Verein e.V."), "{lines:?}");
+
+        let info = parse_info_86("123?45 invoice").unwrap();
+        assert_eq!(info.gvc.as_deref(), Some("123"));
+        assert_eq!(parse_info_86("123/45 invoice").unwrap().text, "123/45 invoice");
+    }
+
+    #[test]
+    fn test_fin_envelope() {
+        let input = "{1:F01BANKDEFFAXXX0001000123}{2:O9401200251001COBADEFFAXXX00010001232510011200N}{3:{108:MUR-1}}{4:
+:20:ST1
+:25:ACC1
+:60F:C251001EUR10,00
+:62F:C251001EUR10,00
+-}{5:{CHK:A1B2C3D4E5F6}}{1:F01BANKDEFFAXXX0001000124}{2:O9401200251001COBADEFFAXXX00010001242510011200N}{4:
+:20:ST2
+:25:ACC1
+:60F:C251001EUR10,00
+:62F:C251001EUR10,00
+-}{5:{CHK:FFFFFFFFFFFF}}
+";
+        let all = Mt940::read_all(input.as_bytes()).unwrap();
+        assert_eq!(all.len(), 2);
+        let envelope = all[0].envelope.as_ref().unwrap();
+        assert_eq!(envelope.sender.as_deref(), Some("COBADEFF"));
+        assert_eq!(envelope.receiver.as_deref(), Some("BANKDEFF"));
+        assert_eq!(envelope.message_type.as_deref(), Some("940"));
+        assert_eq!(envelope.mur.as_deref(), Some("MUR-1"));
+        assert_eq!(envelope.checksum.as_deref(), Some("A1B2C3D4E5F6"));
+        assert_eq!(all[1].envelope.as_ref().unwrap().checksum.as_deref(), Some("FFFFFFFFFFFF"));
+
+        let mut out = Vec::new();
+        Mt940::write_with(&mut out, &all, &WriteOptions { envelope: Some(FinEnvelope::default()), ..Default::default() }).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.starts_with("{1:F01COBADEFFAXXX0000000000}{2:I940BANKDEFFAXXXN}{3:{108:MUR-1}}{4:\n:20:ST1\n"), "{out}");
+        assert!(out.ends_with(":62F:C251001EUR10,00\n-}\n"), "{out}");
+        let back = Mt940::read_all(out.as_bytes()).unwrap();
+        assert_eq!(back[1].envelope, Some(FinEnvelope { checksum: None, ..all[1].envelope.clone().unwrap() }));
+
+        // Without the statement's envelope the caller supplies the receiver.
+        let mut plain = all[0].clone();
+        plain.envelope = None;
+        plain.servicer_bic = Some("COBADEFFXXX".into());
+        let options = WriteOptions { envelope: Some(FinEnvelope { receiver: Some("BANKDEFF".into()), ..Default::default() }), ..Default::default() };
+        let mut out = Vec::new();
+        Mt940::write_with(&mut out, &[plain.clone()], &options).unwrap();
+        assert!(String::from_utf8(out).unwrap().starts_with("{1:F01COBADEFFAXXX0000000000}{2:I940BANKDEFFAXXXN}{4:"));
+        let options = WriteOptions { envelope: Some(FinEnvelope::default()), ..Default::default() };
+        assert!(Mt940::write_with(Vec::new(), &[plain], &options).is_err());
+
+        let err = Mt940::read_all(input.replace("{2:O940", "{2:X940").as_bytes()).unwrap_err();
+        assert!(err.to_string().starts_with("invalid FIN header"), "{err}");
+        assert_eq!(err.location(), Some(&Location::Line { line: 1, column: 1 }));
+    }
+
+    #[test]
+    fn test_parse_account_25_keeps_blz_prefix() {
+        assert_eq!(parse_account_25("37040044/0532013000"), (None, "37040044/0532013000".to_string()));
+        assert_eq!(parse_account_25("DE89370400440532013000"), (None, "DE89370400440532013000".to_string()));
+    }
+
+    #[test]
+    fn test_invalid_account_is_reported() {
+        let input = ":20:ST1\n:25:DE88 3704 0044 0532 0130 00\n:28C:1\n:60F:C251001EUR10,00\n:62F:C251001EUR10,00\n";
+        let report = Mt940::read_with(input.as_bytes(), &ReadOptions::default()).unwrap();
+        assert_eq!(report.statements[0].account_id, "DE88 3704 0044 0532 0130 00");
+        let warnings: Vec<_> = report.warnings.iter().map(|w| w.to_string()).collect();
+        assert_eq!(warnings, ["invalid :25: `DE88 3704 0044 0532 0130 00`: IBAN `DE88 3704 0044 0532 0130 00` has wrong check digits at line 2, column 5"]);
+
+        let strict = ReadOptions { mode: Mode::Strict, ..Default::default() };
+        assert!(Mt940::read_with(input.as_bytes(), &strict).is_err());
+        assert!(Mt940::read_with(input.replace("DE88", "DE89").as_bytes(), &strict).is_ok());
+        let report = Mt940::read_with(input.replace("DE88 3704 0044 0532 0130 00", "COBAQQFF/123").as_bytes(), &ReadOptions::default()).unwrap();
+        assert_eq!(report.warnings.len(), 1);
+    }
+
+    #[test]
+    fn test_parse_account_25_normalizes_iban() {
+        let (bic, account) = parse_account_25("COBADEFFXXX/DE89 3704 0044 0532 0130 00");
+        assert_eq!(bic.as_deref(), Some("COBADEFFXXX"));
+        assert_eq!(account, "DE89370400440532013000");
+        assert_eq!(parse_account_25("ABCDQQFF/123").0, None);
+    }
+
+    #[test]
+    fn test_parse_info_86_structured() {
+        let info = parse_info_86("/CNTP/NL08ABNA0499999999/ABNANL2A/J DOE/AMSTERDAM/INN/7735602068/REMI/USTD//INVOICE 123/").unwrap();
+        let cp = info.counterparty.unwrap();
+        assert_eq!(info.text, "");
+        assert_eq!(info.remittance.as_deref(), Some("INVOICE 123"));
+        assert_eq!(cp.account.as_deref(), Some("NL08ABNA0499999999"));
+        assert_eq!(cp.bank_id.as_deref(), Some("ABNANL2A"));
+        assert_eq!(cp.name.as_deref(), Some("J DOE"));
+        assert_eq!(cp.tax_id.as_deref(), Some("7735602068"));
+
+        let info = parse_info_86("Salary October").unwrap();
+        assert_eq!(info.text, "Salary October");
+        assert!(info.counterparty.is_none());
+    }
+
+    #[test]
+    fn test_info_86_round_trip() {
+        let entry = Entry {
+            description: "INVOICE 123".to_string(),
+            counterparty: Some(Counterparty {
+                name: Some("J DOE".to_string()),
+                account: Some("NL08ABNA0499999999".to_string()),
+                bank_id: Some("ABNANL2A".to_string()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let info = parse_info_86(&info_86_to_str(&entry)).unwrap();
+        assert_eq!(info.remittance.as_ref(), Some(&entry.description));
+        assert_eq!(info.counterparty, entry.counterparty);
+    }
+
+    #[test]
+    fn test_slashes_in_counterparty_round_trip() {
+        let input = ":20:ST1\n:25:ACC1\n:60F:C251001EUR10,00\n:61:2510011001C5,00NTRFREF1\n:86:x\n:62F:C251001EUR15,00\n";
+        let mut st = Mt940::read_from(input.as_bytes()).unwrap();
+        st.entries[0].counterparty = Some(Counterparty {
+            name: Some("A/S Foo//Bar? Ltd".to_string()),
+            account: Some("DK5000400440116243".to_string()),
+            tax_id: Some("12/34".to_string()),
+            ..Default::default()
+        });
+        let mut out = Vec::new();
+        Mt940::write_to(&mut out, &st).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("/A?/S Foo?/?/Bar?? Ltd//INN/12?/34/"), "{out}");
+
+        let back = Mt940::read_from(out.as_bytes()).unwrap();
+        assert_eq!(back.entries[0].counterparty, st.entries[0].counterparty);
+        assert_eq!(split_86("a?/b?c??/"), ["a/b?c?", ""]);
+    }
+
+    #[test]
+    fn test_slashes_in_references_round_trip() {
+        let input = ":20:ST1\n:25:ACC1\n:60F:C251001EUR10,00\n:61:2510011001C5,00NTRFREF1\n:86:x\n:62F:C251001EUR15,00\n";
+        let mut st = Mt940::read_from(input.as_bytes()).unwrap();
+        st.entries[0].description = "Invoice 12/2025 paid".to_string();
+        st.entries[0].remittance_info = vec![st.entries[0].description.clone()];
+        st.entries[0].end_to_end_id = Some("E2E/4446141-5263495/NIC-D".to_string());
+        st.entries[0].mandate_id = Some("M/1".to_string());
+        let mut out = Vec::new();
+        Mt940::write_to(&mut out, &st).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains(":86:/EREF/E2E?/4446141-5263495?/NIC-D/MARF/M?/1/REMI/USTD//Invoice 12\n?/2025 paid/\n"), "{out}");
+
+        let back = Mt940::read_from(out.as_bytes()).unwrap();
+        assert_eq!(back.entries[0].remittance_info, st.entries[0].remittance_info);
+        assert_eq!(back.entries[0].end_to_end_id, st.entries[0].end_to_end_id);
+        assert_eq!(back.entries[0].mandate_id, st.entries[0].mandate_id);
+        assert_eq!(back.entries[0].description, st.entries[0].description);
+    }
+
+    #[test]
+    fn test_reversal_marks() {
+        let tx = parse_transaction_61("2510011001RC5,00NTRFNONREF").unwrap();
+        assert!(!tx.is_credit);
+        assert!(tx.reversal);
+        assert_eq!(tx.amount, Money::new(500, 2));
+
+        let tx = parse_transaction_61("2510011001RD5,00NTRFNONREF").unwrap();
+        assert!(tx.is_credit);
+        assert!(tx.reversal);
+        assert!(parse_transaction_61("2510011001X5,00NTRFNONREF").is_err());
+
+        let entry = Entry { kind: DebitCredit::Debit, reversal: true, amount: Money::new(500, 2), ..Default::default() };
+        assert!(transaction_61_to_str(&entry).contains("RC5,00NMSC"));
+    }
+
+    #[test]
+    fn test_pending_entries_are_not_written() {
+        let st = Statement {
+            id: "ST1".to_string(),
+            account_id: "ACC1".to_string(),
+            entries: vec![
+                Entry { description: "booked".to_string(), ..Default::default() },
+                Entry { description: "pending".to_string(), status: EntryStatus::Pending, ..Default::default() },
+            ],
+            ..Default::default()
+        };
+        let mut out = Vec::new();
+        Mt940::write_with(&mut out, &[st], &WriteOptions { derive_balances: true, ..Default::default() }).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert_eq!(out.matches(":61:").count(), 1);
+        assert!(!out.contains("pending"));
+    }
+
+    #[test]
+    fn test_original_amount_and_charges() {
+        let input = ":20:ST1
+:25:ACC1
+:28C:1
+:60F:C251001EUR1000,00
+:61:2510011001C98,50NTRFNONREF
+/OCMT/USD110,00//CHGS/EUR1,50/
+:86:Transfer from US
+:62F:C251001EUR1098,50
+";
+        let st = Mt940::read_from(input.as_bytes()).unwrap();
+        let e = &st.entries[0];
+        assert_eq!(e.instructed_amount, Some(CurrencyAmount { amount: Money::new(11000, 2), currency: "USD".to_string() }));
+        assert_eq!(e.charges.len(), 1);
+        assert_eq!(e.charges[0].amount, CurrencyAmount { amount: Money::new(150, 2), currency: "EUR".to_string() });
+        assert_eq!(e.description, "Transfer from US");
+
+        let mut out = Vec::new();
+        Mt940::write_to(&mut out, &st).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("NTRFNONREF\n/OCMT/USD110,00//CHGS/EUR1,50/\n"), "{out}");
+        assert_eq!(Mt940::read_from(out.as_bytes()).unwrap(), st);
+    }
+
+    #[test]
+    fn test_unknown_tags_round_trip() {
+        let input = ":20:ST1
+:25:ACC1
+:28C:1/1
+:NS:22Header note
+:60F:C251001EUR1000,00
+:61:2510011001D25,00NCHGREF42
+:86:Fee
+:NS:01Bank specific
+continued here
+:62F:C251001EUR975,00
+:86:Statement info
+";
+        let st = Mt940::read_from(input.as_bytes()).unwrap();
+        assert_eq!(st.extensions.get("mt940:NS"), Some("22Header note"));
+        assert_eq!(st.extensions.get("mt940:86"), Some("Statement info"));
+        assert_eq!(st.entries[0].extensions.get("mt940:NS"), Some("01Bank specific\ncontinued here"));
+        assert_eq!(st.entries[0].description, "Fee");
+
+        let mut out = Vec::new();
+        Mt940::write_to(&mut out, &st).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains(":86:Fee\n:NS:01Bank specific\ncontinued here\n"), "{out}");
+        assert_eq!(Mt940::read_from(out.as_bytes()).unwrap(), st);
+    }
+
+    #[test]
+    fn test_references_round_trip() {
+        let input = ":20:ST1
+:25:ACC1
+:28C:1
+:60F:C251001EUR1000,00
+:61:2510011001D25,00NDDTE2E-1//BANK-77
+:86:/EREF/E2E-1/MARF/MANDATE-9/REMI/STRD/CUR/RF18539007547034/REMI/USTD//Invoice 42/
+:62F:C251001EUR975,00
+";
+        let st = Mt940::read_from(input.as_bytes()).unwrap();
+        let e = &st.entries[0];
+        assert_eq!(e.reference.as_deref(), Some("E2E-1"));
+        assert_eq!(e.servicer_reference.as_deref(), Some("BANK-77"));
+        assert_eq!(e.end_to_end_id.as_deref(), Some("E2E-1"));
+        assert_eq!(e.mandate_id.as_deref(), Some("MANDATE-9"));
+        assert_eq!(e.creditor_reference.as_deref(), Some("RF18539007547034"));
+        assert_eq!(e.remittance_info, ["Invoice 42"]);
+        assert_eq!(e.description, "Invoice 42");
+
+        let mut out = Vec::new();
+        Mt940::write_to(&mut out, &st).unwrap();
+        assert_eq!(Mt940::read_from(out.as_slice()).unwrap(), st);
+
+        let bad = input.replace("RF18", "RF19");
+        assert!(Mt940::read_from(bad.as_bytes()).is_err());
     }
 
     #[test]
     fn test_parse_transaction_61(){
         let s: &str = "2510011001C100,00NTRFNONREF";
         let tx=parse_transaction_61(s).unwrap();
-        assert_eq!(tx.0.date_yyymmdd, "251001");
-        assert_eq!(tx.0.description, "");
-        assert_eq!(tx.0.amount, "100,00");
-        assert_eq!(tx.0.is_credit, true);
-        assert_eq!(tx.0.reference, "");
-        assert_eq!(tx.0.type_code, "NTRFNONREF");
+        assert_eq!(tx.value_date, Date::new(2025, 10, 1).unwrap());
+        assert_eq!(tx.booking_date, Date::new(2025, 10, 1).unwrap());
+        assert_eq!(tx.info, Info86::default());
+        assert_eq!(tx.amount, Money::new(10000, 2));
+        assert!(tx.is_credit);
+        assert_eq!(tx.reference, "NONREF");
+        assert_eq!(tx.type_code, "NTRF");
+
+        let tx = parse_transaction_61("2510011001D5,00NCHGREF42").unwrap();
+        assert_eq!(tx.type_code, "NCHG");
+        assert_eq!(tx.reference, "REF42");
+        assert_eq!(tx.bank_reference, None);
+        assert!(parse_transaction_61("2510011001D5,00X").is_err());
+
+        // No entry date, funds code, type `S` with a message type, both references.
+        let tx = parse_transaction_61("251231RDR1234,5S103CUST-7//BANK-99").unwrap();
+        assert_eq!(tx.booking_date, tx.value_date);
+        assert!(tx.is_credit && tx.reversal);
+        assert_eq!(tx.funds_code, Some('R'));
+        assert_eq!(tx.amount, Money::parse("1234.5").unwrap());
+        assert_eq!(tx.type_code, "S103");
+        assert_eq!(tx.reference, "CUST-7");
+        assert_eq!(tx.bank_reference.as_deref(), Some("BANK-99"));
+
+        let tx = parse_transaction_61("2601020101CE7,00FCHKNONREF").unwrap();
+        assert_eq!(tx.booking_date, Date::new(2026, 1, 1).unwrap());
+        assert_eq!((tx.funds_code, tx.type_code.as_str()), (Some('E'), "FCHK"));
+        assert!(parse_transaction_61("251001X5,00NTRF").is_err());
+        assert!(parse_transaction_61("251001C,NTRF").is_err());
+    }
+
+    #[test]
+    fn test_empty_reference_is_none() {
+        let tx = parse_transaction_61("251001C5,00NTRF//BANK-1").unwrap();
+        assert_eq!(tx.reference, "");
+        assert_eq!(Entry::from((&tx, "EUR")).reference, None);
+
+        let tx = parse_transaction_61("251001C5,00NTRFCUST-1").unwrap();
+        assert_eq!(Entry::from((&tx, "EUR")).reference.as_deref(), Some("CUST-1"));
+    }
+
+    #[test]
+    fn test_supplementary_details_round_trip() {
+        let input = ":20:ST1
+:25:ACC1
+:28C:1
+:60F:C251001EUR10,00
+:61:251001CR5,00NTRFCUST-1//BANK-1
+/OCMT/USD6,00/ Wire from abroad
+:86:Payment
+:62F:C251001EUR15,00
+";
+        let st = Mt940::read_from(input.as_bytes()).unwrap();
+        let e = &st.entries[0];
+        assert_eq!(e.funds_code, Some('R'));
+        assert_eq!(e.reference.as_deref(), Some("CUST-1"));
+        assert_eq!(e.servicer_reference.as_deref(), Some("BANK-1"));
+        assert_eq!(e.instructed_amount.as_ref().map(|a| a.currency.as_str()), Some("USD"));
+        assert_eq!(e.supplementary_details.as_deref(), Some("Wire from abroad"));
+
+        let mut out = Vec::new();
+        Mt940::write_to(&mut out, &st).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains(":61:2510011001CR5,00NTRFCUST-1//BANK-1\n/OCMT/USD6,00/Wire from abroad\n"), "{out}");
+        assert_eq!(Mt940::read_from(out.as_bytes()).unwrap(), st);
+    }
+
+    #[test]
+    fn test_transaction_code_round_trip() {
+        let input = ":20:ST1
+:25:ACC1
+:28C:1
+:60F:C251001EUR1000,00
+:61:2510011001D25,00NCHGREF42
+:86:Fee
+:62F:C251001EUR975,00
+";
+        let st = Mt940::read_from(input.as_bytes()).unwrap();
+        let entry = &st.entries[0];
+        assert_eq!(entry.description, "Fee");
+        let code = entry.transaction_code.as_ref().unwrap();
+        assert_eq!(code.swift.as_deref(), Some("NCHG"));
+        assert_eq!(code.family.as_deref(), Some("MDOP"));
+
+        let mut out = Vec::new();
+        Mt940::write_to(&mut out, &st).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains(":61:2510011001D25,00NCHGREF42\n"), "{out}");
+        assert_eq!(Mt940::read_from(out.as_bytes()).unwrap(), st);
     }
 }
\ No newline at end of file
@@ -0,0 +1,281 @@
+use crate::adapter::identifier::is_bic;
+use crate::adapter::statement::FinEnvelope;
+
+/// Punctuation of the SWIFT X character set, besides letters, digits, space and line breaks.
+const CHARSET_X_PUNCTUATION: &str = "/-?:().,'+";
+
+/// Letter-by-letter transliteration of the Bank of Russia for SWIFT messages
+/// (the `RUR6` scheme). Lower case letters stand for Cyrillic letters without
+/// a Latin counterpart, so the mapping is reversible.
+const CYRILLIC: &[(char, char)] = &[
+    ('А', 'A'), ('Б', 'B'), ('В', 'V'), ('Г', 'G'), ('Д', 'D'), ('Е', 'E'), ('Ё', 'o'),
+    ('Ж', 'J'), ('З', 'Z'), ('И', 'I'), ('Й', 'i'), ('К', 'K'), ('Л', 'L'), ('М', 'M'),
+    ('Н', 'N'), ('О', 'O'), ('П', 'P'), ('Р', 'R'), ('С', 'S'), ('Т', 'T'), ('У', 'U'),
+    ('Ф', 'F'), ('Х', 'H'), ('Ц', 'C'), ('Ч', 'c'), ('Ш', 'Q'), ('Щ', 'q'), ('Ъ', 'x'),
+    ('Ы', 'Y'), ('Ь', 'X'), ('Э', 'e'), ('Ю', 'u'), ('Я', 'w'),
+];
+
+/// Replacements for characters outside the X character set, written as source text.
+const REPLACEMENTS: &[(char, &str)] = &[
+    ('№', "N"), ('«', "'"), ('»', "'"), ('"', "'"), ('“', "'"), ('”', "'"), ('„', "'"),
+    ('–', "-"), ('—', "-"), ('_', "-"), (';', ","), ('\t', " "),
+];
+
+/// Substitute for characters that are neither Cyrillic, in the X character set nor in [`REPLACEMENTS`].
+const SUBSTITUTE: char = '.';
+
+/// Marks the start and end of Latin text in transliterated text; doubled for a literal apostrophe.
+const LATIN_MARK: char = '\'';
+
+/// Whether `c` belongs to the SWIFT X character set.
+pub fn is_charset_x(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == ' ' || c == '\r' || c == '\n' || CHARSET_X_PUNCTUATION.contains(c)
+}
+
+/// Transliterates `s` into the X character set.
+///
+/// Cyrillic letters (of either case) follow [`CYRILLIC`] and Latin letters are
+/// enclosed in apostrophes, so that [`detransliterate`] can tell them apart.
+/// Other characters outside the X character set are replaced deterministically.
+pub fn transliterate(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut latin = false;
+    for c in s.chars() {
+        match REPLACEMENTS.iter().find(|(from, _)| *from == c) {
+            Some((_, to)) => to.chars().for_each(|c| push_char(&mut out, &mut latin, c)),
+            None => push_char(&mut out, &mut latin, c),
+        }
+    }
+    if latin {
+        out.push(LATIN_MARK);
+    }
+    out
+}
+
+fn push_char(out: &mut String, latin: &mut bool, c: char) {
+    let upper = c.to_uppercase().next().unwrap_or(c);
+    if let Some((_, code)) = CYRILLIC.iter().find(|(cyr, _)| *cyr == upper) {
+        if std::mem::replace(latin, false) {
+            out.push(LATIN_MARK);
+        }
+        out.push(*code);
+    } else if c.is_ascii_alphabetic() {
+        if !std::mem::replace(latin, true) {
+            out.push(LATIN_MARK);
+        }
+        out.push(c);
+    } else if c == LATIN_MARK {
+        out.push_str("''");
+    } else if is_charset_x(c) {
+        out.push(c);
+    } else {
+        out.push(SUBSTITUTE);
+    }
+}
+
+/// Replaces every character of `s` outside the X character set: Cyrillic
+/// letters as in [`transliterate`] but without marking Latin text, others as
+/// listed in [`REPLACEMENTS`] or by a full stop. Not reversible.
+pub fn to_charset_x(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        let upper = c.to_uppercase().next().unwrap_or(c);
+        if is_charset_x(c) {
+            out.push(c);
+        } else if let Some((_, code)) = CYRILLIC.iter().find(|(cyr, _)| *cyr == upper) {
+            out.push(*code);
+        } else if let Some((_, to)) = REPLACEMENTS.iter().find(|(from, _)| *from == c) {
+            out.push_str(to);
+        } else {
+            out.push(SUBSTITUTE);
+        }
+    }
+    out
+}
+
+/// Reverses [`transliterate`]: text outside apostrophes is read as Cyrillic (in upper case).
+pub fn detransliterate(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() * 2);
+    let mut latin = false;
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == LATIN_MARK {
+            if chars.next_if_eq(&LATIN_MARK).is_some() {
+                out.push(LATIN_MARK);
+            } else {
+                latin = !latin;
+            }
+        } else if latin {
+            out.push(c);
+        } else {
+            out.push(CYRILLIC.iter().find(|(_, code)| *code == c).map_or(c, |(cyr, _)| *cyr));
+        }
+    }
+    out
+}
+
+/// Splits `{id:content}` blocks, whose content may hold nested blocks. The
+/// text block `{4:` ends the list, as its content follows on the next lines.
+fn fin_blocks(s: &str) -> Result<Vec<(&str, &str)>, String> {
+    let mut blocks = Vec::new();
+    let mut rest = s.trim();
+    while !rest.is_empty() {
+        let inner = rest.strip_prefix('{').ok_or_else(|| format!("expected `{{` at `{rest}`"))?;
+        let (id, body) = inner.split_once(':').ok_or_else(|| format!("block without identifier at `{rest}`"))?;
+        if id == "4" {
+            blocks.push((id, body));
+            break;
+        }
+        let mut depth = 1;
+        let end = body.char_indices().find(|&(_, c)| {
+            match c {
+                '{' => depth += 1,
+                '}' => depth -= 1,
+                _ => {}
+            }
+            depth == 0
+        });
+        let Some((end, _)) = end else { return Err(format!("unterminated block `{{{id}:`")) };
+        blocks.push((id, &body[..end]));
+        rest = body[end + 1..].trim_start();
+    }
+    Ok(blocks)
+}
+
+/// BIC of a 12-character logical terminal address: the BIC8, a terminal code and the branch.
+fn lt_bic(lt: &str) -> Result<String, String> {
+    match (lt.get(..8), lt.get(9..12)) {
+        (Some(bic8), Some("XXX")) if lt.len() == 12 => Ok(bic8.to_string()),
+        (Some(bic8), Some(branch)) if lt.len() == 12 => Ok(format!("{bic8}{branch}")),
+        _ => Err(format!("invalid logical terminal address `{lt}`")),
+    }
+}
+
+/// Logical terminal address of `bic`, with terminal code `A`.
+fn bic_lt(bic: &str) -> Result<String, String> {
+    if !is_bic(bic) {
+        return Err(format!("invalid BIC `{bic}`"));
+    }
+    Ok(format!("{}A{}", &bic[..8], if bic.len() == 11 { &bic[8..] } else { "XXX" }))
+}
+
+/// Reads the header blocks of a FIN message: basic `{1:}`, application `{2:}` and
+/// user `{3:}` header, up to the text block `{4:`.
+///
+/// In an output message (`{2:O...}`, delivered by SWIFT) the basic header holds
+/// the receiver and the message input reference the sender; in an input message
+/// (`{2:I...}`) it is the other way round.
+pub fn parse_fin_header(s: &str) -> Result<FinEnvelope, String> {
+    let mut envelope = FinEnvelope::default();
+    let mut own = None;
+    for (id, body) in fin_blocks(s)? {
+        match id {
+            "1" => own = Some(lt_bic(body.get(3..15).ok_or_else(|| format!("basic header too short: `{body}`"))?)?),
+            "2" => {
+                envelope.message_type = body.get(1..4).map(str::to_string);
+                let (direction, other) = match body.get(..1) {
+                    Some("O") => ("O", body.get(14..26)),
+                    Some("I") => ("I", body.get(4..16)),
+                    _ => return Err(format!("application header must start with `I` or `O`: `{body}`")),
+                };
+                let other = lt_bic(other.ok_or_else(|| format!("application header too short: `{body}`"))?)?;
+                if direction == "O" {
+                    envelope.sender = Some(other);
+                } else {
+                    envelope.receiver = Some(other);
+                }
+            }
+            "3" => {
+                let fields = fin_blocks(body)?;
+                envelope.mur = fields.iter().find(|(id, _)| *id == "108").map(|(_, v)| v.to_string());
+            }
+            _ => {}
+        }
+    }
+    if envelope.sender.is_some() {
+        envelope.receiver = own;
+    } else {
+        envelope.sender = own;
+    }
+    Ok(envelope)
+}
+
+/// Reads the checksum from the trailer blocks after the text block, e.g. `{5:{CHK:A1B2C3D4E5F6}}`.
+pub fn parse_fin_trailer(s: &str) -> Result<Option<String>, String> {
+    for (id, body) in fin_blocks(s)? {
+        if id == "5" {
+            let fields = fin_blocks(body)?;
+            return Ok(fields.iter().find(|(id, _)| *id == "CHK").map(|(_, v)| v.to_string()));
+        }
+    }
+    Ok(None)
+}
+
+/// Header blocks of an input message for `envelope`, ending with the opening of
+/// the text block. Input messages have no trailer: SWIFT adds it on delivery.
+pub fn fin_header(envelope: &FinEnvelope) -> Result<String, String> {
+    let sender = envelope.sender.as_deref().ok_or("FIN envelope without sender BIC")?;
+    let receiver = envelope.receiver.as_deref().ok_or("FIN envelope without receiver BIC")?;
+    let message_type = envelope.message_type.as_deref().unwrap_or("940");
+    let mut out = format!("{{1:F01{}0000000000}}{{2:I{message_type}{}N}}", bic_lt(sender)?, bic_lt(receiver)?);
+    if let Some(mur) = &envelope.mur {
+        out.push_str(&format!("{{3:{{108:{mur}}}}}"));
+    }
+    out.push_str("{4:");
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cyrillic_round_trip() {
+        let text = "Оплата по счёту 15 за ЩЕБЕНЬ, НДС не облагается";
+        let swift = transliterate(text);
+        assert_eq!(swift, "OPLATA PO ScoTU 15 ZA qEBENX, NDS NE OBLAGAETSw");
+        assert!(swift.chars().all(is_charset_x));
+        assert_eq!(detransliterate(&swift), text.to_uppercase());
+    }
+
+    #[test]
+    fn latin_text_is_quoted() {
+        let swift = transliterate("ООО Ромашка LLC, Invoice 7");
+        assert_eq!(swift, "OOO ROMAQKA 'LLC, Invoice 7'");
+        assert_eq!(detransliterate(&swift), "ООО РОМАШКА LLC, Invoice 7");
+        assert_eq!(detransliterate(&transliterate("O'Brien")), "O'Brien");
+    }
+
+    #[test]
+    fn characters_outside_charset_x_are_replaced() {
+        let swift = transliterate("Счёт №5 «Альфа» 100%; a_b");
+        assert_eq!(swift, "ScoT 'N5 '''ALXFA'' 100., 'a-b'");
+        assert!(swift.chars().all(is_charset_x));
+        assert_eq!(detransliterate(&swift), "СЧЁТ N5 'АЛЬФА' 100., a-b");
+    }
+
+    #[test]
+    fn identifiers_keep_latin_letters() {
+        assert_eq!(to_charset_x("Счет-42/A_1 «x»"), "ScET-42/A-1 'x'");
+        assert_eq!(to_charset_x("REF-1"), "REF-1");
+    }
+
+    #[test]
+    fn fin_header_round_trip() {
+        let output = "{1:F01BANKDEFFAXXX0001000123}{2:O9401200251001COBADEFFAXXX00010001232510011200N}{3:{108:MUR-1}}{4:";
+        let envelope = parse_fin_header(output).unwrap();
+        assert_eq!(envelope.sender.as_deref(), Some("COBADEFF"));
+        assert_eq!(envelope.receiver.as_deref(), Some("BANKDEFF"));
+        assert_eq!(envelope.message_type.as_deref(), Some("940"));
+        assert_eq!(envelope.mur.as_deref(), Some("MUR-1"));
+
+        let input = fin_header(&envelope).unwrap();
+        assert_eq!(input, "{1:F01COBADEFFAXXX0000000000}{2:I940BANKDEFFAXXXN}{3:{108:MUR-1}}{4:");
+        assert_eq!(parse_fin_header(&input).unwrap(), envelope);
+
+        assert_eq!(parse_fin_trailer("{5:{MAC:00000000}{CHK:A1B2C3D4E5F6}}").unwrap().as_deref(), Some("A1B2C3D4E5F6"));
+        assert!(parse_fin_header("{1:F01SHORT}{4:").is_err());
+        assert!(parse_fin_header("{2:X940}{4:").is_err());
+        assert!(fin_header(&FinEnvelope { sender: Some("COBADEFF".into()), ..Default::default() }).is_err());
+    }
+}
@@ -1,9 +1,12 @@
 use std::io::{BufRead, Write};
-use crate::adapter::adapter::{Adapter, Statement};
-use crate::adapter::errors::AdapterError;
+use crate::adapter::adapter::{Adapter, ReadOptions, ReadReport, Statement};
+use crate::adapter::errors::{map_parse_err, map_write_err, AdapterError, Location};
+use crate::adapter::money::Money;
+use crate::adapter::currency::{normalize_statement, scale};
 use serde::{Deserialize, Serialize};
-use crate::adapter::statement::{DebitCredit,Balance,Entry};
-use quick_xml::{de::from_reader, se::Serializer};
+use crate::adapter::statement::{Balance, BalanceType, Date, DebitCredit, Entry, EntryStatus};
+use quick_xml::{de::{from_reader, from_str}, se::Serializer, Reader};
+use quick_xml::events::Event;
 
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -15,6 +18,10 @@ struct XmlEntry {
     amount: String,
     currency: String,
     dc: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    status: Option<EntryStatus>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    reversal: bool,
     description: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     reference: Option<String>,
@@ -25,11 +32,14 @@ struct XmlBalance {
     date: String,
     amount: String,
     currency: String,
+    /// `C` or `D`; balances without a mark are credit balances.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    dc: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 struct Entries {
-    #[serde(rename = "entry")]
+    #[serde(rename = "entry", default)]
     items: Vec<XmlEntry>,
 }
 
@@ -51,106 +61,310 @@ struct XmlStatement {
 /// Converts between XML and internal `Statement` representation.
 pub struct XML;
 
+/// Root of a multi-statement document, wrapping several `<XmlStatement>` elements.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename = "statements")]
+struct XmlStatements {
+    #[serde(rename = "XmlStatement", default)]
+    items: Vec<XmlStatement>,
+}
+
+const BATCH_ROOT: &[u8] = b"statements";
+
 impl Adapter for XML {
     fn read_from<R: BufRead>(reader: R) -> Result<Statement, AdapterError> {
-        let x: XmlStatement = from_reader(reader).map_err(|e| AdapterError::ParseError(e.to_string()))?;
-        let opening: Option<Balance> = x.opening_balance.map(parse_xml_balance).transpose()?;
-        let closing: Option<Balance> = x.closing_balance.map(parse_xml_balance).transpose()?;
-        let mut entries = Vec::with_capacity(x.entries.items.len());
-
-        for e in x.entries.items {
-            let booking_date = e.booking_date;
-            let value_date = e.value_date;
-            let amount = e.amount;
-
-            entries.push(Entry {
-                booking_date,
-                value_date: value_date.unwrap_or("undefined".to_string()),
-                amount,
-                currency: e.currency,
-                description: e.description,
-                reference: e.reference,
-                kind: DebitCredit::Debit,
-            });
-        }
-
-        Ok(Statement {
-            id: x.statement_id.unwrap(),
-            account_id: x.account_id,
-            opening_balance: opening,
-            closing_balance: closing,
-            entries,
-        })
+        let x: XmlStatement = from_reader(reader).map_err(map_parse_err)?;
+        statement_from_xml(x, "/XmlStatement", &mut ReadReport::default())
     }
 
     fn write_to<W: Write>(mut writer: W, st: &Statement) -> Result<(), AdapterError> {
-        let opening = st.opening_balance.as_ref().map(|b| XmlBalance {
-            date: b.date_yyymmdd.clone(),
-            amount: b.amount.clone(),
-            currency: b.currency.clone(),
-        });
-
-        let closing = st.closing_balance.as_ref().map(|b| XmlBalance {
-            date: b.date_yyymmdd.clone(),
-            amount: b.amount.clone(),
-            currency: b.currency.clone(),
-        });
-
-        let items: Vec<XmlEntry> = st.entries.iter().map(|e| XmlEntry {
-            booking_date: e.booking_date.clone(),
-            value_date: Some(e.value_date.clone()),
-            amount: e.amount.clone(),
-            currency: e.currency.clone(),
-            dc: match e.kind { DebitCredit::Debit => "D".into(), DebitCredit::Credit => "C".into() },
-            description: e.description.clone(),
-            reference: e.reference.clone(),
-        }).collect();
-
-        let x = XmlStatement {
-            statement_id: Some(st.id.clone()),
-            account_id: st.account_id.clone(),
-            opening_balance: opening,
-            closing_balance: closing,
-            entries: Entries { items },
+        let raw = to_pretty_xml(&statement_to_xml(st))?;
+        Ok(writer.write_all(raw.as_bytes())?)
+    }
+
+    /// Accepts both a single `<XmlStatement>` document and a `<statements>` batch.
+    fn read_all<R: BufRead>(reader: R) -> Result<Vec<Statement>, AdapterError> {
+        Ok(Self::read_with(reader, &ReadOptions::default())?.statements)
+    }
+
+    /// Entries that cannot be read are rejected one by one; broken XML fails the read.
+    fn read_with<R: BufRead>(mut reader: R, options: &ReadOptions) -> Result<ReadReport, AdapterError> {
+        let mut raw = String::new();
+        reader.read_to_string(&mut raw)?;
+
+        let mut report = ReadReport::new(options.mode);
+        let documents = if root_name(&raw)?.as_slice() != BATCH_ROOT {
+            vec![(from_str(&raw).map_err(map_parse_err)?, "/XmlStatement".to_string())]
+        } else {
+            let batch: XmlStatements = from_str(&raw).map_err(map_parse_err)?;
+            batch.items.into_iter().enumerate()
+                .map(|(i, x)| (x, format!("/statements/XmlStatement[{}]", i + 1)))
+                .collect()
         };
+        for (x, path) in documents {
+            let mut st = statement_from_xml(x, &path, &mut report)?;
+            report.normalize(&mut st)?;
+            report.statements.push(st);
+        }
+        Ok(report)
+    }
+
+    /// Writes a single statement as is and several as a `<statements>` batch.
+    fn write_all<W: Write>(mut writer: W, statements: &[Statement]) -> Result<(), AdapterError> {
+        if let [st] = statements {
+            return Self::write_to(writer, st);
+        }
+        let batch = XmlStatements { items: statements.iter().map(statement_to_xml).collect() };
+        let raw = to_pretty_xml(&batch)?;
+        Ok(writer.write_all(raw.as_bytes())?)
+    }
+}
+
+/// Converts a deserialized statement; `path` locates it in the document for error messages.
+fn statement_from_xml(x: XmlStatement, path: &str, report: &mut ReadReport) -> Result<Statement, AdapterError> {
+    let opening_path = format!("{path}/opening_balance");
+    let closing_path = format!("{path}/closing_balance");
+    let opening: Option<Balance> = x.opening_balance.map(|b| parse_xml_balance(b, BalanceType::OpeningBooked, &opening_path)).transpose()?;
+    let closing: Option<Balance> = x.closing_balance.map(|b| parse_xml_balance(b, BalanceType::ClosingBooked, &closing_path)).transpose()?;
+    let mut entries = Vec::with_capacity(x.entries.items.len());
+
+    for (i, e) in x.entries.items.into_iter().enumerate() {
+        let entry_path = format!("{path}/entries/entry[{}]", i + 1);
+        match entry_from_xml(&e, &entry_path) {
+            Ok(entry) => entries.push(entry),
+            Err(err) => {
+                report.reject(err)?;
+                report.quarantine(Some(Location::Path(entry_path)), to_pretty_xml(&e)?);
+            }
+        }
+    }
+
+    let mut st = Statement {
+        id: x.statement_id.ok_or_else(|| AdapterError::missing("statement_id").at(Location::Path(path.to_string())))?,
+        account_id: x.account_id,
+        balances: opening.into_iter().chain(closing).collect(),
+        entries,
+        ..Default::default()
+    };
+    normalize_statement(&mut st, false).map_err(map_parse_err)?;
+    Ok(st)
+}
+
+fn entry_from_xml(e: &XmlEntry, path: &str) -> Result<Entry, AdapterError> {
+    let booking_date = Date::parse(&e.booking_date)
+        .map_err(|err| invalid_at(path, "booking_date", &e.booking_date, err))?;
+    let value_date = match e.value_date.as_deref() {
+        Some(v) => Some(Date::parse(v).map_err(|err| invalid_at(path, "value_date", v, err))?),
+        None => None,
+    };
+    let amount = Money::parse(&e.amount).map_err(|err| invalid_at(path, "amount", &e.amount, err))?;
+    let kind = parse_dc(&e.dc).map_err(|err| invalid_at(path, "dc", &e.dc, err))?;
 
-        let raw = to_pretty_xml(&x).map_err(|e| AdapterError::ParseError(format!("{e}")))?;
-        writer.write_all(raw.as_bytes()).map_err(|e|AdapterError::WriteError(e.to_string()))
+    Ok(Entry {
+        booking_date,
+        value_date: value_date.unwrap_or(booking_date),
+        amount,
+        currency: e.currency.clone(),
+        description: e.description.clone(),
+        reference: e.reference.clone(),
+        kind,
+        status: e.status.unwrap_or_default(),
+        reversal: e.reversal,
+        ..Default::default()
+    })
+}
+
+fn statement_to_xml(st: &Statement) -> XmlStatement {
+    let opening = st.opening_balance().map(|b| XmlBalance {
+        date: b.date.to_string(),
+        amount: scale(b.amount, &b.currency).to_string(),
+        currency: b.currency.clone(),
+        dc: Some(dc_mark(b.kind)),
+    });
+
+    let closing = st.closing_balance().map(|b| XmlBalance {
+        date: b.date.to_string(),
+        amount: scale(b.amount, &b.currency).to_string(),
+        currency: b.currency.clone(),
+        dc: Some(dc_mark(b.kind)),
+    });
+
+    let items: Vec<XmlEntry> = st.entries.iter().map(|e| XmlEntry {
+        booking_date: e.booking_date.to_string(),
+        value_date: Some(e.value_date.to_string()),
+        amount: scale(e.amount, &e.currency).to_string(),
+        currency: e.currency.clone(),
+        dc: dc_mark(e.kind),
+        status: (e.status != EntryStatus::Booked).then_some(e.status),
+        reversal: e.reversal,
+        description: e.description.clone(),
+        reference: e.reference.clone(),
+    }).collect();
+
+    XmlStatement {
+        statement_id: Some(st.id.clone()),
+        account_id: st.account_id.clone(),
+        opening_balance: opening,
+        closing_balance: closing,
+        entries: Entries { items },
     }
 }
 
-fn to_pretty_xml<T: Serialize>(value: &T) -> Result<String, quick_xml::Error> {
+fn dc_mark(kind: DebitCredit) -> String {
+    match kind { DebitCredit::Debit => "D".into(), DebitCredit::Credit => "C".into() }
+}
+
+fn parse_dc(dc: &str) -> Result<DebitCredit, String> {
+    match dc {
+        "C" => Ok(DebitCredit::Credit),
+        "D" => Ok(DebitCredit::Debit),
+        _ => Err("expected `C` or `D`".to_string()),
+    }
+}
+
+/// Local name of the document element.
+fn root_name(raw: &str) -> Result<Vec<u8>, AdapterError> {
+    let mut reader = Reader::from_str(raw);
+    loop {
+        match reader.read_event().map_err(map_parse_err)? {
+            Event::Start(e) | Event::Empty(e) => return Ok(e.local_name().as_ref().to_vec()),
+            Event::Eof => return Err(map_parse_err("empty XML document")),
+            _ => {}
+        }
+    }
+}
+
+fn to_pretty_xml<T: Serialize>(value: &T) -> Result<String, AdapterError> {
     let mut out = String::new();
     let mut ser = Serializer::new(&mut out);
     ser.indent(' ', 4);
-    value.serialize(ser).unwrap();
+    value.serialize(ser).map_err(map_write_err)?;
     Ok(out)
 }
 
-fn parse_xml_balance(b: XmlBalance) -> Result<Balance, AdapterError> {
+fn parse_xml_balance(b: XmlBalance, balance_type: BalanceType, path: &str) -> Result<Balance, AdapterError> {
     Ok(Balance {
-        kind: DebitCredit::Debit,
-        date_yyymmdd: b.date,
-        amount: b.amount,
+        balance_type,
+        kind: match &b.dc {
+            Some(dc) => parse_dc(dc).map_err(|e| invalid_at(path, "dc", dc, e))?,
+            None => DebitCredit::Credit,
+        },
+        date: Date::parse(&b.date).map_err(|e| invalid_at(path, "date", &b.date, e))?,
+        amount: Money::parse(&b.amount).map_err(|e| invalid_at(path, "amount", &b.amount, e))?,
         currency: b.currency,
     })
 }
 
+/// Error for an invalid `value` of the element `name` under `path`.
+fn invalid_at(path: &str, name: &str, value: &str, reason: String) -> AdapterError {
+    AdapterError::invalid(name, value, reason).at(Location::Path(format!("{path}/{name}")))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    #[test]
+    fn test_batch_round_trip() {
+        let st = |id: &str| Statement {
+            id: id.to_string(),
+            account_id: format!("ACC-{id}"),
+            ..Default::default()
+        };
+        let all = vec![st("1"), st("2")];
+
+        let mut out = Vec::new();
+        XML::write_all(&mut out, &all).unwrap();
+        let back = XML::read_all(out.as_slice()).unwrap();
+        assert_eq!(back.len(), 2);
+        assert_eq!(back[1].account_id, "ACC-2");
+
+        let mut out = Vec::new();
+        XML::write_all(&mut out, &all[..1]).unwrap();
+        assert_eq!(XML::read_all(out.as_slice()).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_entry_direction_and_status_round_trip() {
+        let st = Statement {
+            id: "1".to_string(),
+            entries: vec![
+                Entry { kind: DebitCredit::Credit, ..Default::default() },
+                Entry { kind: DebitCredit::Debit, status: EntryStatus::Pending, reversal: true, ..Default::default() },
+            ],
+            ..Default::default()
+        };
+        let mut out = Vec::new();
+        XML::write_to(&mut out, &st).unwrap();
+        let back = XML::read_from(out.as_slice()).unwrap();
+        let flags = |st: &Statement| st.entries.iter().map(|e| (e.kind, e.status, e.reversal)).collect::<Vec<_>>();
+        assert_eq!(flags(&back), flags(&st));
+    }
+
+    #[test]
+    fn test_errors_name_field_and_path() {
+        let err = XML::read_from("<XmlStatement><account_id>A</account_id><entries/></XmlStatement>".as_bytes()).unwrap_err();
+        assert_eq!(err.to_string(), "missing statement_id at /XmlStatement");
+
+        let batch = "<statements><XmlStatement><statement_id>S</statement_id><account_id>A</account_id><entries>\
+            <entry><booking_date>2025-10-01</booking_date><amount>1</amount><currency>EUR</currency><dc>X</dc><description/></entry>\
+            </entries></XmlStatement></statements>";
+        let err = XML::read_all(batch.as_bytes()).unwrap_err();
+        assert_eq!(err.to_string(), "invalid dc `X`: expected `C` or `D` at /statements/XmlStatement[1]/entries/entry[1]/dc");
+    }
+
+    #[test]
+    fn test_lenient_mode_skips_bad_entries() {
+        let doc = "<XmlStatement><statement_id>S</statement_id><account_id>A</account_id><entries>\
+            <entry><booking_date>2025-10-01</booking_date><amount>1</amount><currency>EUR</currency><dc>X</dc><description/></entry>\
+            <entry><booking_date>2025-10-01</booking_date><amount>2</amount><currency>EUR</currency><dc>C</dc><description/></entry>\
+            </entries></XmlStatement>";
+        let lenient = ReadOptions { mode: crate::adapter::adapter::Mode::Lenient, ..Default::default() };
+        let report = XML::read_with(doc.as_bytes(), &lenient).unwrap();
+        assert_eq!(report.statements[0].entries.len(), 1);
+        assert_eq!(report.warnings.len(), 1);
+        assert_eq!(report.rejected[0].location, Some(Location::Path("/XmlStatement/entries/entry[1]".into())));
+        assert!(report.rejected[0].raw.contains("<dc>X</dc>"), "{}", report.rejected[0].raw);
+    }
+
     #[test]
     fn test_parse_xml_balance() {
         let b= XmlBalance{
             date: "20251224".to_string(),
             amount: "999".to_string(),
             currency: "EUR".to_string(),
+            dc: None,
         };
-        let res = parse_xml_balance(b).unwrap();
+        let res = parse_xml_balance(b, BalanceType::OpeningBooked, "/XmlStatement/opening_balance").unwrap();
 
-        assert_eq!(res.date_yyymmdd, "20251224");
-        assert_eq!(res.amount, "999");
+        assert_eq!(res.date, Date::new(2025, 12, 24).unwrap());
+        assert_eq!(res.amount, Money::new(99900, 2));
         assert_eq!(res.currency, "EUR");
+        assert_eq!(res.kind, DebitCredit::Credit);
+    }
+
+    #[test]
+    fn test_balance_marks() {
+        let doc = "<XmlStatement><statement_id>S</statement_id><account_id>A</account_id>\
+            <opening_balance><date>2025-10-01</date><amount>10.00</amount><currency>EUR</currency><dc>D</dc></opening_balance>\
+            <closing_balance><date>2025-10-01</date><amount>5.00</amount><currency>EUR</currency></closing_balance><entries>\
+            <entry><booking_date>2025-10-01</booking_date><amount>15.00</amount><currency>EUR</currency><dc>C</dc><description/></entry>\
+            </entries></XmlStatement>";
+        let st = XML::read_from(doc.as_bytes()).unwrap();
+        assert_eq!(st.opening_balance().unwrap().kind, DebitCredit::Debit);
+        assert_eq!(st.closing_balance().unwrap().kind, DebitCredit::Credit);
+        assert!(crate::adapter::validation::reconcile(&st).is_empty());
+
+        let mut out = Vec::new();
+        XML::write_to(&mut out, &st).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("<currency>EUR</currency>\n        <dc>D</dc>\n    </opening_balance>"), "{out}");
+        let again = XML::read_from(out.as_bytes()).unwrap();
+        assert_eq!(again.opening_balance().unwrap().kind, DebitCredit::Debit);
+        assert_eq!(again.closing_balance().unwrap().kind, DebitCredit::Credit);
+
+        let bad = doc.replace("<dc>D</dc>", "<dc>X</dc>");
+        let err = XML::read_from(bad.as_bytes()).unwrap_err();
+        assert_eq!(err.to_string(), "invalid dc `X`: expected `C` or `D` at /XmlStatement/opening_balance/dc");
     }
 }
 
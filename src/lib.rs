@@ -6,7 +6,7 @@
 
 use std::io::{BufRead};
 use crate::adapter::adapter::Adapter;
-use crate::adapter::errors::{AdapterError,map_parse_err};
+use crate::adapter::errors::AdapterError;
 use crate::adapter::statement::Statement;
 use crate::format::csv::CSV;
 use crate::format::mt940::Mt940;
@@ -35,34 +35,32 @@ pub fn convert<R: BufRead>(
     input_format: FormatType,
     output_format: FormatType,
 ) -> Result<String, AdapterError> {
-    let statement: Statement;
-
-    match input_format {
+    let statements: Vec<Statement> = match input_format {
         FormatType::CSV => {
-            statement = CSV::read_from(reader)?;
+            CSV::read_all(reader)?
         }
         FormatType::MT940 =>{
-            statement = Mt940::read_from(reader)?;
+            Mt940::read_all(reader)?
         }
         FormatType::CAMT053 =>{
-            statement = XML::read_from(reader)?;
+            XML::read_all(reader)?
         }
-    }
+    };
 
     match output_format{
         FormatType::CSV => {
-            let file = File::create("output.csv").map_err(map_parse_err)?;
-            CSV::write_to(file, &statement)?;
+            let file = File::create("output.csv")?;
+            CSV::write_all(file, &statements)?;
             Ok("csv file was created.".to_string())
         }
         FormatType::MT940 => {
-            let file = File::create("output.mt940").map_err(map_parse_err)?;
-            Mt940::write_to(file, &statement)?;
+            let file = File::create("output.mt940")?;
+            Mt940::write_all(file, &statements)?;
             Ok("mt940 was created.".to_string())
         }
         FormatType::CAMT053 => {
-            let file = File::create("output.camt053").map_err(map_parse_err)?;
-            XML::write_to(file, &statement)?;
+            let file = File::create("output.camt053")?;
+            XML::write_all(file, &statements)?;
             Ok("camt053 was created.".to_string())
         }
     }
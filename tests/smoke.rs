@@ -5,7 +5,7 @@ use std::process::Command;
 use tempfile::tempdir;
 
 fn bin() -> Command {
-    Command::cargo_bin("parser").expect("binary 'parser' not found")
+    Command::new(assert_cmd::cargo::cargo_bin!("parser"))
 }
 
 #[test]
@@ -60,6 +60,45 @@ fn mt940_to_camt053_smoke() {
     assert!(xml.contains("BkToCstmrStmt")); // корневой блок camt.053
     assert!(xml.contains("<Stmt>"));
     assert!(xml.contains("<Ntry>"));
+    assert!(xml.contains(">100.00</Amt>"));
+    assert!(!xml.contains("100,00"));
+    assert!(xml.contains("<Dt>2025-10-01</Dt>"));
+    assert!(xml.contains("<Dt>2025-10-31</Dt>"));
+    assert!(xml.contains("<SubFmlyCd>DMCT</SubFmlyCd>"));
+    assert!(!xml.contains("type:NTRF"));
+}
+
+#[test]
+fn mt940_batch_to_camt053_keeps_every_statement() {
+    let mt940 = r#"
+:20:STATEMENT1
+:25:DE0012345678
+:60F:C251001EUR1000,00
+:61:2510011001C100,00NTRFNONREF
+:86:Salary October
+:62F:C251031EUR1100,00
+:20:STATEMENT2
+:25:DE0087654321
+:60F:C251001EUR50,00
+:62F:C251031EUR50,00
+"#;
+
+    let dir = tempdir().unwrap();
+    let input = dir.path().join("input.mt940");
+    fs::write(&input, mt940).unwrap();
+
+    let mut cmd = bin();
+    cmd.args([
+        "--in-format", "mt940",
+        "--out-format", "camt053",
+        "--input", input.to_str().unwrap(),
+    ]);
+
+    let out = cmd.assert().success().get_output().stdout.clone();
+    let xml = String::from_utf8(out).unwrap();
+    assert_eq!(xml.matches("<Stmt>").count(), 2);
+    assert!(xml.contains("<Id>STATEMENT2</Id>"));
+    assert!(xml.contains("DE0087654321"));
 }
 
 #[test]
@@ -143,5 +182,287 @@ fn camt053_to_csv_smoke() {
     let csv = fs::read_to_string(&output).unwrap();
     assert!(csv.contains("Salary October"));
     assert!(csv.contains("100"));
-    assert!(csv.contains("2025-10-01"));
+    assert!(csv.contains("01.10.2025"));
+}
+
+#[test]
+fn camt053_batch_expands_to_csv_rows() {
+    let camt = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Document xmlns="urn:iso:std:iso:20022:tech:xsd:camt.053.001.02">
+  <BkToCstmrStmt>
+    <Stmt>
+      <Id>STATEMENT1</Id>
+      <Acct><Id><IBAN>DE0012345678</IBAN></Id></Acct>
+      <Ntry>
+        <Amt Ccy="EUR">30.00</Amt>
+        <CdtDbtInd>CRDT</CdtDbtInd>
+        <BookgDt><Dt>2025-10-01</Dt></BookgDt>
+        <NtryDtls>
+          <Btch><NbOfTxs>2</NbOfTxs></Btch>
+          <TxDtls>
+            <AmtDtls><TxAmt><Amt Ccy="EUR">10.00</Amt></TxAmt></AmtDtls>
+            <RmtInf><Ustrd>Invoice A</Ustrd></RmtInf>
+          </TxDtls>
+          <TxDtls>
+            <AmtDtls><TxAmt><Amt Ccy="EUR">20.00</Amt></TxAmt></AmtDtls>
+            <RmtInf><Ustrd>Invoice B</Ustrd></RmtInf>
+          </TxDtls>
+        </NtryDtls>
+      </Ntry>
+    </Stmt>
+  </BkToCstmrStmt>
+</Document>
+"#;
+
+    let dir = tempdir().unwrap();
+    let input = dir.path().join("statement.xml");
+    fs::write(&input, camt).unwrap();
+
+    let output = bin()
+        .args(["--in-format", "camt053", "--out-format", "csv", "--expand-batches"])
+        .args(["--input", input.to_str().unwrap()])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let csv = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(csv.lines().count(), 3);
+    assert!(csv.contains("Invoice A") && csv.contains("10.00"));
+    assert!(csv.contains("Invoice B") && csv.contains("20.00"));
+}
+
+#[test]
+fn validate_fails_on_balance_mismatch() {
+    let mt940 = ":20:STATEMENT1
+:25:DE89370400440532013000
+:60F:C251001EUR1000,00
+:61:2510011001C100,00NTRFNONREF
+:86:Salary
+:62F:C251031EUR1200,00
+";
+    let dir = tempdir().unwrap();
+    let input = dir.path().join("input.mt940");
+    fs::write(&input, mt940).unwrap();
+
+    bin()
+        .args(["--in-format", "mt940", "--validate", "--input", input.to_str().unwrap()])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("closing balance is 1200.00"));
+
+    fs::write(&input, mt940.replace("1200,00", "1100,00")).unwrap();
+    bin()
+        .args(["--in-format", "mt940", "--validate", "--input", input.to_str().unwrap()])
+        .assert()
+        .success()
+        .stderr(predicate::str::is_empty());
+}
+
+#[test]
+fn strict_rejects_unknown_currency() {
+    let mt940 = ":20:STATEMENT1
+:25:DE89370400440532013000
+:60F:C251001RUR1000,00
+:62F:C251031RUR1000,00
+";
+    let dir = tempdir().unwrap();
+    let input = dir.path().join("input.mt940");
+    fs::write(&input, mt940).unwrap();
+
+    bin()
+        .args(["--in-format", "mt940", "--out-format", "xml", "--strict", "--input", input.to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("RUB").and(predicate::str::contains("RUR").not()));
+
+    fs::write(&input, mt940.replace("RUR", "ABC")).unwrap();
+    bin()
+        .args(["--in-format", "mt940", "--out-format", "xml", "--strict", "--input", input.to_str().unwrap()])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("unknown currency `ABC`"));
+}
+
+#[test]
+fn transliterate_keeps_mt940_in_charset_x() {
+    let mt940 = ":20:STATEMENT1
+:25:40702810600014448120
+:60F:C251001RUB1000,00
+:61:2510011001D100,00NTRFNONREF
+:86:Оплата по счёту №5
+:62F:C251031RUB900,00
+";
+    let dir = tempdir().unwrap();
+    let input = dir.path().join("input.mt940");
+    let output = dir.path().join("output.mt940");
+    fs::write(&input, mt940).unwrap();
+
+    bin()
+        .args(["--in-format", "mt940", "--out-format", "mt940", "--transliterate"])
+        .args(["--input", input.to_str().unwrap(), "--output", output.to_str().unwrap()])
+        .assert()
+        .success();
+    let written = fs::read_to_string(&output).unwrap();
+    assert!(written.contains(":86:OPLATA PO ScoTU 'N5'"), "{written}");
+
+    bin()
+        .args(["--in-format", "mt940", "--out-format", "xml", "--transliterate", "--input", output.to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("ОПЛАТА ПО СЧЁТУ N5"));
+}
+
+#[test]
+fn lenient_mode_quarantines_bad_transactions() {
+    let mt940 = ":20:ST1
+:25:ACC1
+:60F:C251001EUR10,00
+:61:2510011001C5,00NTRFREF1
+:86:first
+:61:2510321001C7,00NTRFREF2
+:86:second
+:62F:C251001EUR15,00
+";
+    let dir = tempdir().unwrap();
+    let input = dir.path().join("input.mt940");
+    let rejected = dir.path().join("rejected.mt940");
+    fs::write(&input, mt940).unwrap();
+
+    bin()
+        .args(["--in-format", "mt940", "--out-format", "csv", "--input", input.to_str().unwrap()])
+        .assert()
+        .failure();
+
+    bin()
+        .args(["--in-format", "mt940", "--out-format", "csv", "--lenient", "--input", input.to_str().unwrap()])
+        .args(["--quarantine", rejected.to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("REF1").and(predicate::str::contains("REF2").not()))
+        .stderr(predicate::str::contains("warning: invalid :61: `2510321001C7,00NTRFREF2`"));
+    assert_eq!(fs::read_to_string(&rejected).unwrap(), ":61:2510321001C7,00NTRFREF2\n:86:second\n");
+}
+
+#[test]
+fn fin_envelope_is_read_and_written() {
+    let fin = "{1:F01BANKDEFFAXXX0001000123}{2:O9401200251001COBADEFFAXXX00010001232510011200N}{4:
+:20:ST1
+:25:ACC1
+:60F:C251001EUR10,00
+:62F:C251001EUR10,00
+-}{5:{CHK:A1B2C3D4E5F6}}{1:F01BANKDEFFAXXX0001000124}{2:O9401200251001COBADEFFAXXX00010001242510011200N}{4:
+:20:ST2
+:25:ACC1
+:60F:C251001EUR10,00
+:62F:C251001EUR10,00
+-}{5:{CHK:FFFFFFFFFFFF}}
+";
+    let dir = tempdir().unwrap();
+    let input = dir.path().join("input.fin");
+    fs::write(&input, fin).unwrap();
+
+    let output = bin()
+        .args(["--in-format", "mt940", "--out-format", "mt940", "--fin-envelope", "--input", input.to_str().unwrap()])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let written = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(written.matches("{1:F01COBADEFFAXXX0000000000}{2:I940BANKDEFFAXXXN}{4:").count(), 2, "{written}");
+
+    bin()
+        .args(["--in-format", "mt940", "--out-format", "mt940", "--fin-envelope", "--input", input.to_str().unwrap()])
+        .args(["--fin-receiver", "BANKDEFF"])
+        .assert()
+        .success();
+    bin()
+        .args(["--in-format", "mt940", "--out-format", "mt940", "--fin-receiver", "BANKDEFF"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn errors_quote_the_offending_line() {
+    let dir = tempdir().unwrap();
+    let input = dir.path().join("input.mt940");
+    fs::write(&input, ":20:ST1\n:25:ACC1\n:60F:C25x001EUR10,00\n:62F:C251001EUR10,00\n").unwrap();
+
+    bin()
+        .args(["--in-format", "mt940", "--out-format", "xml", "--input", input.to_str().unwrap()])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("error: invalid :60F: `C25x001EUR10,00`"))
+        .stderr(predicate::str::contains("input.mt940:3:6"))
+        .stderr(predicate::str::contains("3 | :60F:C25x001EUR10,00\n  |      ^"));
+
+    bin()
+        .args(["--in-format", "mt940", "--out-format", "xml", "--input", dir.path().join("missing").to_str().unwrap()])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::starts_with("error: I/O error:"));
+}
+
+#[test]
+fn mt940_is_written_with_crlf_and_terminators() {
+    let mt940 = ":20:ST1\n:25:ACC1\n:60F:C251001EUR10,00\n:61:2510011001C5,00NTRFREF1\n:86:Salary\n:62F:C251001EUR15,00\n";
+    let dir = tempdir().unwrap();
+    let input = dir.path().join("input.mt940");
+    fs::write(&input, mt940).unwrap();
+
+    let output = bin()
+        .args(["--in-format", "mt940", "--out-format", "mt940", "--crlf", "--input", input.to_str().unwrap()])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let written = String::from_utf8(output.stdout).unwrap();
+    assert!(written.starts_with(":20:ST1\r\n:25:ACC1\r\n:28C:1\r\n"), "{written}");
+    assert!(written.contains(":61:2510011001C5,00NTRFREF1\r\n"), "{written}");
+    assert!(written.ends_with(":62F:C251001EUR15,00\r\n-\r\n"), "{written}");
+}
+
+#[test]
+fn bundled_mt940_sample_is_valid() {
+    bin()
+        .args(["--in-format", "mt940", "--validate", "--input", "examples/data.mt940"])
+        .assert()
+        .success()
+        .stderr("");
+}
+
+#[test]
+fn bundled_csv_sample_is_valid() {
+    bin()
+        .args(["--in-format", "csv", "--validate", "--input", "examples/data.csv"])
+        .assert()
+        .success()
+        .stderr("");
+}
+
+#[test]
+fn csv_without_balances_converts_to_readable_mt940() {
+    let dir = tempdir().unwrap();
+    let output = dir.path().join("output.mt940");
+    bin()
+        .args(["--in-format", "csv", "--out-format", "mt940", "--input", "examples/data.csv"])
+        .args(["--output", output.to_str().unwrap()])
+        .assert()
+        .failure()
+        .stderr("error: missing :60F:\n");
+    bin()
+        .args(["--in-format", "csv", "--out-format", "mt940", "--derive-balances", "--input", "examples/data.csv"])
+        .args(["--output", output.to_str().unwrap()])
+        .assert()
+        .success();
+
+    let written = fs::read_to_string(&output).unwrap();
+    assert!(written.contains(":60F:C240220RUB0,00\n"), "{written}");
+    bin()
+        .args(["--in-format", "mt940", "--out-format", "csv", "--input", output.to_str().unwrap()])
+        .assert()
+        .success();
+    let output = bin()
+        .args(["--in-format", "mt940", "--validate", "--input", output.to_str().unwrap()])
+        .output()
+        .unwrap();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(!stderr.contains("closing balance is"), "{stderr}");
 }